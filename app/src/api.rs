@@ -1,8 +1,9 @@
+use pob::diff::BuildDiff;
 use reqwasm::http::{Request, Response};
 use serde::{Deserialize, Serialize};
 use shared::{
     model::{Paste, PasteSummary},
-    PasteId, UserPasteId,
+    PasteId, Realm, UserPasteId,
 };
 
 use crate::{Error, Result};
@@ -44,6 +45,35 @@ pub async fn create_paste(content: CreatePaste<'_>) -> Result<PasteId> {
     Ok(resp.json::<PasteId>().await?)
 }
 
+/// Imports a character from pathofexile.com, returns the Path of Building export of the character.
+#[allow(dead_code)] // Only used in !SSR
+pub async fn get_character(realm: Realm, account: &str, character: &str) -> Result<String> {
+    let _in_flight = crate::progress::start_request();
+    let encode =
+        |value| percent_encoding::utf8_percent_encode(value, percent_encoding::NON_ALPHANUMERIC);
+    let path = format!(
+        "/pob/character/{}/{}/{}",
+        realm.as_str(),
+        encode(account),
+        encode(character)
+    );
+
+    let resp = Request::get(&path).send().await?;
+
+    if resp.status() == 404 {
+        return Err(Error::NotFound(
+            "character",
+            format!("{account}/{character}"),
+        ));
+    }
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.text().await?)
+}
+
 pub async fn get_paste(id: &PasteId) -> Result<Paste> {
     let _in_flight = crate::progress::start_request();
     let path = id.to_json_url();
@@ -61,6 +91,23 @@ pub async fn get_paste(id: &PasteId) -> Result<Paste> {
     Ok(resp.json().await?)
 }
 
+pub async fn get_diff(before: &PasteId, after: &PasteId) -> Result<BuildDiff> {
+    let _in_flight = crate::progress::start_request();
+    let path = before.to_diff_json_url(after);
+
+    let resp = Request::get(&path).send().await?;
+
+    if resp.status() == 404 {
+        return Err(Error::NotFound("paste", format!("{before} or {after}")));
+    }
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.json().await?)
+}
+
 pub async fn delete_paste(id: &UserPasteId) -> Result<()> {
     let _in_flight = crate::progress::start_request();
     let resp = Request::delete(&format!("/api/internal/paste/{id}"))
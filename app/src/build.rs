@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 
-use pob::{PathOfBuilding, SerdePathOfBuilding, TreeSpec};
+use pob::{Loadout, ParseWarning, PathOfBuilding, SerdePathOfBuilding, TreeSpec};
 use shared::model::data;
 use sycamore::reactive::{create_rc_signal, RcSignal};
 
@@ -9,8 +9,12 @@ pub struct Build {
     pub content: String,
     pob: SerdePathOfBuilding,
     data: data::Data,
+    /// Parts of the build which failed to parse and were skipped.
+    warnings: Vec<ParseWarning>,
 
     active_tree: RcSignal<usize>,
+    active_item_set: RcSignal<usize>,
+    active_skill_set: RcSignal<usize>,
 }
 
 impl Build {
@@ -22,27 +26,59 @@ impl Build {
         &self.data
     }
 
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
     pub fn active_tree(&self) -> &RcSignal<usize> {
         &self.active_tree
     }
+
+    pub fn active_item_set(&self) -> &RcSignal<usize> {
+        &self.active_item_set
+    }
+
+    pub fn active_skill_set(&self) -> &RcSignal<usize> {
+        &self.active_skill_set
+    }
+
+    /// Switches tree, items and skills to the loadout.
+    pub fn set_loadout(&self, loadout: &Loadout) {
+        self.active_tree.set(loadout.tree_spec);
+        self.active_item_set.set(loadout.item_set);
+        self.active_skill_set.set(loadout.skill_set);
+    }
 }
 
 impl Build {
     // TODO: this needs a rewrite, accepting additional data from /json is awkward
     pub fn new(content: String, data: data::Data) -> crate::Result<Self> {
-        let pob = SerdePathOfBuilding::from_export(&content)?;
+        let (pob, warnings) = SerdePathOfBuilding::from_export_lenient(&content)?;
 
         let active_tree = pob
             .tree_specs()
             .iter()
             .position(|spec| spec.active)
             .unwrap_or(0);
+        let active_item_set = pob
+            .item_sets()
+            .iter()
+            .position(|set| set.is_selected)
+            .unwrap_or(0);
+        let active_skill_set = pob
+            .skill_sets()
+            .iter()
+            .position(|set| set.is_selected)
+            .unwrap_or(0);
 
         Ok(Self {
             content,
             pob,
             data,
+            warnings,
             active_tree: create_rc_signal(active_tree),
+            active_item_set: create_rc_signal(active_item_set),
+            active_skill_set: create_rc_signal(active_skill_set),
         })
     }
 
@@ -97,11 +97,31 @@ pub fn CreatePaste<G: Html>(cx: Scope, props: CreatePasteProps) -> View<G> {
             return None;
         }
 
-        match SerdePathOfBuilding::from_export(value) {
-            Ok(pob) => Some(pob),
+        // A passive tree link alone is shared as a build with only the tree.
+        if is_tree_url(value) {
+            return match SerdePathOfBuilding::from_tree_url(value) {
+                Ok(pob) => Some(pob),
+                Err(err) => {
+                    tracing::info!("{}", err);
+                    error.set("Invalid Passive Tree Link".to_owned());
+                    None
+                }
+            };
+        }
+
+        // Partially broken builds can be shared, their warnings are shown on the paste.
+        match SerdePathOfBuilding::from_export_lenient(value) {
+            Ok((pob, _)) => Some(pob),
             Err(err) => {
                 tracing::info!("{}", err);
-                error.set("Invalid PoB Code".to_owned());
+                // Mistakes the user can fix get a more specific message.
+                let message = match err {
+                    pob::Error::MixedBase64 | pob::Error::Truncated | pob::Error::NotABuild => {
+                        format!("Invalid PoB Code, {err}")
+                    }
+                    _ => "Invalid PoB Code".to_owned(),
+                };
+                error.set(message);
                 None
             }
         }
@@ -128,7 +148,17 @@ pub fn CreatePaste<G: Html>(cx: Scope, props: CreatePasteProps) -> View<G> {
 
         error.set("".to_owned());
 
-        let value = value.get();
+        // Tree links are uploaded as a build export.
+        let value = match (is_tree_url(&value.get()), &*pob.get()) {
+            (true, Some(pob)) => match pob.to_export() {
+                Ok(export) => std::rc::Rc::new(export),
+                Err(err) => {
+                    error.set(err.to_string());
+                    return;
+                }
+            },
+            _ => value.get(),
+        };
         let as_user = *as_user.get();
         let title = title.get();
         let custom_title = custom_title.get();
@@ -330,3 +360,9 @@ pub fn CreatePaste<G: Html>(cx: Scope, props: CreatePasteProps) -> View<G> {
         }
     }
 }
+
+fn is_tree_url(value: &str) -> bool {
+    value.trim().starts_with("https://")
+        && (value.contains("/passive-skill-tree/")
+            || value.contains("/fullscreen-passive-skill-tree/"))
+}
@@ -0,0 +1,94 @@
+use shared::Realm;
+use sycamore::prelude::*;
+
+use crate::{api, svg, utils::memo_cond};
+
+#[component]
+pub fn ImportCharacter<G: Html>(cx: Scope) -> View<G> {
+    let realm = create_signal(cx, Realm::Pc.as_str().to_owned());
+    let account = create_signal(cx, String::new());
+    let character = create_signal(cx, String::new());
+    let loading = create_signal(cx, false);
+    let error = create_signal(cx, String::new());
+
+    let btn_disabled = create_memo(cx, || {
+        *loading.get() || account.get().trim().is_empty() || character.get().trim().is_empty()
+    });
+
+    let submit = move |ev: web_sys::Event| {
+        // Stop the browser from submitting the form and reloading the page.
+        ev.prevent_default();
+
+        let realm = realm.get().parse().unwrap_or(Realm::Pc);
+        let account = account.get().trim().to_owned();
+        let character = character.get().trim().to_owned();
+
+        let future = async move {
+            // Characters are shared like any other build, as an anonymous paste.
+            let result = match api::get_character(realm, &account, &character).await {
+                Ok(content) => {
+                    let params = api::CreatePaste {
+                        as_user: false,
+                        content: &content,
+                        title: &character,
+                        custom_id: "",
+                        id: None,
+                        pinned: false,
+                        private: false,
+                    };
+                    api::create_paste(params).await
+                }
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(id) => sycamore_router::navigate(&id.to_url()),
+                Err(err) => {
+                    loading.set(false);
+                    error.set(err.to_string());
+                    tracing::info!("{:?}", err);
+                }
+            }
+        };
+
+        error.set(String::new());
+        loading.set(true);
+        sycamore::futures::spawn_local_scoped(cx, future);
+    };
+
+    let btn_content = memo_cond!(cx, loading, svg::SPINNER, "Import");
+
+    let realms = View::new_fragment(
+        Realm::ALL
+            .into_iter()
+            .map(|realm| view! { cx, option(value=realm.as_str()) { (realm.name()) } })
+            .collect(),
+    );
+
+    view! { cx,
+        div(class="flex flex-col gap-y-1") {
+            div(class="dark:text-slate-200 text-slate-800") { "Import a character from pathofexile.com" }
+            form(class="flex flex-wrap items-center justify-end gap-3") {
+                select(class="input", aria-label="Realm", bind:value=realm) { (realms) }
+                input(
+                    class="input flex-1 basis-[10rem]",
+                    placeholder="Account name",
+                    bind:value=account
+                ) {}
+                input(
+                    class="input flex-1 basis-[10rem]",
+                    placeholder="Character name",
+                    bind:value=character
+                ) {}
+                button(
+                    class="btn btn-primary min-w-[100px]",
+                    type="submit",
+                    disabled=*btn_disabled.get(),
+                    on:click=submit,
+                    dangerously_set_inner_html=&btn_content.get()
+                ) {}
+            }
+            div(class="text-sm text-red-500 empty:hidden") { (*error.get()) }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod create_paste;
+mod import_character;
 mod import_pastebin;
 mod login_status;
 mod paste_history;
@@ -9,6 +10,9 @@ mod pob_gear_preview;
 mod pob_gems;
 mod pob_item;
 mod pob_item_set;
+mod pob_leveling_guide;
+mod pob_loadout_select;
+mod pob_notes;
 mod pob_tree_preview;
 mod popup;
 mod static_popup;
@@ -16,6 +20,7 @@ mod tree_node;
 mod view_paste;
 
 pub use self::create_paste::{CreatePaste, CreatePasteProps};
+pub use self::import_character::ImportCharacter;
 pub use self::import_pastebin::ImportPastebin;
 pub use self::login_status::LoginStatus;
 pub use self::paste_history::PasteHistory;
@@ -26,6 +31,9 @@ pub use self::pob_gear_preview::PobGearPreview;
 pub use self::pob_gems::PobGems;
 pub use self::pob_item::PobItem;
 pub use self::pob_item_set::PobItemSet;
+pub use self::pob_leveling_guide::PobLevelingGuide;
+pub use self::pob_loadout_select::PobLoadoutSelect;
+pub use self::pob_notes::PobNotes;
 pub use self::pob_tree_preview::PobTreePreview;
 pub use self::popup::Popup;
 pub use self::static_popup::StaticPopup;
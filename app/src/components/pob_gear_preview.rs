@@ -1,10 +1,10 @@
-use itertools::Itertools;
-use pob::PathOfBuilding;
+use pob::{GearStat, GearStats, ItemSet, PathOfBuilding};
 use sycamore::prelude::*;
 use wasm_bindgen::JsCast;
 
 use super::{PobColoredSelect, PobItem, PobItemSet, Popup};
 use crate::build::Build;
+use crate::utils::IteratorExt;
 
 #[component]
 pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
@@ -21,8 +21,19 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
 
     let item_sets = create_ref(cx, build.item_sets());
 
-    let item_set = item_sets.iter().find_or_first(|set| set.is_selected);
-    let item_set = create_signal(cx, item_set);
+    let item_set = create_memo(cx, move || {
+        let index = *build.active_item_set().get();
+        item_sets.get(index).or_else(|| item_sets.first())
+    });
+    let swap = create_signal(cx, false);
+    // Every item set remembers its active weapon set.
+    create_effect(cx, move || {
+        swap.set(
+            item_set
+                .get()
+                .map_or(false, |set| set.use_second_weapon_set),
+        );
+    });
 
     let options = item_sets
         .iter()
@@ -32,13 +43,31 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
                 .map(|s| s.to_owned())
                 .unwrap_or_else(|| item_set.id.to_string())
         })
-        .collect();
+        .collect::<Vec<_>>();
+    let options = create_ref(cx, options);
 
-    let selected = item_sets.iter().position(|set| set.is_selected);
     let on_change = move |index| {
         let Some(index) = index else { return };
-        item_set.set(item_sets.get(index));
+        build.active_item_set().set(index);
     };
+    // Re-rendered when the item set is changed by a different selection, e.g. the loadout.
+    let select = create_memo(cx, move || {
+        let selected = Some(*build.active_item_set().get());
+        view! { cx,
+            PobColoredSelect(options=options.clone(), selected=selected, label="Select gear set", on_change=on_change)
+        }
+    });
+
+    let has_swap = create_memo(cx, move || {
+        item_set.get().map_or(false, |set| {
+            set.gear.weapon1_swap.is_some() || set.gear.weapon2_swap.is_some()
+        })
+    });
+    let toggle_swap = move |_: web_sys::Event| swap.set(!*swap.get());
+    let swap_label = create_memo(cx, move || match *swap.get() {
+        true => "Weapon Set II",
+        false => "Weapon Set I",
+    });
 
     let items = create_memo(cx, move || {
         let item_set = item_set.get();
@@ -46,11 +75,17 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
             PobItemSet(
                 build_=build,
                 item_set=*item_set,
+                swap=*swap.get(),
                 current_item=current_item,
             )
         }
     });
 
+    let gear_stats = create_memo(cx, move || match *item_set.get() {
+        Some(item_set) => render_gear_stats(cx, item_set, *swap.get()),
+        None => View::empty(),
+    });
+
     let mouseover = |event: web_sys::Event| {
         let a = event
             .target()
@@ -70,8 +105,21 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     view! { cx,
         Popup(attach=attach, parent=None) { (&*popup.get()) }
         div(class=select_classes) {
-            PobColoredSelect(options=options, selected=selected, label="Select gear set", on_change=on_change)
+            (&*select.get())
         }
+        (if *has_swap.get() {
+            view! { cx,
+                div(class="text-right -mb-5") {
+                    button(
+                        on:click=toggle_swap,
+                        title="Toggle weapon swap",
+                        class="hover:underline hover:cursor-pointer px-3 text-sm",
+                    ) { (swap_label.get()) }
+                }
+            }
+        } else {
+            View::empty()
+        })
         div(class="flex flex-col justify-center mt-5 sm:px-3",
             on:mouseover=mouseover,
             on:mouseout=mouseout,
@@ -82,5 +130,123 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
                 (&*items.get())
             }
         }
+        (&*gear_stats.get())
+    }
+}
+
+fn render_gear_stats<G: Html>(cx: Scope, item_set: &ItemSet, swap: bool) -> View<G> {
+    let slots = item_set.gear_stats_with(swap);
+
+    let mut total = GearStats::default();
+    for slot in &slots {
+        total += &slot.stats;
+    }
+    if total.is_empty() {
+        return View::empty();
+    }
+
+    let columns = total.iter().map(|(stat, _)| stat).collect::<Vec<_>>();
+
+    let header = columns
+        .iter()
+        .map(|&stat| {
+            view! { cx,
+                th(class="px-1 text-right", title=stat.name()) { (short_name(stat)) }
+            }
+        })
+        .collect_view();
+
+    let row = |name: String, title: String, stats: &GearStats, class: &'static str| {
+        let cells = columns
+            .iter()
+            .map(|&stat| {
+                let value = format_gear_stat(stat, stats.get(stat));
+                view! { cx, td(class=format!("px-1 text-right {}", stat_color(stat))) { (value) } }
+            })
+            .collect_view();
+        view! { cx,
+            tr(class=class) {
+                td(class="pr-2 text-slate-500 whitespace-nowrap", title=title) { (name) }
+                (cells)
+            }
+        }
+    };
+
+    let rows = slots
+        .iter()
+        .filter(|slot| !slot.stats.is_empty())
+        .map(|slot| {
+            // Mods which are not translated are missing from the stats, list them on hover.
+            let title = match slot.untranslated.is_empty() {
+                true => String::new(),
+                false => format!("Not included:\n{}", slot.untranslated.join("\n")),
+            };
+            row(slot.slot.to_owned(), title, &slot.stats, "")
+        })
+        .collect_view();
+    let total = row(
+        "Total".to_owned(),
+        String::new(),
+        &total,
+        "border-t border-solid font-bold",
+    );
+
+    view! { cx,
+        details(class="mt-3 sm:px-3 text-sm") {
+            summary(class="cursor-pointer dark:text-slate-100 text-slate-900") { "Gear Stats" }
+            div(class="overflow-x-auto") {
+                table(class="mt-2 w-full") {
+                    thead {
+                        tr(class="text-slate-500") {
+                            th(class="pr-2 text-left") { "Slot" }
+                            (header)
+                        }
+                    }
+                    tbody {
+                        (rows)
+                        (total)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn short_name(stat: GearStat) -> &'static str {
+    match stat {
+        GearStat::Life => "Life",
+        GearStat::EnergyShield => "ES",
+        GearStat::Mana => "Mana",
+        GearStat::Strength => "Str",
+        GearStat::Dexterity => "Dex",
+        GearStat::Intelligence => "Int",
+        GearStat::FireResistance => "Fire",
+        GearStat::ColdResistance => "Cold",
+        GearStat::LightningResistance => "Light",
+        GearStat::ChaosResistance => "Chaos",
+        GearStat::MovementSpeed => "MS",
+        GearStat::SpellSuppression => "Supp",
+    }
+}
+
+fn stat_color(stat: GearStat) -> &'static str {
+    match stat {
+        GearStat::Life | GearStat::Strength => "text-rose-500",
+        GearStat::EnergyShield => "text-cyan-200",
+        GearStat::Mana | GearStat::Intelligence => "text-blue-400",
+        GearStat::Dexterity => "text-lime-400",
+        GearStat::FireResistance => "text-orange-500 dark:text-orange-400",
+        GearStat::ColdResistance => "text-blue-400",
+        GearStat::LightningResistance => "text-yellow-600 dark:text-yellow-300",
+        GearStat::ChaosResistance => "text-fuchsia-500",
+        GearStat::MovementSpeed | GearStat::SpellSuppression => "",
+    }
+}
+
+fn format_gear_stat(stat: GearStat, value: f64) -> String {
+    match (value, stat.is_percent()) {
+        (v, _) if v == 0.0 => String::new(),
+        (v, true) => format!("{v}%"),
+        (v, false) => format!("{v}"),
     }
 }
@@ -22,8 +22,6 @@ pub fn PobGems<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     }
     let show_select = skill_sets.len() > 1;
 
-    let content = create_signal(cx, view! { cx, });
-
     let options = skill_sets
         .iter()
         .map(|ss| {
@@ -31,19 +29,22 @@ pub fn PobGems<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
                 .map(|s| s.to_owned())
                 .unwrap_or_else(|| ss.id.to_string())
         })
-        .collect();
-    let selected = skill_sets.iter().position(|ss| ss.is_selected);
+        .collect::<Vec<_>>();
+    let options = create_ref(cx, options);
+
+    let content = create_memo(cx, move || {
+        let index = *build.active_skill_set().get();
+        match build.skill_sets().into_iter().nth(index) {
+            Some(ss) => render_skills(cx, ss.skills, build.data()),
+            None => View::empty(),
+        }
+    });
+
     let on_change = move |index| {
         let Some(index) = index else { return };
-        if let Some(ss) = build.skill_sets().into_iter().nth(index) {
-            content.set(render_skills::<G>(cx, ss.skills, build.data()));
-        }
+        build.active_skill_set().set(index);
     };
 
-    if let Some(ss) = skill_sets.into_iter().find(|ss| ss.is_selected) {
-        content.set(render_skills(cx, ss.skills, build.data()));
-    }
-
     let attach = create_signal(cx, None);
     let popup = create_signal(cx, View::default());
 
@@ -64,15 +65,19 @@ pub fn PobGems<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     };
     let mouseout = |_: web_sys::Event| attach.set(None);
 
-    let select = match show_select {
-        true => {
-            view! { cx, PobColoredSelect(options=options, selected=selected, label="Select skill set", on_change=on_change) }
+    // Re-rendered when the skill set is changed by a different selection, e.g. the loadout.
+    let select = create_memo(cx, move || {
+        if !show_select {
+            return View::empty();
         }
-        false => View::default(),
-    };
+        let selected = Some(*build.active_skill_set().get());
+        view! { cx,
+            PobColoredSelect(options=options.clone(), selected=selected, label="Select skill set", on_change=on_change)
+        }
+    });
 
     view! { cx,
-        (select)
+        (&*select.get())
 
         Popup(attach=attach, parent=None) { (&*popup.get()) }
         div(class="columns-2xs gap-5 sm:ml-3 leading-[1.35rem]") {
@@ -82,7 +87,7 @@ pub fn PobGems<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
 }
 
 #[derive(Debug)]
-struct PopupGem {
+pub struct PopupGem {
     id: String,
     name: String,
     quality: u8,
@@ -90,7 +95,7 @@ struct PopupGem {
 }
 
 impl PopupGem {
-    fn from_element(element: &web_sys::Element) -> Option<Self> {
+    pub fn from_element(element: &web_sys::Element) -> Option<Self> {
         let id = element
             .get_attribute("data-gem-id")
             .filter(|id| !id.is_empty())?;
@@ -111,7 +116,7 @@ impl PopupGem {
     }
 }
 
-fn render_popup<'a, G: GenericNode + Html>(
+pub fn render_popup<'a, G: GenericNode + Html>(
     cx: Scope<'a>,
     gem: PopupGem,
     data: &'a data::Gem,
@@ -266,12 +271,10 @@ fn render_skill<'a, G: Html>(cx: Scope<'a>, skill: Skill<'a>, data: &'a data::Da
                 .unwrap_or("<unknown>")
                 .to_owned();
 
-            let quality = match gem.quality_id {
-                Some("Alternate1") => "Anomalous ",
-                Some("Alternate2") => "Divergent ",
-                Some("Alternate3") => "Phantasmal ",
-                _ => "",
-            };
+            let quality = gem
+                .alternate_quality
+                .map(|quality| format!("{} ", quality.name()))
+                .unwrap_or_default();
 
             let mut color = "";
             let mut bold = false;
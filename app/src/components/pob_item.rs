@@ -62,6 +62,22 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
     if item.energy_shield > 0 {
         stats.push(render_property(cx, "Energy Shield:", item.energy_shield))
     }
+    if item.ward > 0 {
+        stats.push(render_property(cx, "Ward:", item.ward))
+    }
+    if let Some(radius) = item.radius {
+        stats.push(render_property(cx, "Radius:", radius.name()))
+    }
+    if !item.sockets.is_empty() {
+        stats.push(render_property(
+            cx,
+            "Sockets:",
+            render_sockets(item.sockets),
+        ))
+    }
+    if let Some(requirements) = render_requirements(item.requirements) {
+        stats.push(render_property(cx, "Requires", requirements))
+    }
 
     let mut unmet = Vec::new();
     if item.split {
@@ -127,6 +143,35 @@ fn render_property<G: Html>(
     }
 }
 
+fn render_sockets(sockets: pob::Sockets) -> String {
+    sockets
+        .groups()
+        .map(|group| group.colors().map(|color| color.name()).join("-"))
+        .join(" ")
+}
+
+fn render_requirements(requirements: pob::Requirements) -> Option<String> {
+    if requirements.is_empty() {
+        return None;
+    }
+
+    let requirements = [
+        ("Level", requirements.level as u16),
+        ("Str", requirements.strength),
+        ("Dex", requirements.dexterity),
+        ("Int", requirements.intelligence),
+    ]
+    .into_iter()
+    .filter(|(_, value)| *value > 0)
+    .map(|(name, value)| match name {
+        "Level" => format!("{name} {value}"),
+        _ => format!("{value} {name}"),
+    })
+    .join(", ");
+
+    Some(requirements)
+}
+
 fn rarity_str(rarity: pob::Rarity) -> &'static str {
     match rarity {
         pob::Rarity::Normal => "White",
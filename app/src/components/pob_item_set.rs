@@ -13,6 +13,7 @@ pub fn PobItemSet<'a, 'b, G: Html>(
     cx: Scope<'a>,
     build_: &'a Build,
     item_set: Option<&'a pob::ItemSet<'a>>,
+    swap: bool,
     current_item: &'a Signal<Option<pob::Item<'a>>>,
 ) -> View<G> {
     let build = build_;
@@ -20,7 +21,7 @@ pub fn PobItemSet<'a, 'b, G: Html>(
         .map(|set| &set.gear)
         .unwrap_or_else(|| create_ref(cx, pob::Gear::default()));
 
-    let slots = slots(gear)
+    let slots = slots(gear, swap)
         .into_iter()
         .map(move |(name, item)| render_item_str(cx, name, item, current_item))
         .collect_view();
@@ -54,9 +55,21 @@ pub fn PobItemSet<'a, 'b, G: Html>(
             )
         });
 
+        // Jewels socketed into the currently hidden weapon set.
+        let hidden = gear
+            .slots
+            .iter()
+            .filter(
+                |slot| matches!(slot.socketed_in(), Some(parent) if parent.starts_with("Weapon")),
+            )
+            .filter(|slot| slot.is_weapon_swap() != swap)
+            .map(|slot| slot.item)
+            .collect_vec();
+
         gear.sockets
-            .clone()
-            .into_iter()
+            .iter()
+            .copied()
+            .filter(|item| !hidden.contains(item))
             .filter_map(|item| pob::Item::parse(item).ok())
             .chain(tree_sockets)
             .map(move |item| render_item(cx, "socket", Some(item), current_item))
@@ -159,10 +172,15 @@ fn item_image_name<'a>(item: &pob::Item<'a>) -> &'a str {
     }
 }
 
-fn slots<'a>(gear: &pob::Gear<'a>) -> [(&'static str, Option<&'a str>); 10] {
+fn slots<'a>(gear: &pob::Gear<'a>, swap: bool) -> [(&'static str, Option<&'a str>); 10] {
+    let (weapon1, weapon2) = match swap {
+        true => (gear.weapon1_swap, gear.weapon2_swap),
+        false => (gear.weapon1, gear.weapon2),
+    };
+
     [
-        ("weapon1", gear.weapon1),
-        ("weapon2", gear.weapon2),
+        ("weapon1", weapon1),
+        ("weapon2", weapon2),
         ("helmet", gear.helmet),
         ("body_armour", gear.body_armour),
         ("gloves", gear.gloves),
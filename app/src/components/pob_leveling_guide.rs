@@ -0,0 +1,136 @@
+use pob::leveling::{GemStep, LevelSource, SkillStep, Step, TreeStep};
+use pob::PathOfBuildingExt;
+use sycamore::prelude::*;
+
+use crate::build::Build;
+use crate::utils::IteratorExt;
+
+/// Tree specs and skill sets of the build as a step by step leveling guide.
+#[component]
+pub fn PobLevelingGuide<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+    let Some(guide) = build.leveling_guide() else {
+        return View::empty();
+    };
+
+    let steps = guide
+        .steps
+        .into_iter()
+        .map(|step| render_step(cx, build, step))
+        .collect_view();
+
+    view! { cx,
+        ol(class="flex flex-col gap-5 sm:ml-3 text-sm") { (steps) }
+    }
+}
+
+fn render_step<'a, G: Html>(cx: Scope<'a>, build: &'a Build, step: Step<'_>) -> View<G> {
+    let title = step.title().unwrap_or("Untitled").to_owned();
+    let level = match step.level {
+        Some(level) if level.source == LevelSource::Title => format!("Level {}", level.level),
+        Some(level) => format!("Level ~{}", level.level),
+        None => String::new(),
+    };
+
+    let tree_spec = step.tree.as_ref().map(|tree| tree.tree_spec);
+    let skill_set = step.skills.as_ref().map(|skills| skills.skill_set);
+    let show = move |_: web_sys::Event| {
+        if let Some(tree_spec) = tree_spec {
+            build.active_tree().set(tree_spec);
+        }
+        if let Some(skill_set) = skill_set {
+            build.active_skill_set().set(skill_set);
+        }
+    };
+
+    let tree = step
+        .tree
+        .map(|tree| render_tree(cx, tree))
+        .unwrap_or_default();
+    let skills = step
+        .skills
+        .map(|skills| render_skills(cx, skills))
+        .unwrap_or_default();
+
+    view! { cx,
+        li(class="flex flex-col gap-1") {
+            div(class="flex items-baseline gap-3 border-b border-solid border-slate-500/30") {
+                span(class="text-slate-500 w-20 shrink-0") { (level) }
+                span(class="flex-auto font-bold dark:text-slate-100 text-slate-900") { (title) }
+                button(
+                    on:click=show,
+                    title="Show the tree and gems of this step",
+                    class="hover:underline hover:cursor-pointer text-xs",
+                ) { "Show" }
+            }
+            div(class="sm:ml-[5.75rem] flex flex-col gap-1") {
+                (tree)
+                (skills)
+            }
+        }
+    }
+}
+
+fn render_tree<G: Html>(cx: Scope, tree: TreeStep) -> View<G> {
+    let summary = format!(
+        "{} passive points, {} allocated, {} refunded",
+        tree.points,
+        tree.added.len(),
+        tree.removed.len()
+    );
+    let notables = match tree.notables_added.is_empty() {
+        true => View::empty(),
+        false => {
+            let notables = tree.notables_added.join(", ");
+            view! { cx, div(class="text-xs text-slate-500") { (notables) } }
+        }
+    };
+
+    view! { cx,
+        div { (summary) }
+        (notables)
+    }
+}
+
+fn render_skills<G: Html>(cx: Scope, skills: SkillStep) -> View<G> {
+    let added = skills
+        .gems_added
+        .into_iter()
+        .map(|gem| render_gem(cx, gem))
+        .collect_view();
+    let removed = skills
+        .gems_removed
+        .into_iter()
+        .map(|name| {
+            let name = name.to_owned();
+            view! { cx, li(class="line-through text-slate-500") { (name) } }
+        })
+        .collect_view();
+
+    view! { cx,
+        ul(class="flex flex-col gap-0.5") {
+            (added)
+            (removed)
+        }
+    }
+}
+
+fn render_gem<G: Html>(cx: Scope, gem: GemStep) -> View<G> {
+    let name = gem.name.to_owned();
+    let required_level = gem
+        .required_level
+        .map(|level| format!("(level {level})"))
+        .unwrap_or_default();
+    let vendor = gem
+        .vendors
+        .first()
+        .map(|vendor| format!("Act {}, {} after {}", vendor.act, vendor.npc, vendor.quest))
+        .unwrap_or_default();
+
+    view! { cx,
+        li(class="flex flex-wrap gap-x-2") {
+            span(class="text-lime-500") { "+ " (name) }
+            span(class="text-slate-500") { (required_level) }
+            span(class="text-xs text-slate-500 self-center") { (vendor) }
+        }
+    }
+}
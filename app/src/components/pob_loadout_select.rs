@@ -0,0 +1,31 @@
+use pob::PathOfBuildingExt;
+use sycamore::prelude::*;
+
+use super::PobColoredSelect;
+use crate::build::Build;
+
+/// Switches tree, gear and gems together, only shown for builds with multiple loadouts.
+#[component]
+pub fn PobLoadoutSelect<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+    let loadouts = build.loadouts();
+    if loadouts.len() <= 1 {
+        return view! { cx, };
+    }
+
+    let options = loadouts.iter().map(|l| l.title.to_owned()).collect();
+    let selected = loadouts.iter().position(|l| l.is_selected);
+
+    let loadouts = create_ref(cx, loadouts);
+    let on_change = move |index: Option<usize>| {
+        if let Some(loadout) = index.and_then(|index| loadouts.get(index)) {
+            build.set_loadout(loadout);
+        }
+    };
+
+    view! { cx,
+        div(class="flex items-center gap-2 -mt-16 mb-8") {
+            span(class="text-sm dark:text-slate-400 text-slate-600") { "Loadout" }
+            PobColoredSelect(options=options, selected=selected, label="Select loadout", on_change=on_change)
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use pob::notes::{Block, Heading, Inline, LinkKind, Notes, Span};
+use sycamore::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::pob_colored_text::{color_to_style, Style};
+use super::pob_gems::{render_popup, PopupGem};
+use super::{PobItem, Popup};
+use crate::build::Build;
+use crate::utils::{links::is_domain_whitelisted, IteratorExt};
+
+/// Build notes with a table of contents and hoverable gems and items of the build.
+#[component]
+pub fn PobNotes<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+    let notes = create_ref(cx, Notes::from_build(build.pob()));
+
+    let toc = render_toc(cx, notes);
+    let content = render_blocks(cx, &notes.blocks);
+
+    let attach = create_signal(cx, None);
+    let popup = create_signal(cx, View::default());
+
+    let mouseover = move |event: web_sys::Event| {
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+        let Some(element) = target.as_ref() else {
+            attach.set(None);
+            return;
+        };
+
+        let view = if let Some(gem) = PopupGem::from_element(element) {
+            build
+                .data()
+                .gems
+                .get(&gem.id)
+                .map(|data| render_popup(cx, gem, data))
+        } else {
+            element
+                .get_attribute("data-item")
+                .and_then(|name| item_by_name(notes, &name))
+                .and_then(|item| pob::Item::parse(item).ok())
+                .map(|item| view! { cx, PobItem(item) })
+        };
+
+        match view {
+            Some(view) => {
+                popup.set(view);
+                attach.set(target);
+            }
+            None => attach.set(None),
+        }
+    };
+    let mouseout = |_: web_sys::Event| attach.set(None);
+
+    view! { cx,
+        Popup(attach=attach, parent=None) { (&*popup.get()) }
+        (toc)
+        div(
+            class="text-xs break-words whitespace-pre-wrap font-mono sm:ml-3",
+            on:mouseover=mouseover,
+            on:mouseout=mouseout,
+        ) {
+            (content)
+        }
+    }
+}
+
+fn render_toc<'a, G: Html>(cx: Scope<'a>, notes: &'a Notes<'a>) -> View<G> {
+    let headings = notes.headings().collect::<Vec<_>>();
+    if headings.len() < 2 {
+        return View::empty();
+    }
+
+    let entries = headings
+        .into_iter()
+        .map(|heading| {
+            let href = format!("#{}", heading.anchor);
+            let title = heading.title.clone();
+            let class = match heading.level {
+                1 => "",
+                2 => "ml-3",
+                _ => "ml-6",
+            };
+            view! { cx, li(class=class) { a(href=href, class="hover:underline") { (title) } } }
+        })
+        .collect_view();
+
+    view! { cx,
+        nav(class="text-sm mb-4 sm:ml-3") {
+            ul(class="flex flex-col gap-0.5") { (entries) }
+        }
+    }
+}
+
+fn render_blocks<'a, G: Html>(cx: Scope<'a>, blocks: &'a [Block<'a>]) -> View<G> {
+    let mut views = Vec::new();
+    let mut list = Vec::new();
+
+    for block in blocks {
+        if let Block::ListItem(spans) = block {
+            let spans = render_spans(cx, spans);
+            list.push(view! { cx, li { (spans) } });
+            continue;
+        }
+
+        if !list.is_empty() {
+            let items = View::new_fragment(std::mem::take(&mut list));
+            views.push(view! { cx, ul(class="list-disc ml-5 whitespace-normal") { (items) } });
+        }
+
+        views.push(match block {
+            Block::Heading(heading) => render_heading(cx, heading),
+            Block::Line(spans) => {
+                let spans = render_spans(cx, spans);
+                view! { cx, div { (spans) } }
+            }
+            Block::Empty => view! { cx, div(class="h-4") {} },
+            Block::ListItem(_) => unreachable!(),
+        });
+    }
+
+    if !list.is_empty() {
+        let items = View::new_fragment(list);
+        views.push(view! { cx, ul(class="list-disc ml-5 whitespace-normal") { (items) } });
+    }
+
+    View::new_fragment(views)
+}
+
+fn render_heading<'a, G: Html>(cx: Scope<'a>, heading: &'a Heading<'a>) -> View<G> {
+    let id = heading.anchor.clone();
+    let spans = render_spans(cx, &heading.spans);
+    let class = match heading.level {
+        1 => "text-base font-bold mt-3 mb-1 scroll-mt-4",
+        2 => "text-sm font-bold mt-2 mb-1 scroll-mt-4",
+        _ => "font-bold mt-1 scroll-mt-4",
+    };
+    view! { cx, div(id=id, class=class) { (spans) } }
+}
+
+fn render_spans<'a, G: Html>(cx: Scope<'a>, spans: &'a [Span<'a>]) -> View<G> {
+    spans
+        .iter()
+        .map(|span| {
+            let inline = render_inline(cx, span.inline);
+            match color_to_style(span.color) {
+                Style::Class(class) => view! { cx, span(class=class) { (inline) } },
+                Style::Style(style) => view! { cx, span(style=style) { (inline) } },
+                Style::None => inline,
+            }
+        })
+        .collect_view()
+}
+
+fn render_inline<'a, G: Html>(cx: Scope<'a>, inline: Inline<'a>) -> View<G> {
+    match inline {
+        Inline::Text(text) => {
+            let text = text.to_owned();
+            view! { cx, (text) }
+        }
+        Inline::Link(link) if is_domain_whitelisted(link.domain()) => {
+            let href = link.url.to_owned();
+            let text = link.url.to_owned();
+            let badge = match link.kind {
+                LinkKind::YouTube => "YouTube",
+                LinkKind::PoeNinja => "poe.ninja",
+                LinkKind::Pobbin => "pobb.in",
+                LinkKind::Other => "",
+            };
+            view! { cx,
+                (if badge.is_empty() {
+                    View::empty()
+                } else {
+                    view! { cx, span(class="mr-1 px-1 rounded bg-slate-500/20 text-[0.65rem]") { (badge) } }
+                })
+                a(href=href, class="underline", target="_blank", rel="noopener") { (text) }
+            }
+        }
+        Inline::Link(link) => {
+            let text = link.url.to_owned();
+            view! { cx, (text) }
+        }
+        Inline::Gem(gem) => {
+            let name = gem.name.to_owned();
+            let id = gem.gem_id.unwrap_or_default().to_owned();
+            view! { cx,
+                span(
+                    class="underline decoration-dotted cursor-help",
+                    data-gem-id=id,
+                    data-gem-level=gem.level,
+                    data-gem-quality=gem.quality,
+                ) { (name) }
+            }
+        }
+        Inline::Item(item) => {
+            let name = item.name.to_owned();
+            let data_item = name.clone();
+            view! { cx,
+                span(class="underline decoration-dotted cursor-help", data-item=data_item) { (name) }
+            }
+        }
+    }
+}
+
+fn item_by_name<'a>(notes: &'a Notes<'a>, name: &str) -> Option<&'a str> {
+    notes
+        .blocks
+        .iter()
+        .flat_map(|block| match block {
+            Block::Heading(heading) => heading.spans.as_slice(),
+            Block::ListItem(spans) | Block::Line(spans) => spans.as_slice(),
+            Block::Empty => &[],
+        })
+        .find_map(|span| match span.inline {
+            Inline::Item(item) if item.name == name => Some(item.item),
+            _ => None,
+        })
+}
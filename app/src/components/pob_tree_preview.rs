@@ -15,6 +15,8 @@ use crate::{
 
 #[derive(Debug)]
 struct Tree<'build> {
+    /// Index of the spec in [`PathOfBuilding::tree_specs`].
+    index: usize,
     name: String,
     tree_url: String,
     svg_url: &'static str,
@@ -53,11 +55,13 @@ struct Override<'build> {
 pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     let trees = build
         .trees()
-        .filter_map(|(nodes, spec)| {
+        .enumerate()
+        .filter_map(|(index, (nodes, spec))| {
             let tree_url = get_tree_url(&spec)?;
             let svg_url = SvgTree::url(&spec);
             let overrides = extract_overrides(&spec.overrides);
             Some(Tree {
+                index,
                 name: spec.title.unwrap_or("<Default>").to_owned(),
                 tree_url,
                 svg_url,
@@ -73,7 +77,10 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     }
 
     let trees = create_ref(cx, trees);
-    let current_tree = create_signal(cx, trees.iter().find_or_first(|t| t.spec.active).unwrap());
+    let current_tree = create_memo(cx, move || {
+        let index = *build.active_tree().get();
+        trees.iter().find_or_first(|t| t.index == index).unwrap()
+    });
     let tree_loaded = create_signal(cx, false);
     let node_ref = create_node_ref(cx);
 
@@ -142,11 +149,11 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
         });
     });
 
-    // TODO: this updates the currently active tree, but it doesn't read from it
-    // the select would need to be updated as well if the tree changes, kinda tricky...
-    let select = render_select(cx, trees, move |index, tree| {
-        current_tree.set(tree);
-        build.active_tree().set(index);
+    // Re-rendered when the tree is changed by a different selection, e.g. the loadout.
+    let select = create_memo(cx, move || {
+        render_select(cx, trees, *build.active_tree().get(), move |tree| {
+            build.active_tree().set(tree.index);
+        })
     });
 
     let nodes = create_memo(cx, move || render_nodes(cx, &current_tree.get()));
@@ -186,7 +193,7 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     view! { cx,
         Popup(attach=attach, parent=Some(node_ref)) { (&*popup.get()) }
         div(class="flex flex-wrap align-center") {
-            div(class="h-9 max-w-full") { (select) }
+            div(class="h-9 max-w-full") { (&*select.get()) }
             div(class="flex-1 text-right sm:mr-3 whitespace-nowrap") { (&*tree_level.get()) }
         }
         div(class="grid grid-cols-10 gap-3") {
@@ -405,20 +412,21 @@ fn render_mastery<G: GenericNode + Html>(cx: Scope, node: &data::Node) -> View<G
 fn render_select<'a, G: GenericNode + Html, F>(
     cx: Scope<'a>,
     trees: &'a [Tree],
+    active: usize,
     on_change: F,
 ) -> View<G>
 where
-    F: Fn(usize, &'a Tree) + 'a,
+    F: Fn(&'a Tree) + 'a,
 {
     if trees.len() <= 1 {
         return view! { cx, };
     }
 
     let options = trees.iter().map(|t| t.name.clone()).collect();
-    let selected = trees.iter().position(|t| t.spec.active);
+    let selected = trees.iter().position(|t| t.index == active);
     let on_change = move |index| {
         if let Some(index) = index {
-            on_change(index, &trees[index])
+            on_change(&trees[index])
         }
     };
 
@@ -1,4 +1,7 @@
-use ::pob::{PathOfBuilding, PathOfBuildingExt};
+use ::pob::{
+    lint::{Finding, Severity},
+    ParseWarning, PathOfBuilding, PathOfBuildingExt,
+};
 use shared::PasteId;
 use sycamore::{futures::spawn_local_scoped, prelude::*};
 use wasm_bindgen::{JsCast, JsValue};
@@ -7,7 +10,7 @@ use web_sys::HtmlTextAreaElement;
 use super::PobGearPreview;
 use crate::{
     build::Build,
-    components::{PobColoredText, PobGems, PobTreePreview},
+    components::{PobGems, PobLevelingGuide, PobLoadoutSelect, PobNotes, PobTreePreview},
     consts::IMG_ONERROR_HIDDEN,
     pob::{self, Element},
     storage::Storage,
@@ -41,6 +44,21 @@ impl CopyState {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Notes,
+    LevelingGuide,
+}
+
+impl Tab {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Notes => "Notes",
+            Self::LevelingGuide => "Leveling Guide",
+        }
+    }
+}
+
 #[component]
 pub fn ViewPaste<'a, G: Html>(
     cx: Scope<'a>,
@@ -61,11 +79,61 @@ pub fn ViewPaste<'a, G: Html>(
 
     let open_in_pob_url = id.to_pob_open_url();
 
-    let notes = view_cond!(cx, !build.notes().is_empty(), {
+    let has_notes = !build.notes().is_empty();
+    let has_leveling_guide = build.leveling_guide().is_some();
+    let tab = create_signal(
+        cx,
+        if has_notes {
+            Tab::Notes
+        } else {
+            Tab::LevelingGuide
+        },
+    );
+    let tabs = [
+        (Tab::Notes, has_notes),
+        (Tab::LevelingGuide, has_leveling_guide),
+    ]
+    .into_iter()
+    .filter(|(_, available)| *available)
+    .map(|(t, _)| {
+        let class = create_memo(cx, move || match *tab.get() == t {
+            true => "border-b-2 border-current -mb-px",
+            false => "text-slate-500 hover:cursor-pointer hover:underline",
+        });
+        view! { cx, button(class=class.get(), on:click=move |_| tab.set(t)) { (t.name()) } }
+    })
+    .collect_view();
+    let tab_content = create_memo(cx, move || match *tab.get() {
+        Tab::Notes => view! { cx, PobNotes(build) },
+        Tab::LevelingGuide => view! { cx, PobLevelingGuide(build) },
+    });
+    let notes = view_cond!(cx, has_notes || has_leveling_guide, {
         div(class="flex-auto") {
-            h2(class="text-lg dark:text-slate-100 text-slate-900 mb-2 mt-24 border-b border-solid") { "Notes" }
-            pre(class="text-xs break-words whitespace-pre-wrap font-mono sm:ml-3") {
-                PobColoredText(text=build.notes(), links=true)
+            h2(class="flex gap-6 text-lg dark:text-slate-100 text-slate-900 mb-2 mt-24 border-b border-solid") {
+                (tabs)
+            }
+            (&*tab_content.get())
+        }
+    });
+    let warnings = view_cond!(cx, !build.warnings().is_empty(), {
+        details(class="mb-8 text-sm dark:text-amber-300 text-amber-700") {
+            summary(class="cursor-pointer") {
+                "Parts of this build could not be read and are missing or incomplete"
+            }
+            ul(class="mt-2 ml-5 list-disc font-mono text-xs break-all") {
+                (render_warnings(cx, build.warnings()))
+            }
+        }
+    });
+    let findings = ::pob::lint::lint(build.pob());
+    let build_checks_title = format!("Build Checks ({})", findings.len());
+    let build_checks = view_cond!(cx, !findings.is_empty(), {
+        details(class="mt-12 text-sm") {
+            summary(class="cursor-pointer text-lg dark:text-slate-100 text-slate-900 border-b border-solid") {
+                (build_checks_title)
+            }
+            ul(class="mt-2 ml-5 list-disc") {
+                (render_findings(cx, &findings))
             }
         }
     });
@@ -119,10 +187,11 @@ pub fn ViewPaste<'a, G: Html>(
     let core_stats = pob::summary::core_stats(build.pob());
     let defense = pob::summary::defense(build.pob());
     let offense = pob::summary::offense(build.pob());
+    let minion = pob::summary::minion(build.pob());
     let config = pob::summary::config(build.pob());
     let choices = pob::summary::choices(build.pob());
 
-    let summary = [core_stats, defense, offense, config, choices]
+    let summary = [core_stats, defense, offense, minion, config, choices]
         .into_iter()
         .map(|stat| render(cx, stat))
         .map(|stat| view! { cx, div(class="flex-row gap-x-5") { (stat) } })
@@ -132,6 +201,7 @@ pub fn ViewPaste<'a, G: Html>(
 
     view! { cx,
         div(class="text-right text-sm text-slate-500", title=date, data-last-modified=last_modified) { (since) }
+        (warnings)
         div(class="flex flex-col md:flex-row gap-y-5 md:gap-x-3 mb-24") {
             div(class="flex-auto flex flex-col gap-y-2 -mt-[3px]") {
                 h1(class="flex items-center text-xl mb-1 dark:text-slate-100 text-slate-900") {
@@ -174,6 +244,7 @@ pub fn ViewPaste<'a, G: Html>(
                 }
             }
         }
+        PobLoadoutSelect(build)
         div(class="flex flex-wrap gap-x-10 gap-y-16") {
             div(class="flex-auto w-60") {
                 h2(class="text-lg dark:text-slate-100 text-slate-900 mb-2 border-b border-solid") { "Gear" }
@@ -184,6 +255,7 @@ pub fn ViewPaste<'a, G: Html>(
                 PobGems(build)
             }
         }
+        (build_checks)
         (tree_preview)
         (notes)
         div(class="h-[150px]") {}
@@ -197,6 +269,33 @@ fn render<G: Html>(cx: Scope, elements: Vec<Element>) -> View<G> {
         .collect_view()
 }
 
+fn render_warnings<G: Html>(cx: Scope, warnings: &[ParseWarning]) -> View<G> {
+    warnings
+        .iter()
+        .map(|warning| {
+            let path = warning.path.clone();
+            let message = warning.message.clone();
+            view! { cx, li { span(class="font-bold") { (path) } ": " (message) } }
+        })
+        .collect_view()
+}
+
+fn render_findings<G: Html>(cx: Scope, findings: &[Finding]) -> View<G> {
+    findings
+        .iter()
+        .map(|finding| {
+            let class = match finding.severity() {
+                Severity::Error => "dark:text-red-400 text-red-700",
+                Severity::Warning => "dark:text-amber-300 text-amber-700",
+                Severity::Info => "dark:text-slate-400 text-slate-600",
+            };
+            let name = finding.lint.name();
+            let message = finding.message.clone();
+            view! { cx, li(class=class) { span(class="font-bold") { (name) } ": " (message) } }
+        })
+        .collect_view()
+}
+
 fn has_displayable_tree(pob: &impl PathOfBuilding) -> bool {
     let specs = pob.tree_specs();
 
@@ -1,3 +1,4 @@
+use pob::diff::BuildDiff;
 use shared::{
     model::{data, PasteMetadata, PasteSummary},
     PasteId, UserPasteId,
@@ -62,6 +63,13 @@ impl Context {
         }
     }
 
+    pub fn diff(before: PasteId, after: PasteId, diff: BuildDiff) -> Self {
+        Self {
+            route: Ok(Route::Diff(before, after)),
+            inner: Inner::Diff(Box::new(diff)),
+        }
+    }
+
     pub fn route(&self) -> Result<&Route, &crate::Error> {
         self.route.as_ref()
     }
@@ -81,6 +89,13 @@ impl Context {
         }
     }
 
+    pub fn into_diff(self) -> Option<BuildDiff> {
+        match self.inner {
+            Inner::Diff(diff) => Some(*diff),
+            _ => None,
+        }
+    }
+
     pub fn get_user(&self) -> Option<&Vec<PasteSummary>> {
         match self.inner {
             Inner::User(ref pastes) => Some(pastes),
@@ -100,6 +115,7 @@ enum Inner {
     None,
     Paste(Paste),
     User(Vec<PasteSummary>),
+    Diff(Box<BuildDiff>),
 }
 
 impl From<shared::model::Paste> for Inner {
@@ -0,0 +1,374 @@
+use ::pob::diff::{BuildDiff, Change, ConfigDiff, SkillDiff, SlotDiff, StatDiff, TreeDiff};
+use shared::PasteId;
+use sycamore::prelude::*;
+
+use crate::{
+    future::LocalBoxFuture,
+    router::RoutedComponent,
+    utils::{deserialize_attribute, serialize_for_attribute, view_cond, IteratorExt},
+    Meta, Result,
+};
+
+static H2: &str = "text-lg dark:text-slate-100 text-slate-900 mb-2 mt-12 border-b border-solid";
+static GREEN: &str = "text-lime-400";
+static RED: &str = "text-rose-500";
+
+pub struct DiffPage {
+    before: PasteId,
+    after: PasteId,
+    diff: BuildDiff,
+}
+
+impl RoutedComponent for DiffPage {
+    type RouteArg = (PasteId, PasteId);
+
+    fn from_context((before, after): Self::RouteArg, ctx: crate::Context) -> Result<Self> {
+        Ok(Self {
+            before,
+            after,
+            diff: ctx.into_diff().unwrap(),
+        })
+    }
+
+    fn from_hydration((before, after): Self::RouteArg, element: web_sys::Element) -> Result<Self> {
+        let diff = deserialize_attribute(&element, "data-ssr").unwrap_or_default();
+
+        Ok(Self {
+            before,
+            after,
+            diff,
+        })
+    }
+
+    fn from_dynamic<'a>((before, after): Self::RouteArg) -> LocalBoxFuture<'a, Result<Self>> {
+        Box::pin(async move {
+            let diff = crate::api::get_diff(&before, &after).await?;
+            Ok(Self {
+                before,
+                after,
+                diff,
+            })
+        })
+    }
+
+    fn meta(&self) -> Result<Meta> {
+        let Self {
+            before,
+            after,
+            diff,
+        } = self;
+
+        let title = format!("Comparing {before} to {after}").into();
+
+        let (added, removed) = diff.trees.iter().fold((0, 0), |(a, r), tree| {
+            (a + tree.added.len(), r + tree.removed.len())
+        });
+        let skills = diff
+            .skill_sets
+            .iter()
+            .map(|ss| ss.skills.len())
+            .sum::<usize>();
+        let items = diff
+            .item_sets
+            .iter()
+            .map(|is| is.slots.len())
+            .sum::<usize>();
+
+        let description = [
+            format!("\u{27A4} {} stats changed", diff.stats.len()),
+            format!("\u{27A4} {added} passives allocated, {removed} removed"),
+            format!("\u{27A4} {skills} skills changed"),
+            format!("\u{27A4} {items} items swapped"),
+            format!("\u{27A4} {} config options changed", diff.configs.len()),
+        ]
+        .join("\n")
+        .into();
+
+        Ok(Meta {
+            title,
+            description,
+            image: crate::assets::logo().into(),
+            ..Default::default()
+        })
+    }
+
+    fn render<G: Html>(self, cx: Scope) -> View<G> {
+        view! { cx, DiffPageComponent(self) }
+    }
+}
+
+#[component]
+fn DiffPageComponent<G: Html>(
+    cx: Scope,
+    DiffPage {
+        before,
+        after,
+        diff,
+    }: DiffPage,
+) -> View<G> {
+    let data_ssr = serialize_for_attribute::<G>(&diff);
+
+    let before_url = before.to_url();
+    let after_url = after.to_url();
+    let before = before.to_string();
+    let after = after.to_string();
+
+    let empty = view_cond!(cx, diff.is_empty(), {
+        div(class="text-center mt-12") { "Both builds are identical." }
+    });
+
+    let stats = render_stats(cx, &diff.stats);
+    let trees = render_trees(cx, &diff.trees);
+    let skills = diff
+        .skill_sets
+        .iter()
+        .filter(|ss| !ss.skills.is_empty())
+        .map(|ss| render_skills(cx, ss.title.as_deref(), &ss.skills))
+        .collect_view();
+    let items = diff
+        .item_sets
+        .iter()
+        .filter(|is| !is.slots.is_empty())
+        .map(|is| render_slots(cx, is.title.as_deref(), &is.slots))
+        .collect_view();
+    let configs = render_configs(cx, &diff.configs);
+
+    view! { cx,
+        h1(class="text-amber-50 text-xl mb-4") {
+            "Comparing "
+            a(href=before_url, class="text-sky-500 dark:text-sky-400 hover:underline") { (before) }
+            " to "
+            a(href=after_url, class="text-sky-500 dark:text-sky-400 hover:underline") { (after) }
+        }
+        div(data-ssr=data_ssr) {}
+        (empty)
+        (stats)
+        (trees)
+        (skills)
+        (items)
+        (configs)
+        div(class="h-[150px]") {}
+    }
+}
+
+fn render_stats<G: Html>(cx: Scope, stats: &[StatDiff]) -> View<G> {
+    if stats.is_empty() {
+        return View::empty();
+    }
+
+    let rows = stats
+        .iter()
+        .map(|stat| {
+            let name = stat.stat.clone();
+            let before = stat.before.as_deref().map(format_value).unwrap_or_default();
+            let after = stat.after.as_deref().map(format_value).unwrap_or_default();
+            let (delta, color) = match stat.delta() {
+                Some(delta) if delta > 0.0 => (format!("+{}", format_number(delta)), GREEN),
+                Some(delta) if delta < 0.0 => (format_number(delta), RED),
+                _ => (String::new(), ""),
+            };
+
+            view! { cx,
+                tr {
+                    td(class="pr-4") { (name) }
+                    td(class="pr-4 text-right") { (before) }
+                    td(class="pr-4 text-right") { (after) }
+                    td(class=format!("text-right {color}")) { (delta) }
+                }
+            }
+        })
+        .collect_view();
+
+    view! { cx,
+        h2(class=H2) { "Stats" }
+        table(class="text-sm") {
+            thead {
+                tr(class="text-left text-slate-500") {
+                    th(class="pr-4") { "Stat" }
+                    th(class="pr-4 text-right") { "Before" }
+                    th(class="pr-4 text-right") { "After" }
+                    th(class="text-right") { "Change" }
+                }
+            }
+            tbody { (rows) }
+        }
+    }
+}
+
+fn render_trees<G: Html>(cx: Scope, trees: &[TreeDiff]) -> View<G> {
+    let rows = trees
+        .iter()
+        .enumerate()
+        .filter(|(_, tree)| !tree.is_empty())
+        .map(|(i, tree)| {
+            let title = tree
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Tree {}", i + 1));
+            let added = format!("+{} allocated", tree.added.len());
+            let removed = format!("-{} removed", tree.removed.len());
+            let masteries = tree.mastery_effects_added.len() + tree.mastery_effects_removed.len();
+            let masteries = view_cond!(cx, masteries > 0, {
+                span(class="text-slate-500") { (format!("{masteries} mastery effects changed")) }
+            });
+
+            view! { cx,
+                li(class="flex gap-x-4") {
+                    span { (title) }
+                    span(class=GREEN) { (added) }
+                    span(class=RED) { (removed) }
+                    (masteries)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if rows.is_empty() {
+        return View::empty();
+    }
+    let rows = View::new_fragment(rows);
+
+    view! { cx,
+        h2(class=H2) { "Tree" }
+        ul(class="text-sm flex flex-col gap-1") { (rows) }
+    }
+}
+
+fn render_skills<G: Html>(cx: Scope, title: Option<&str>, skills: &[SkillDiff]) -> View<G> {
+    let title = match title {
+        Some(title) => format!("Gems - {title}"),
+        None => "Gems".to_owned(),
+    };
+
+    let rows = skills
+        .iter()
+        .map(|skill| {
+            let (prefix, color) = match skill.change {
+                Change::Added => ("+ ", GREEN),
+                Change::Removed => ("- ", RED),
+                Change::Changed => ("", ""),
+            };
+            let name = format!("{prefix}{}", skill.name);
+
+            let added = skill
+                .gems_added
+                .iter()
+                .map(|gem| format!("+ {gem}"))
+                .map(|gem| view! { cx, li(class=GREEN) { (gem) } });
+            let removed = skill
+                .gems_removed
+                .iter()
+                .map(|gem| format!("- {gem}"))
+                .map(|gem| view! { cx, li(class=RED) { (gem) } });
+            let changed = skill.gems_changed.iter().map(|gem| {
+                let text = format!(
+                    "{}: {}/{} \u{2192} {}/{}",
+                    gem.name,
+                    gem.before.level,
+                    gem.before.quality,
+                    gem.after.level,
+                    gem.after.quality
+                );
+                view! { cx, li { (text) } }
+            });
+            let gems = added.chain(removed).chain(changed).collect_view();
+
+            view! { cx,
+                li {
+                    span(class=color) { (name) }
+                    ul(class="ml-4 text-slate-400") { (gems) }
+                }
+            }
+        })
+        .collect_view();
+
+    view! { cx,
+        h2(class=H2) { (title) }
+        ul(class="text-sm flex flex-col gap-2") { (rows) }
+    }
+}
+
+fn render_slots<G: Html>(cx: Scope, title: Option<&str>, slots: &[SlotDiff]) -> View<G> {
+    let title = match title {
+        Some(title) => format!("Items - {title}"),
+        None => "Items".to_owned(),
+    };
+
+    let rows = slots
+        .iter()
+        .map(|slot| {
+            let name = slot.slot.clone();
+            let before = slot.before.clone().unwrap_or_else(|| "Empty".to_owned());
+            let after = slot.after.clone().unwrap_or_else(|| "Empty".to_owned());
+
+            let added = slot
+                .mods_added
+                .iter()
+                .map(|m| format!("+ {m}"))
+                .map(|m| view! { cx, li(class=GREEN) { (m) } });
+            let removed = slot
+                .mods_removed
+                .iter()
+                .map(|m| format!("- {m}"))
+                .map(|m| view! { cx, li(class=RED) { (m) } });
+            let mods = added.chain(removed).collect_view();
+
+            view! { cx,
+                li {
+                    span(class="pr-4 text-slate-500") { (name) }
+                    span(class=format!("pr-4 {RED}")) { (before) }
+                    span(class=GREEN) { (after) }
+                    ul(class="ml-4 text-slate-400") { (mods) }
+                }
+            }
+        })
+        .collect_view();
+
+    view! { cx,
+        h2(class=H2) { (title) }
+        ul(class="text-sm flex flex-col gap-2") { (rows) }
+    }
+}
+
+fn render_configs<G: Html>(cx: Scope, configs: &[ConfigDiff]) -> View<G> {
+    if configs.is_empty() {
+        return View::empty();
+    }
+
+    let rows = configs
+        .iter()
+        .map(|config| {
+            let name = config.config.clone();
+            let before = config.before.clone().unwrap_or_else(|| "-".to_owned());
+            let after = config.after.clone().unwrap_or_else(|| "-".to_owned());
+
+            view! { cx,
+                tr {
+                    td(class="pr-4 text-slate-500") { (name) }
+                    td(class=format!("pr-4 {RED}")) { (before) }
+                    td(class=GREEN) { (after) }
+                }
+            }
+        })
+        .collect_view();
+
+    view! { cx,
+        h2(class=H2) { "Configuration" }
+        table(class="text-sm") { tbody { (rows) } }
+    }
+}
+
+fn format_value(value: &str) -> String {
+    value
+        .parse::<f64>()
+        .map(format_number)
+        .unwrap_or_else(|_| value.to_owned())
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract().abs() < 0.005 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
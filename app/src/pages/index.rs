@@ -1,9 +1,10 @@
 use sycamore::prelude::*;
 
 use crate::{
-    components::{CreatePaste, CreatePasteProps, ImportPastebin},
+    components::{CreatePaste, CreatePasteProps, ImportCharacter, ImportPastebin},
     future::LocalBoxFuture,
     router::RoutedComponent,
+    session::SessionValue,
     Meta, Result,
 };
 
@@ -29,10 +30,20 @@ impl RoutedComponent for IndexPage {
     }
 
     fn render<G: Html>(self, cx: Scope) -> View<G> {
+        let session = use_context::<SessionValue>(cx);
+
         view! { cx,
             div(class="flex flex-col gap-12") {
                 CreatePaste(CreatePasteProps::default())
                 ImportPastebin()
+                div() { // need the div for hydration to not break
+                    // Importing fetches from pathofexile.com, only offered to logged in users.
+                    (if session.get().is_logged_in() {
+                        view! { cx, ImportCharacter() }
+                    } else {
+                        View::empty()
+                    })
+                }
             }
         }
     }
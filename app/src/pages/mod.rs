@@ -1,9 +1,11 @@
+pub(crate) mod diff;
 pub(crate) mod index;
 pub(crate) mod paste;
 pub(crate) mod user;
 pub(crate) mod user_edit;
 pub(crate) mod user_paste;
 
+pub use self::diff::DiffPage;
 pub use self::index::IndexPage;
 pub use self::paste::PastePage;
 pub use self::user::UserPage;
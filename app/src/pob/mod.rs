@@ -1,4 +1,7 @@
-use pob::{Keystone, PathOfBuilding, PathOfBuildingExt, Stat};
+use pob::{
+    archetype::{BuildArchetype, DefenseLayer, Delivery, Trigger},
+    Config, ConfigSection, ConfigValue, Keystone, PathOfBuilding, PathOfBuildingExt, Stat,
+};
 use thousands::Separable;
 
 mod element;
@@ -18,23 +21,7 @@ macro_rules! push_if {
     }};
 }
 
-pub fn is_crit<T: PathOfBuilding>(pob: &T) -> bool {
-    !pob.has_keystone(Keystone::ElementalOverload) && pob.stat_at_least(Stat::CritChance, 20.0)
-}
-
-pub fn is_low_life<T: PathOfBuilding>(pob: &T) -> bool {
-    pob.stat_at_most(Stat::LifeUnreservedPercent, 50.0)
-}
-
-pub fn is_hybrid<T: PathOfBuilding>(pob: &T) -> bool {
-    !pob.has_keystone(Keystone::ChaosInoculation)
-        && !pob.has_keystone(Keystone::EldritchBattery)
-        && !is_low_life(pob)
-        && pob.stat_at_least(
-            Stat::EnergyShield,
-            pob.stat_parse(Stat::LifeUnreserved).unwrap_or(0.0) * 0.25,
-        )
-}
+pub use pob::archetype::{is_crit, is_hybrid, is_low_life};
 
 pub fn hp_pool<T: PathOfBuilding>(pob: &T) -> u32 {
     let mut ehp = pob.stat_parse(Stat::LifeUnreserved).unwrap_or(1);
@@ -95,6 +82,86 @@ pub fn formatted_max_hit<T: PathOfBuilding>(pob: &T) -> Option<String> {
     Some(result).filter(|r| !r.is_empty())
 }
 
+/// All configuration options set by the build author, grouped by section.
+///
+/// Custom modifiers are listed last, verbatim.
+pub fn formatted_configs<T: PathOfBuilding>(pob: &T) -> Option<String> {
+    let mut configs = pob
+        .configs()
+        .filter(|(config, _)| config.section() != ConfigSection::CustomMods)
+        .filter(|(_, value)| !matches!(value, ConfigValue::Bool(false)))
+        .collect::<Vec<_>>();
+    configs.sort_by_key(|(config, _)| config.section() as u8);
+
+    let mut result = Vec::new();
+    let mut section = None;
+    for (config, value) in configs {
+        if section != Some(config.section()) {
+            section = Some(config.section());
+            result.push(format!("^xFFFBEB{}", config.section().name()));
+        }
+
+        let value = match value {
+            ConfigValue::Bool(_) => None,
+            ConfigValue::Number(number) => Some(number.to_string()),
+            ConfigValue::String(value) => Some(value.to_owned()),
+            ConfigValue::None => continue,
+        };
+        match value {
+            Some(value) => result.push(format!("^xCBD5E1{}: {value}", config.label())),
+            None => result.push(format!("^xCBD5E1{}", config.label())),
+        }
+    }
+
+    // Options unknown to this version are shown with their PoB name and raw value.
+    let unknown = pob
+        .unknown_configs()
+        .filter(|(_, value)| !matches!(value, ConfigValue::Bool(false)))
+        .collect::<Vec<_>>();
+    if !unknown.is_empty() {
+        result.push("^xFFFBEBOther".to_owned());
+    }
+    for (name, value) in unknown {
+        match value {
+            ConfigValue::Number(number) => result.push(format!("^xCBD5E1{name}: {number}")),
+            ConfigValue::String(value) => result.push(format!("^xCBD5E1{name}: {value}")),
+            ConfigValue::Bool(_) | ConfigValue::None => result.push(format!("^xCBD5E1{name}")),
+        }
+    }
+
+    if let Some(custom_mods) = pob
+        .config(Config::CustomMods)
+        .string()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        result.push(format!("^xFFFBEB{}", ConfigSection::CustomMods.name()));
+        result.push(custom_mods.to_owned());
+    }
+
+    Some(result.join("\n")).filter(|r| !r.is_empty())
+}
+
+/// All non-zero damage per second stats of the player or minion.
+pub fn formatted_dps<T: PathOfBuilding>(pob: &T, minion: bool) -> Option<String> {
+    let stats = match minion {
+        true => pob.minion_stats(),
+        false => pob.stats(),
+    };
+
+    let result = stats
+        .filter(|(stat, _)| <&str>::from(*stat).ends_with("DPS"))
+        .filter(|(_, value)| value.as_f64() > 0.0 && value.as_f64().is_finite())
+        .map(|(stat, value)| {
+            let value = (value.as_f64() as u64).separate_with_commas();
+            format!("{}^xCBD5E1: {value}", stat.label())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(result).filter(|r| !r.is_empty())
+}
+
 #[derive(Default)]
 pub struct TitleConfig {
     pub no_level: bool,
@@ -112,34 +179,38 @@ pub fn title_with_config<T: PathOfBuilding>(pob: &T, config: &TitleConfig) -> St
         items.push(level.as_str());
     }
 
-    push_if!(items, is_low_life(pob), "LL");
-    push_if!(items, is_hybrid(pob), "Hybrid");
-    push_if!(items, pob.has_keystone(Keystone::ChaosInoculation), "CI");
-    push_if!(items, pob.has_keystone(Keystone::MindOverMatter), "MoM");
+    let archetype = BuildArchetype::classify(pob);
 
-    push_if!(items, is_crit(pob), "Crit");
+    let defenses = [
+        DefenseLayer::LowLife,
+        DefenseLayer::Hybrid,
+        DefenseLayer::ChaosInoculation,
+        DefenseLayer::MindOverMatter,
+    ];
+    for defense in defenses {
+        push_if!(items, archetype.has_defense(defense), defense.name());
+    }
+
+    push_if!(items, archetype.crit, "Crit");
     push_if!(
         items,
-        pob.main_skill_supported_by_any(&[
-            "Cast On Critical Strike",
-            "Awakened Cast On Critical Strike"
-        ]),
-        "CoC"
+        archetype.has_trigger(Trigger::CastOnCrit),
+        Trigger::CastOnCrit.name()
     );
 
     if let Some(main_skill) = pob.main_skill_name() {
         items.push(main_skill);
     }
 
-    if pob.main_skill_supported_by_any(&["Spell Totem", "Ballista Totem"]) {
-        items.push("Totem");
-    } else if pob.main_skill_supported_by_any(&["Blastchain Main", "High-Impact Mine"]) {
-        items.push("Mine");
-    } else if pob.main_skill_supported_by("Trap") {
-        items.push("Trap");
+    if let Some(delivery @ (Delivery::Totem | Delivery::Mine | Delivery::Trap)) = archetype.delivery
+    {
+        items.push(delivery.name());
     }
 
     items.push(pob.ascendancy_or_class().as_str());
+    if let Some(alternate) = pob.alternate_ascendancy() {
+        items.push(alternate.as_str());
+    }
 
     items.join(" ")
 }
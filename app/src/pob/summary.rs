@@ -1,4 +1,5 @@
 use ::pob::{Config, Keystone, PathOfBuilding, PathOfBuildingExt, Stat};
+use poe_data::{ascendancies, bandits, pantheon};
 
 use crate::pob::{self, Element};
 
@@ -175,29 +176,82 @@ pub fn defense(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
     elements
 }
 
+pub fn minion(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
+    let mut elements = Vec::with_capacity(4);
+
+    let Some(minion) = pob.minion() else {
+        return elements;
+    };
+    let stats = &minion.stats;
+
+    Element::new("Minion")
+        .color(AMBER_50)
+        .stat_str(minion.name().or_else(|| minion.summoned_by.map(Into::into)))
+        .hover(
+            minion
+                .summoned_by
+                .map(|skill| format!("Summoned by {skill}")),
+        )
+        .add_to(&mut elements);
+
+    Element::new("Life")
+        .color("text-rose-500")
+        .stat_int(stats.life.map(|life| life as f32))
+        .add_to(&mut elements);
+
+    if stats.energy_shield.is_some_and(|es| es >= 10.0) {
+        Element::new("ES")
+            .title("Energy Shield")
+            .color("text-cyan-200")
+            .stat_int(stats.energy_shield.map(|es| es as f32))
+            .add_to(&mut elements);
+    }
+
+    Element::new("Resistances")
+        .push_percent(
+            "text-orange-500 dark:text-orange-400",
+            stats.fire_resistance.unwrap_or(-60.0) as f32,
+        )
+        .push_percent(
+            "text-blue-400",
+            stats.cold_resistance.unwrap_or(-60.0) as f32,
+        )
+        .push_percent(
+            "text-yellow-600 dark:text-yellow-300",
+            stats.lightning_resistance.unwrap_or(-60.0) as f32,
+        )
+        .push_percent(
+            "text-fuchsia-500",
+            stats.chaos_resistance.unwrap_or(-60.0) as f32,
+        )
+        .add_to(&mut elements);
+
+    elements
+}
+
 pub fn offense(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
     let mut elements = Vec::with_capacity(5);
 
-    // TODO: real minion support
-    let is_minion = pob.minion_stat(Stat::CombinedDps).is_some();
+    let minion = pob.minion();
+    let is_minion = minion.is_some();
 
     let dps = pob
         .stat_parse(Stat::FullDps)
         .filter(|&dps: &f32| dps.is_finite() && dps > 0.0)
-        .or_else(|| match is_minion {
-            true => pob.minion_stat_parse(Stat::CombinedDps),
-            false => pob.stat_parse(Stat::CombinedDps),
+        .or_else(|| match &minion {
+            Some(minion) => minion.stats.dps.map(|dps| dps as f32),
+            None => pob.stat_parse(Stat::CombinedDps),
         });
 
     Element::new("DPS")
         .color(AMBER_50)
         .stat_int(dps)
+        .hover(pob::formatted_dps(pob, is_minion))
         .add_to(&mut elements);
 
-    let speed = if is_minion {
-        pob.minion_stat_parse(Stat::Speed)
-    } else {
-        pob.stat_parse(Stat::Speed)
+    let speed = match &minion {
+        Some(minion) => minion.stats.speed.map(|speed| speed as f32),
+        None => pob.stat_parse(Stat::Speed),
     };
 
     if speed > Some(0.001) {
@@ -208,6 +262,11 @@ pub fn offense(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
             .add_to(&mut elements);
     }
 
+    if is_minion {
+        // Hit and crit stats of the player are meaningless for summoners.
+        return elements;
+    }
+
     Element::new("Hit Rate")
         .color(AMBER_50)
         .stat_float(pob.stat_parse(Stat::HitRate))
@@ -314,13 +373,97 @@ pub fn config(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
     let element = Element::new("Config")
         .color(AMBER_50)
         .stat_str(Some(configs.join(", ")))
-        .hover(custom_mods);
+        .hover(pob::formatted_configs(pob));
+
+    let mut elements = vec![element];
+
+    let party = pob.party();
+    if !party.is_empty() {
+        let buffs = [&party.auras, &party.curses, &party.links]
+            .into_iter()
+            .flatten()
+            .chain(&party.enemy_conditions)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut hover = vec!["^xFFFBEBStats assume buffs from party members".to_owned()];
+        for (name, buffs) in [
+            ("Auras", &party.auras),
+            ("Curses", &party.curses),
+            ("Links", &party.links),
+            ("Enemy", &party.enemy_conditions),
+        ] {
+            if !buffs.is_empty() {
+                hover.push(format!("^xCBD5E1{name}: {}", buffs.join(", ")));
+            }
+        }
+        if let Some(buff_mode) = pob.calcs().buff_mode {
+            hover.push(format!("^xCBD5E1Calculation mode: {}", buff_mode.name()));
+        }
+
+        Element::new("Party")
+            .color(AMBER_50)
+            .stat_str(Some(buffs.join(", ")))
+            .hover(Some(hover.join("\n")))
+            .add_to(&mut elements);
+    }
 
-    vec![element]
+    elements
 }
 
 pub fn choices(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
-    let mut elements = Vec::with_capacity(2);
+    let mut elements = Vec::with_capacity(4);
+
+    // Only notables and keystones allocated in the active tree are listed.
+    let specs = pob.tree_specs();
+    let spec = specs.iter().find(|spec| spec.active);
+    let allocated = spec
+        .map(|spec| pob.allocated_notables(spec))
+        .unwrap_or_default();
+    let keystones = spec.map(|spec| pob.keystones(spec)).unwrap_or_default();
+
+    let alternate = pob.alternate_ascendancy();
+    let notables = pob
+        .ascendancy()
+        .map(ascendancies::notables)
+        .unwrap_or_default()
+        .iter()
+        .chain(
+            alternate
+                .map(ascendancies::alternate_notables)
+                .unwrap_or_default(),
+        )
+        .filter(|notable| allocated.iter().any(|&(id, _)| id == notable.id))
+        .map(|notable| formatted_stats(notable.name, notable.stats))
+        .collect::<Vec<_>>();
+
+    if !notables.is_empty() {
+        let ascendancies = [
+            Some(pob.ascendancy_or_class().as_str()),
+            alternate.map(|a| a.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        Element::new("Ascendancy")
+            .color(AMBER_50)
+            .stat_str(Some(ascendancies.join(", ")))
+            .hover(Some(notables.join("\n")))
+            .add_to(&mut elements);
+    }
+
+    if !keystones.is_empty() {
+        let names = keystones
+            .iter()
+            .map(|keystone| keystone.name())
+            .collect::<Vec<_>>();
+
+        Element::new("Keystones")
+            .color(AMBER_50)
+            .stat_str(Some(names.join(", ")))
+            .add_to(&mut elements);
+    }
 
     let bandit = pob
         .bandit()
@@ -330,22 +473,62 @@ pub fn choices(pob: &impl PathOfBuilding) -> Vec<Element<'_>> {
     Element::new("Bandit")
         .color(AMBER_50)
         .stat_str(Some(bandit))
+        .hover(Some(formatted_stats(
+            bandit,
+            bandits::rewards(pob.bandit()),
+        )))
         .add_to(&mut elements);
 
-    let pantheons = [
-        pob.pantheon_major_god().map(|god| god.name()),
-        pob.pantheon_minor_god().map(|god| god.name()),
-    ]
-    .into_iter()
-    .flatten()
-    .collect::<Vec<_>>();
+    let major = pob.pantheon_major_god();
+    let minor = pob.pantheon_minor_god();
+
+    let pantheons = [major.map(|god| god.name()), minor.map(|god| god.name())]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    // Souls without stat text have nothing to show.
+    let souls = major
+        .map(pantheon::major)
+        .unwrap_or_default()
+        .iter()
+        .chain(minor.map(pantheon::minor).unwrap_or_default())
+        .filter(|soul| !soul.stats.is_empty())
+        .map(|soul| formatted_stats(soul.name, soul.stats))
+        .collect::<Vec<_>>();
 
     if !pantheons.is_empty() {
         Element::new("Pantheon")
             .color(AMBER_50)
             .stat_str(Some(pantheons.join(", ")))
+            .hover(Some(souls.join("\n")).filter(|souls| !souls.is_empty()))
+            .add_to(&mut elements);
+    }
+
+    // Path of Building only stores hashes of the account and character name.
+    if let Some(import) = pob.import() {
+        let source = [import.realm, import.league]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        Element::new("Imported")
+            .color(AMBER_50)
+            .stat_str(Some(match source.is_empty() {
+                true => "Character".to_owned(),
+                false => source.join(", "),
+            }))
+            .hover(Some("^xCBD5E1Imported from a character on pathofexile.com"))
             .add_to(&mut elements);
     }
 
     elements
 }
+
+/// Formats a heading followed by its stat lines for a hover.
+fn formatted_stats(name: &str, stats: &[&str]) -> String {
+    std::iter::once(format!("^xFFFBEB{name}"))
+        .chain(stats.iter().map(|stat| format!("^xCBD5E1{stat}")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -77,7 +77,7 @@ fn is_link_end(c: char) -> bool {
     c.is_whitespace() || matches!(c, ')' | ']' | '}')
 }
 
-fn is_domain_whitelisted(domain: &str) -> bool {
+pub fn is_domain_whitelisted(domain: &str) -> bool {
     let domain = domain.strip_prefix("www.").unwrap_or(domain);
     crate::consts::LINK_WHITELIST.contains(&domain)
 }
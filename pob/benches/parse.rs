@@ -0,0 +1,128 @@
+//! Parse time and peak memory of the test builds.
+//!
+//! Run with `cargo bench -p pob`, the number of iterations per
+//! build can be changed with the `POB_BENCH_ITERATIONS` environment variable.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use pob::{PathOfBuilding, SerdePathOfBuilding};
+
+static BUILDS: &[(&str, &str)] = &[
+    ("316_empty", include_str!("../test/316_empty.xml")),
+    ("316_poison_occ", include_str!("../test/316_poison_occ.xml")),
+    ("318_skillset", include_str!("../test/318_skillset.xml")),
+    (
+        "319_mastery_effects",
+        include_str!("../test/319_mastery_effects.xml"),
+    ),
+    (
+        "320_impending_doom",
+        include_str!("../test/320_impending_doom.xml"),
+    ),
+    ("322_overrides", include_str!("../test/322_overrides.xml")),
+];
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+struct CountingAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl CountingAllocator {
+    const fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    fn reset_peak(&self) {
+        self.peak
+            .store(self.current.load(Ordering::SeqCst), Ordering::SeqCst);
+    }
+
+    /// Peak of allocated bytes since the last reset, relative to the allocated bytes at that time.
+    fn peak_since(&self, start: usize) -> usize {
+        self.peak.load(Ordering::SeqCst).saturating_sub(start)
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            self.peak.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let iterations = std::env::var("POB_BENCH_ITERATIONS")
+        .ok()
+        .and_then(|i| i.parse().ok())
+        .unwrap_or(200u32);
+
+    // Same as the worker, builds are compressed at rest.
+    let builds = BUILDS
+        .iter()
+        .map(|(name, xml)| (*name, *xml, pob::compress(xml).expect("compress build")))
+        .collect::<Vec<_>>();
+
+    println!(
+        "{:<24} {:>10} {:>12} {:>12} {:>12} {:>12}",
+        "build", "size", "from_xml", "editable", "from_export", "peak memory"
+    );
+
+    for (name, xml, export) in &builds {
+        let from_xml = measure(iterations, || SerdePathOfBuilding::from_xml(xml));
+        let editable = measure(iterations, || SerdePathOfBuilding::from_xml_editable(xml));
+        let from_export = measure(iterations, || SerdePathOfBuilding::from_export(export));
+
+        let start = ALLOCATOR.current.load(Ordering::SeqCst);
+        ALLOCATOR.reset_peak();
+        let pob = SerdePathOfBuilding::from_export(export).expect("valid build");
+        // Touch the build like the worker does for `/json` requests.
+        std::hint::black_box((pob.tree_specs(), pob.skill_sets()));
+        let peak = ALLOCATOR.peak_since(start);
+        drop(pob);
+
+        println!(
+            "{name:<24} {:>10} {:>12} {:>12} {:>12} {:>12}",
+            format_bytes(xml.len()),
+            format_duration(from_xml),
+            format_duration(editable),
+            format_duration(from_export),
+            format_bytes(peak),
+        );
+    }
+}
+
+/// Average time of one call to `f`.
+fn measure<T, E: std::fmt::Debug>(iterations: u32, f: impl Fn() -> Result<T, E>) -> Duration {
+    // Warm up.
+    std::hint::black_box(f().expect("valid build"));
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(f().expect("valid build"));
+    }
+    start.elapsed() / iterations
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}µs", duration.as_secs_f64() * 1_000_000.0)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    format!("{:.1}KiB", bytes as f64 / 1024.0)
+}
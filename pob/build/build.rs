@@ -0,0 +1,18 @@
+use std::{env, fs::File, path::Path};
+
+mod configs;
+mod mods;
+mod stats;
+
+pub fn main() -> anyhow::Result<()> {
+    let stats_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("stats.rs");
+    stats::generate(&mut File::create(stats_path)?)?;
+
+    let configs_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("configs.rs");
+    configs::generate(&mut File::create(configs_path)?)?;
+
+    let mods_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("mods.rs");
+    mods::generate(&mut File::create(mods_path)?)?;
+
+    Ok(())
+}
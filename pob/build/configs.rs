@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    name: String,
+    variant: String,
+    label: String,
+    section: String,
+    #[serde(rename = "type")]
+    typ: String,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("configs.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Config> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+
+    writeln!(output, "#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]")?;
+    writeln!(output, "pub enum Config {{")?;
+    for config in &data {
+        writeln!(output, "    /// {}", config.label)?;
+        writeln!(output, "    {},", config.variant)?;
+    }
+    writeln!(output, "}}")?;
+
+    writeln!(output, "impl Config {{")?;
+
+    writeln!(output, "    pub const ALL: &'static [Config] = &[")?;
+    for config in &data {
+        writeln!(output, "        Self::{},", config.variant)?;
+    }
+    writeln!(output, "    ];")?;
+
+    writeln!(output, "    fn name(&self) -> &'static str {{")?;
+    writeln!(output, "        match self {{")?;
+    for config in &data {
+        writeln!(
+            output,
+            "            Self::{} => {:?},",
+            config.variant, config.name
+        )?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(
+        output,
+        "    /// Label of the option in the Path of Building configuration tab."
+    )?;
+    writeln!(output, "    pub fn label(&self) -> &'static str {{")?;
+    writeln!(output, "        match self {{")?;
+    for config in &data {
+        writeln!(
+            output,
+            "            Self::{} => {:?},",
+            config.variant, config.label
+        )?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "    pub fn section(&self) -> ConfigSection {{")?;
+    writeln!(output, "        match self {{")?;
+    for config in &data {
+        let section = match config.section.as_str() {
+            "general" => "ConfigSection::General",
+            "bandit" => "ConfigSection::Bandit",
+            "skills" => "ConfigSection::Skills",
+            "charges" => "ConfigSection::Charges",
+            "conditions" => "ConfigSection::Conditions",
+            "enemy" => "ConfigSection::Enemy",
+            "map_mods" => "ConfigSection::MapMods",
+            "custom_mods" => "ConfigSection::CustomMods",
+            _ => anyhow::bail!(
+                "invalid config section '{}' for '{}'",
+                config.section,
+                config.name
+            ),
+        };
+        writeln!(output, "            Self::{} => {section},", config.variant)?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "    pub fn value_type(&self) -> ConfigType {{")?;
+    writeln!(output, "        match self {{")?;
+    for config in &data {
+        let typ = match config.typ.as_str() {
+            "boolean" => "ConfigType::Boolean",
+            "integer" => "ConfigType::Integer",
+            "number" => "ConfigType::Number",
+            "list" => "ConfigType::List",
+            "text" => "ConfigType::Text",
+            _ => anyhow::bail!("invalid config type '{}' for '{}'", config.typ, config.name),
+        };
+        writeln!(output, "            Self::{} => {typ},", config.variant)?;
+        map.entry(config.name.as_str(), &format!("Config::{}", config.variant));
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "}}")?;
+
+    writeln!(
+        output,
+        "static CONFIGS: phf::Map<&'static str, Config> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::fs::File;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct StatDescription {
+    text: String,
+    stats: Vec<String>,
+    /// Stats of the line when it is local to the item it is on.
+    local: Option<Vec<String>>,
+    #[serde(default)]
+    negate: bool,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("stat_descriptions.json");
+
+    let data = File::open(path)?;
+    let data: Vec<StatDescription> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+    let mut keys = std::collections::HashSet::new();
+
+    for description in data {
+        // Signs are part of the value, `+# to maximum Life` matches `-10 to maximum Life`.
+        let key = description.text.replace("+#", "#").replace("-#", "#");
+
+        let values = key.matches('#').count();
+        if values != description.stats.len() {
+            anyhow::bail!(
+                "'{}' has {values} values but {} stats",
+                description.text,
+                description.stats.len()
+            );
+        }
+        if let Some(local) = description.local.as_ref() {
+            if local.len() != description.stats.len() {
+                anyhow::bail!(
+                    "'{}' has {} stats but {} local stats",
+                    description.text,
+                    description.stats.len(),
+                    local.len()
+                );
+            }
+        }
+        if !keys.insert(key.clone()) {
+            anyhow::bail!("duplicate stat description '{}'", description.text);
+        }
+
+        let local = match description.local {
+            Some(local) => format!("Some(&{local:?})"),
+            None => "None".to_owned(),
+        };
+        let value = format!(
+            "StatDescription {{ stats: &{:?}, local: {local}, negate: {} }}",
+            description.stats, description.negate
+        );
+        map.entry(key, &value);
+    }
+
+    writeln!(
+        output,
+        "static STAT_DESCRIPTIONS: phf::Map<&'static str, StatDescription> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
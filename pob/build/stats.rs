@@ -0,0 +1,114 @@
+use std::fs::File;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Stat {
+    name: String,
+    variant: String,
+    label: String,
+    kind: String,
+}
+
+/// Generates the `Stat` enum from `data/stats.json`.
+///
+/// The list is maintained by hand and mirrors the player stats Path of Building shows
+/// in its sidebar, `displayStats` in `src/Modules/BuildDisplayStats.lua`.
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("stats.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Stat> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+
+    writeln!(output, "#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]")?;
+    writeln!(output, "#[non_exhaustive]")?;
+    writeln!(output, "pub enum Stat<'a> {{")?;
+    for stat in &data {
+        writeln!(output, "    /// {}", stat.label)?;
+        writeln!(output, "    {},", stat.variant)?;
+    }
+    writeln!(output, "    Custom(&'static str),")?;
+    writeln!(
+        output,
+        "    /// A stat of the build which is not known, with the name Path of Building uses for it."
+    )?;
+    writeln!(output, "    Other(&'a str),")?;
+    writeln!(output, "}}")?;
+
+    writeln!(output, "impl<'a> Stat<'a> {{")?;
+
+    writeln!(
+        output,
+        "    /// All known stats, excluding [`Stat::Custom`] and [`Stat::Other`]."
+    )?;
+    writeln!(output, "    pub const ALL: &'static [Stat<'static>] = &[")?;
+    for stat in &data {
+        writeln!(output, "        Stat::{},", stat.variant)?;
+    }
+    writeln!(output, "    ];")?;
+
+    writeln!(output, "    fn name(&self) -> &'a str {{")?;
+    writeln!(output, "        match self {{")?;
+    for stat in &data {
+        writeln!(
+            output,
+            "            Self::{} => {:?},",
+            stat.variant, stat.name
+        )?;
+    }
+    writeln!(output, "            Self::Custom(s) => s,")?;
+    writeln!(output, "            Self::Other(s) => s,")?;
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(
+        output,
+        "    /// Human readable name of the stat, as displayed by Path of Building."
+    )?;
+    writeln!(output, "    pub fn label(&self) -> &'a str {{")?;
+    writeln!(output, "        match self {{")?;
+    for stat in &data {
+        writeln!(
+            output,
+            "            Self::{} => {:?},",
+            stat.variant, stat.label
+        )?;
+    }
+    writeln!(output, "            Self::Custom(s) => s,")?;
+    writeln!(output, "            Self::Other(s) => s,")?;
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "    pub fn kind(&self) -> StatKind {{")?;
+    writeln!(output, "        match self {{")?;
+    for stat in &data {
+        let kind = match stat.kind.as_str() {
+            "integer" => "StatKind::Integer",
+            "percent" => "StatKind::Percent",
+            "float" => "StatKind::Float",
+            "rate" => "StatKind::Rate",
+            _ => anyhow::bail!("invalid stat kind '{}' for '{}'", stat.kind, stat.name),
+        };
+        writeln!(output, "            Self::{} => {kind},", stat.variant)?;
+        map.entry(stat.name.as_str(), &format!("Stat::{}", stat.variant));
+    }
+    writeln!(
+        output,
+        "            Self::Custom(_) | Self::Other(_) => StatKind::Float,"
+    )?;
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "}}")?;
+
+    writeln!(
+        output,
+        "static STATS: phf::Map<&'static str, Stat<'static>> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,580 @@
+//! Classification of a build into its archetype.
+//!
+//! Path of Building exports don't contain damage per type, the classification is
+//! inferred from the gem tags of the main skill, its supports, the stats
+//! calculated by Path of Building and the gear.
+use crate::{Item, Keystone, PathOfBuilding, PathOfBuildingExt, Rarity, Stat};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Cold,
+    Lightning,
+    Chaos,
+}
+
+impl DamageType {
+    pub const ALL: [DamageType; 5] = [
+        Self::Physical,
+        Self::Fire,
+        Self::Cold,
+        Self::Lightning,
+        Self::Chaos,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Physical => "Physical",
+            Self::Fire => "Fire",
+            Self::Cold => "Cold",
+            Self::Lightning => "Lightning",
+            Self::Chaos => "Chaos",
+        }
+    }
+
+    /// Gem tag of skills which deal the damage type.
+    fn tag(&self) -> &'static str {
+        match self {
+            Self::Physical => "physical",
+            Self::Fire => "fire",
+            Self::Cold => "cold",
+            Self::Lightning => "lightning",
+            Self::Chaos => "chaos",
+        }
+    }
+
+    /// Supports which only make sense for the damage type.
+    fn supports(&self) -> &'static [&'static str] {
+        match self {
+            Self::Physical => &[
+                "Brutality",
+                "Chance to Bleed",
+                "Impale",
+                "Maim",
+                "Melee Physical Damage",
+            ],
+            Self::Fire => &[
+                "Added Fire Damage",
+                "Burning Damage",
+                "Cold to Fire",
+                "Fire Penetration",
+                "Immolate",
+            ],
+            Self::Cold => &[
+                "Added Cold Damage",
+                "Cold Penetration",
+                "Hypothermia",
+                "Ice Bite",
+            ],
+            Self::Lightning => &[
+                "Added Lightning Damage",
+                "Innervate",
+                "Lightning Penetration",
+            ],
+            Self::Chaos => &[
+                "Added Chaos Damage",
+                "Critical Strike Affliction",
+                "Void Manipulation",
+                "Withering Touch",
+            ],
+        }
+    }
+
+    /// Damage over time stat which is only dealt with the damage type.
+    fn ailment(&self) -> Option<Stat<'static>> {
+        match self {
+            Self::Physical => Some(Stat::BleedDps),
+            Self::Fire => Some(Stat::IgniteDps),
+            Self::Chaos => Some(Stat::PoisonDps),
+            Self::Cold | Self::Lightning => None,
+        }
+    }
+}
+
+/// How the main skill deals its damage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Delivery {
+    Attack,
+    Spell,
+    Minion,
+    DamageOverTime,
+    Totem,
+    Trap,
+    Mine,
+    Brand,
+}
+
+impl Delivery {
+    pub const ALL: [Delivery; 8] = [
+        Self::Attack,
+        Self::Spell,
+        Self::Minion,
+        Self::DamageOverTime,
+        Self::Totem,
+        Self::Trap,
+        Self::Mine,
+        Self::Brand,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Attack => "Attack",
+            Self::Spell => "Spell",
+            Self::Minion => "Minion",
+            Self::DamageOverTime => "DoT",
+            Self::Totem => "Totem",
+            Self::Trap => "Trap",
+            Self::Mine => "Mine",
+            Self::Brand => "Brand",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DefenseLayer {
+    LowLife,
+    Hybrid,
+    ChaosInoculation,
+    MindOverMatter,
+    EldritchBattery,
+    Ward,
+    Armour,
+    Evasion,
+    Block,
+    SpellSuppression,
+}
+
+impl DefenseLayer {
+    pub const ALL: [DefenseLayer; 10] = [
+        Self::LowLife,
+        Self::Hybrid,
+        Self::ChaosInoculation,
+        Self::MindOverMatter,
+        Self::EldritchBattery,
+        Self::Ward,
+        Self::Armour,
+        Self::Evasion,
+        Self::Block,
+        Self::SpellSuppression,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LowLife => "LL",
+            Self::Hybrid => "Hybrid",
+            Self::ChaosInoculation => "CI",
+            Self::MindOverMatter => "MoM",
+            Self::EldritchBattery => "EB",
+            Self::Ward => "Ward",
+            Self::Armour => "Armour",
+            Self::Evasion => "Evasion",
+            Self::Block => "Block",
+            Self::SpellSuppression => "Suppression",
+        }
+    }
+
+    fn is_present<P: PathOfBuilding>(&self, pob: &P) -> bool {
+        match self {
+            Self::LowLife => is_low_life(pob),
+            Self::Hybrid => is_hybrid(pob),
+            Self::ChaosInoculation => pob.has_keystone(Keystone::ChaosInoculation),
+            Self::MindOverMatter => pob.has_keystone(Keystone::MindOverMatter),
+            Self::EldritchBattery => pob.has_keystone(Keystone::EldritchBattery),
+            Self::Ward => pob.stat_at_least(Stat::Ward, 1000.0),
+            Self::Armour => pob.stat_at_least(Stat::Armour, 20000.0),
+            Self::Evasion => pob.stat_at_least(Stat::Evasion, 20000.0),
+            Self::Block => {
+                pob.stat_at_least(Stat::BlockChance, 50.0)
+                    && pob.stat_at_least(Stat::SpellBlockChance, 50.0)
+            }
+            Self::SpellSuppression => pob.stat_at_least(Stat::SpellSuppressionChance, 100.0),
+        }
+    }
+}
+
+/// Mechanics which trigger the main skill.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    CastOnCrit,
+    CastWhileChannelling,
+    CastWhenDamageTaken,
+    CastOnMeleeKill,
+    ArcanistBrand,
+    Spellslinger,
+}
+
+impl Trigger {
+    pub const ALL: [Trigger; 6] = [
+        Self::CastOnCrit,
+        Self::CastWhileChannelling,
+        Self::CastWhenDamageTaken,
+        Self::CastOnMeleeKill,
+        Self::ArcanistBrand,
+        Self::Spellslinger,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CastOnCrit => "CoC",
+            Self::CastWhileChannelling => "CwC",
+            Self::CastWhenDamageTaken => "CwDT",
+            Self::CastOnMeleeKill => "CoMK",
+            Self::ArcanistBrand => "Arcanist Brand",
+            Self::Spellslinger => "Spellslinger",
+        }
+    }
+
+    fn supports(&self) -> &'static [&'static str] {
+        match self {
+            Self::CastOnCrit => &[
+                "Cast On Critical Strike",
+                "Awakened Cast On Critical Strike",
+            ],
+            Self::CastWhileChannelling => {
+                &["Cast while Channelling", "Awakened Cast While Channelling"]
+            }
+            Self::CastWhenDamageTaken => &["Cast when Damage Taken"],
+            Self::CastOnMeleeKill => &["Cast on Melee Kill"],
+            Self::ArcanistBrand => &["Arcanist Brand"],
+            Self::Spellslinger => &["Spellslinger"],
+        }
+    }
+}
+
+/// Rough estimate of the cost of the gear.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BudgetTier {
+    Starter,
+    Medium,
+    High,
+    Mirror,
+}
+
+impl BudgetTier {
+    pub const ALL: [BudgetTier; 4] = [Self::Starter, Self::Medium, Self::High, Self::Mirror];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Starter => "Starter",
+            Self::Medium => "Medium",
+            Self::High => "High",
+            Self::Mirror => "Mirror",
+        }
+    }
+
+    fn from_items<'a>(items: impl IntoIterator<Item = Item<'a>>) -> Self {
+        let mut score = 0;
+        let mut chase_unique = false;
+        for item in items {
+            if item.mirrored {
+                return Self::Mirror;
+            }
+            chase_unique |= item.rarity.is_unique()
+                && item.name.is_some_and(|name| CHASE_UNIQUES.contains(&name));
+
+            score += match (item.rarity, item.influence1, item.influence2) {
+                (Rarity::Rare, Some(_), Some(_)) => 3,
+                (Rarity::Rare, Some(_), None) => 2,
+                (rarity, _, _) if rarity.is_unique() && item.corrupted => 2,
+                (rarity, _, _) if rarity.is_unique() || rarity.is_rare() => 1,
+                _ => 0,
+            };
+        }
+
+        match score {
+            _ if chase_unique => Self::High,
+            0..=9 => Self::Starter,
+            10..=19 => Self::Medium,
+            _ => Self::High,
+        }
+    }
+}
+
+/// Uniques which on their own make a build expensive.
+const CHASE_UNIQUES: &[&str] = &[
+    "Headhunter",
+    "Mageblood",
+    "Nimis",
+    "Squire",
+    "Original Sin",
+    "Progenesis",
+    "Voidforge",
+    "Forbidden Flame",
+    "Forbidden Flesh",
+    "Ashes of the Stars",
+    "The Adorned",
+];
+
+/// Structured classification of a build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildArchetype {
+    /// Damage types of the main skill, in order of [`DamageType::ALL`].
+    pub damage_types: Vec<DamageType>,
+    pub delivery: Option<Delivery>,
+    pub defenses: Vec<DefenseLayer>,
+    pub triggers: Vec<Trigger>,
+    pub crit: bool,
+    pub budget: BudgetTier,
+}
+
+impl BuildArchetype {
+    pub fn classify<P: PathOfBuilding>(pob: &P) -> Self {
+        let triggers = Trigger::ALL
+            .into_iter()
+            .filter(|trigger| pob.main_skill_supported_by_any(trigger.supports()))
+            .collect();
+
+        let defenses = DefenseLayer::ALL
+            .into_iter()
+            .filter(|defense| defense.is_present(pob))
+            .collect();
+
+        let budget = pob
+            .item_sets()
+            .iter()
+            .find(|set| set.is_selected)
+            .map(|set| {
+                let items = set
+                    .gear
+                    .slots
+                    .iter()
+                    .filter(|slot| !slot.is_weapon_swap())
+                    .filter_map(|slot| Item::parse(slot.item).ok());
+                BudgetTier::from_items(items)
+            })
+            .unwrap_or(BudgetTier::Starter);
+
+        Self {
+            damage_types: damage_types(pob),
+            delivery: delivery(pob),
+            defenses,
+            triggers,
+            crit: is_crit(pob),
+            budget,
+        }
+    }
+
+    pub fn has_defense(&self, defense: DefenseLayer) -> bool {
+        self.defenses.contains(&defense)
+    }
+
+    pub fn has_trigger(&self, trigger: Trigger) -> bool {
+        self.triggers.contains(&trigger)
+    }
+}
+
+pub fn is_crit<P: PathOfBuilding>(pob: &P) -> bool {
+    !pob.has_keystone(Keystone::ElementalOverload) && pob.stat_at_least(Stat::CritChance, 20.0)
+}
+
+pub fn is_low_life<P: PathOfBuilding>(pob: &P) -> bool {
+    pob.stat_at_most(Stat::LifeUnreservedPercent, 50.0)
+}
+
+pub fn is_hybrid<P: PathOfBuilding>(pob: &P) -> bool {
+    !pob.has_keystone(Keystone::ChaosInoculation)
+        && !pob.has_keystone(Keystone::EldritchBattery)
+        && !is_low_life(pob)
+        && pob.stat_at_least(
+            Stat::EnergyShield,
+            pob.stat_parse(Stat::LifeUnreserved).unwrap_or(0.0) * 0.25,
+        )
+}
+
+fn damage_types<P: PathOfBuilding>(pob: &P) -> Vec<DamageType> {
+    let tags = main_skill_tags(pob);
+
+    DamageType::ALL
+        .into_iter()
+        .filter(|damage_type| {
+            tags.contains(&damage_type.tag())
+                || pob.main_skill_supported_by_any(damage_type.supports())
+                || damage_type
+                    .ailment()
+                    .is_some_and(|stat| pob.stat_at_least(stat, 1.0))
+        })
+        .collect()
+}
+
+fn delivery<P: PathOfBuilding>(pob: &P) -> Option<Delivery> {
+    let tags = main_skill_tags(pob);
+
+    // Totems, mines and traps may also be minion skills, e.g. a Spell Totem with Raise Zombie.
+    if tags.contains(&"totem") || pob.main_skill_supported_by_any(["Spell Totem", "Ballista Totem"])
+    {
+        return Some(Delivery::Totem);
+    }
+    // Awakened variants match through their base name.
+    if tags.contains(&"mine")
+        || pob.main_skill_supported_by_any(["Blastchain Mine", "High-Impact Mine"])
+    {
+        return Some(Delivery::Mine);
+    }
+    if tags.contains(&"trap") || pob.main_skill_supported_by("Trap") {
+        return Some(Delivery::Trap);
+    }
+    // Arcanist Brand only triggers the linked spells.
+    if tags.contains(&"brand") && pob.main_skill_name() != Some("Arcanist Brand") {
+        return Some(Delivery::Brand);
+    }
+    if tags.contains(&"minion") && pob.minion().is_some() {
+        return Some(Delivery::Minion);
+    }
+
+    let hit = pob.stat_parse::<f64>(Stat::TotalDps).unwrap_or(0.0);
+    let dot = pob.stat_parse::<f64>(Stat::TotalDotDps).unwrap_or(0.0)
+        + pob.stat_parse::<f64>(Stat::PoisonDps).unwrap_or(0.0)
+        + pob.stat_parse::<f64>(Stat::IgniteDps).unwrap_or(0.0)
+        + pob.stat_parse::<f64>(Stat::BleedDps).unwrap_or(0.0);
+    if dot > hit {
+        return Some(Delivery::DamageOverTime);
+    }
+
+    if tags.contains(&"spell") {
+        Some(Delivery::Spell)
+    } else if tags.contains(&"attack") {
+        Some(Delivery::Attack)
+    } else {
+        None
+    }
+}
+
+/// Gem tags of the main skill, empty if the gem is not known.
+fn main_skill_tags<P: PathOfBuilding>(pob: &P) -> &'static [&'static str] {
+    pob.main_skill_gem().map(|gem| gem.tags).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_EMPTY: &str = include_str!("../test/316_empty.xml");
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+
+    #[test]
+    fn classify_poison_occ() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+
+        let archetype = BuildArchetype::classify(&pob);
+        assert!(archetype.damage_types.contains(&DamageType::Chaos));
+        assert_eq!(Some(Delivery::DamageOverTime), archetype.delivery);
+        assert!(archetype.triggers.is_empty());
+    }
+
+    #[test]
+    fn classify_empty() {
+        let pob = SerdePathOfBuilding::from_xml(V316_EMPTY).unwrap();
+
+        let archetype = BuildArchetype::classify(&pob);
+        assert!(archetype.damage_types.is_empty());
+        assert!(archetype.defenses.is_empty());
+        assert!(!archetype.crit);
+        assert_eq!(BudgetTier::Starter, archetype.budget);
+    }
+
+    /// The empty build with a single skill of `gems`, `(name, gem id)`, as the main skill.
+    fn with_main_skill(gems: &[(&str, &str)]) -> SerdePathOfBuilding {
+        let gems = gems
+            .iter()
+            .map(|(name, id)| {
+                format!(
+                    r#"<Gem nameSpec="{name}" gemId="Metadata/Items/Gems/{id}" level="20" quality="0" enabled="true"/>"#
+                )
+            })
+            .collect::<String>();
+        let xml = V316_EMPTY.replace(
+            r#"showAltQualityGems="false"/>"#,
+            &format!(
+                r#"showAltQualityGems="false"><Skill mainActiveSkill="1" enabled="true">{gems}</Skill></Skills>"#
+            ),
+        );
+        SerdePathOfBuilding::from_xml(&xml).unwrap()
+    }
+
+    #[test]
+    fn damage_types_from_gem_tags() {
+        let pob = with_main_skill(&[("Blade Vortex", "SkillGemBladeVortex")]);
+        let archetype = BuildArchetype::classify(&pob);
+        assert_eq!(vec![DamageType::Physical], archetype.damage_types);
+        assert_eq!(Some(Delivery::Spell), archetype.delivery);
+
+        let pob = with_main_skill(&[("Frost Blades", "SkillGemFrostBlades")]);
+        let archetype = BuildArchetype::classify(&pob);
+        assert_eq!(vec![DamageType::Cold], archetype.damage_types);
+        assert_eq!(Some(Delivery::Attack), archetype.delivery);
+
+        let pob = with_main_skill(&[
+            ("Frost Blades", "SkillGemFrostBlades"),
+            ("Added Fire Damage", "SupportGemAddedFireDamage"),
+        ]);
+        let archetype = BuildArchetype::classify(&pob);
+        assert_eq!(
+            vec![DamageType::Fire, DamageType::Cold],
+            archetype.damage_types
+        );
+    }
+
+    #[test]
+    fn delivery_from_gem_tags() {
+        let pob = with_main_skill(&[("Storm Brand", "SkillGemStormBrand")]);
+        assert_eq!(
+            Some(Delivery::Brand),
+            BuildArchetype::classify(&pob).delivery
+        );
+
+        let pob = with_main_skill(&[("Bear Trap", "SkillGemBearTrap")]);
+        assert_eq!(
+            Some(Delivery::Trap),
+            BuildArchetype::classify(&pob).delivery
+        );
+
+        let pob = with_main_skill(&[
+            ("Fireball", "SkillGemFireball"),
+            ("Spell Totem", "SupportGemSpellTotem"),
+        ]);
+        assert_eq!(
+            Some(Delivery::Totem),
+            BuildArchetype::classify(&pob).delivery
+        );
+
+        let pob = with_main_skill(&[
+            ("Freezing Pulse", "SkillGemFreezingPulse"),
+            ("Blastchain Mine", "SupportGemRemoteMine"),
+        ]);
+        assert_eq!(
+            Some(Delivery::Mine),
+            BuildArchetype::classify(&pob).delivery
+        );
+
+        let pob = with_main_skill(&[
+            ("Freezing Pulse", "SkillGemFreezingPulse"),
+            ("High-Impact Mine", "SupportGemRemoteMine2"),
+        ]);
+        assert_eq!(
+            Some(Delivery::Mine),
+            BuildArchetype::classify(&pob).delivery
+        );
+
+        let pob = with_main_skill(&[
+            ("Freezing Pulse", "SkillGemFreezingPulse"),
+            ("Awakened Blastchain Mine", "SupportGemRemoteMinePlus"),
+        ]);
+        assert_eq!(
+            Some(Delivery::Mine),
+            BuildArchetype::classify(&pob).delivery
+        );
+    }
+
+    #[test]
+    fn budget_tier() {
+        let rare = "Rarity: RARE\nDoom Loop\nHubris Circlet\nShaper Item\nElder Item";
+        let items = std::iter::repeat_with(|| Item::parse(rare).unwrap()).take(4);
+        assert_eq!(BudgetTier::Medium, BudgetTier::from_items(items));
+
+        let mageblood = "Rarity: UNIQUE\nMageblood\nHeavy Belt";
+        let items = [Item::parse(mageblood).unwrap()];
+        assert_eq!(BudgetTier::High, BudgetTier::from_items(items));
+    }
+}
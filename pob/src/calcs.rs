@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Selections of the calcs tab of Path of Building.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calcs {
+    /// Socket group selected in the calcs tab, starts at 1.
+    pub skill_number: Option<u16>,
+    /// Which buffs are included in the calculated stats.
+    pub buff_mode: Option<BuffMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuffMode {
+    Unbuffed,
+    Buffed,
+    InCombat,
+    Effective,
+}
+
+impl BuffMode {
+    pub const ALL: [BuffMode; 4] = [
+        Self::Unbuffed,
+        Self::Buffed,
+        Self::InCombat,
+        Self::Effective,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Unbuffed => "Unbuffed",
+            Self::Buffed => "Buffed",
+            Self::InCombat => "In Combat",
+            Self::Effective => "Effective DPS",
+        }
+    }
+
+    /// Value of the mode as stored by Path of Building, e.g. `EFFECTIVE`.
+    pub(crate) fn from_pob(value: &str) -> Option<Self> {
+        match value {
+            "UNBUFFED" => Some(Self::Unbuffed),
+            "BUFFED" => Some(Self::Buffed),
+            "COMBAT" => Some(Self::InCombat),
+            "EFFECTIVE" => Some(Self::Effective),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! Importer for characters from the official character-window API.
+//!
+//! The API has separate endpoints for the equipped items (`get-items`) and
+//! the passive tree (`get-passive-skills`), both are turned into a Path of Building
+//! document with [`SerdePathOfBuilding::from_character`](crate::SerdePathOfBuilding::from_character).
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::serde::{Element, Node};
+use crate::{Result, TreeUrl};
+
+/// Node ids of cluster jewel nodes are offset in Path of Building, the API returns them without.
+const CLUSTER_NODE_OFFSET: u32 = 65536;
+
+/// Response of the `character-window/get-items` endpoint.
+#[derive(Debug, Deserialize)]
+pub struct CharacterItems {
+    pub character: Character,
+    #[serde(default)]
+    pub items: Vec<CharacterItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Character {
+    pub name: String,
+    #[serde(default)]
+    pub league: Option<String>,
+    pub class_id: u8,
+    #[serde(default)]
+    pub ascendancy_class: u8,
+    pub level: u8,
+}
+
+/// Response of the `character-window/get-passive-skills` endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct CharacterPassives {
+    #[serde(default)]
+    pub hashes: Vec<u32>,
+    /// Allocated cluster jewel nodes.
+    #[serde(default)]
+    pub hashes_ex: Vec<u32>,
+    /// Selected mastery effects as `(node, effect)`.
+    #[serde(default, deserialize_with = "deserialize_mastery_effects")]
+    pub mastery_effects: Vec<(u32, u32)>,
+    /// Jewels socketed in the tree, [`CharacterItem::x`] is the index of the jewel socket.
+    #[serde(default)]
+    pub items: Vec<CharacterItem>,
+}
+
+/// An item as returned by the API, gems are items socketed into other items.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CharacterItem {
+    pub inventory_id: Option<String>,
+    pub x: Option<u32>,
+    pub name: String,
+    pub type_line: String,
+    pub base_type: Option<String>,
+    pub frame_type: u8,
+    pub ilvl: u8,
+    pub properties: Vec<ItemProperty>,
+    pub requirements: Vec<ItemProperty>,
+    pub enchant_mods: Vec<String>,
+    pub implicit_mods: Vec<String>,
+    pub fractured_mods: Vec<String>,
+    pub explicit_mods: Vec<String>,
+    pub crafted_mods: Vec<String>,
+    pub influences: HashMap<String, bool>,
+    pub searing: bool,
+    pub tangled: bool,
+    pub synthesised: bool,
+    pub corrupted: bool,
+    /// Mirrored.
+    pub duplicated: bool,
+    pub split: bool,
+    pub abyss_jewel: bool,
+    pub support: bool,
+    pub sockets: Vec<ItemSocket>,
+    pub socketed_items: Vec<CharacterItem>,
+    /// Index into the sockets of the item this item is socketed in.
+    pub socket: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ItemProperty {
+    pub name: String,
+    /// Values with their display style, e.g. `["+20%", 1]`.
+    #[serde(default)]
+    pub values: Vec<(String, u8)>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ItemSocket {
+    pub group: u8,
+    #[serde(default, rename = "sColour")]
+    pub colour: String,
+}
+
+impl CharacterItem {
+    /// Name of the Path of Building slot the item is equipped in.
+    fn slot(&self) -> Option<String> {
+        let slot = match self.inventory_id.as_deref()? {
+            "Weapon" => "Weapon 1",
+            "Offhand" => "Weapon 2",
+            "Weapon2" => "Weapon 1 Swap",
+            "Offhand2" => "Weapon 2 Swap",
+            "Helm" => "Helmet",
+            "BodyArmour" => "Body Armour",
+            "Gloves" => "Gloves",
+            "Boots" => "Boots",
+            "Amulet" => "Amulet",
+            "Ring" => "Ring 1",
+            "Ring2" => "Ring 2",
+            "Belt" => "Belt",
+            "Flask" => return Some(format!("Flask {}", self.x.unwrap_or(0) + 1)),
+            _ => return None,
+        };
+        Some(slot.to_owned())
+    }
+
+    fn property(&self, name: &str) -> Option<&str> {
+        find_property(&self.properties, name)
+    }
+
+    /// The item in Path of Building's item text format, `None` for items which are not gear.
+    fn to_pob(&self) -> Option<String> {
+        let rarity = match self.frame_type {
+            0 => "NORMAL",
+            1 => "MAGIC",
+            2 => "RARE",
+            3 => "UNIQUE",
+            9 => "RELIC",
+            _ => return None,
+        };
+
+        let mut lines = vec![format!("Rarity: {rarity}")];
+        let name = strip_markup(&self.name);
+        let type_line = strip_markup(&self.type_line);
+        if !name.is_empty() && self.frame_type >= 2 {
+            lines.push(name.to_owned());
+            lines.push(self.base_type.as_deref().unwrap_or(type_line).to_owned());
+        } else {
+            lines.push(type_line.to_owned());
+        }
+
+        lines.push(format!("Item Level: {}", self.ilvl));
+        for property in ITEM_PROPERTIES {
+            if let Some(value) = self.property(property) {
+                let value = value.trim_start_matches('+').trim_end_matches('%');
+                lines.push(format!("{property}: {value}"));
+            }
+        }
+        if !self.sockets.is_empty() {
+            lines.push(format!("Sockets: {}", self.sockets_text()));
+        }
+        if let Some(level) = find_property(&self.requirements, "Level") {
+            lines.push(format!("LevelReq: {level}"));
+        }
+        for (stat, names) in [
+            ("Str", ["Str", "Strength"]),
+            ("Dex", ["Dex", "Dexterity"]),
+            ("Int", ["Int", "Intelligence"]),
+        ] {
+            if let Some(value) = names
+                .iter()
+                .find_map(|name| find_property(&self.requirements, name))
+            {
+                lines.push(format!("{stat}: {value}"));
+            }
+        }
+
+        for influence in self.influence_lines() {
+            lines.push(influence.to_owned());
+        }
+
+        let implicits = mod_lines(&self.enchant_mods, "{enchant}")
+            .chain(mod_lines(&self.implicit_mods, ""))
+            .collect::<Vec<_>>();
+        lines.push(format!("Implicits: {}", implicits.len()));
+        lines.extend(implicits);
+        lines.extend(mod_lines(&self.fractured_mods, "{fractured}"));
+        lines.extend(mod_lines(&self.explicit_mods, ""));
+        lines.extend(mod_lines(&self.crafted_mods, "{crafted}"));
+
+        if self.split {
+            lines.push("Split".to_owned());
+        }
+        if self.duplicated {
+            lines.push("Mirrored".to_owned());
+        }
+        if self.corrupted {
+            lines.push("Corrupted".to_owned());
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    /// Sockets in Path of Building's format, e.g. `R-G-B W`.
+    fn sockets_text(&self) -> String {
+        let mut text = String::new();
+        let mut group = None;
+        for socket in &self.sockets {
+            match group {
+                Some(group) if group == socket.group => text.push('-'),
+                Some(_) => text.push(' '),
+                None => {}
+            }
+            group = Some(socket.group);
+            text.push_str(&socket.colour);
+        }
+        text
+    }
+
+    fn influence_lines(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let influences = [
+            ("shaper", "Shaper Item"),
+            ("elder", "Elder Item"),
+            ("crusader", "Crusader Item"),
+            ("hunter", "Hunter Item"),
+            ("redeemer", "Redeemer Item"),
+            ("warlord", "Warlord Item"),
+        ];
+
+        influences
+            .into_iter()
+            .filter(|(key, _)| self.influences.get(*key).copied().unwrap_or(false))
+            .map(|(_, line)| line)
+            .chain(self.searing.then_some("Searing Exarch Item"))
+            .chain(self.tangled.then_some("Eater of Worlds Item"))
+            .chain(self.synthesised.then_some("Synthesised Item"))
+    }
+
+    /// Socketed gems grouped by their linked sockets.
+    fn gem_groups(&self) -> Vec<Vec<&CharacterItem>> {
+        let mut groups = Vec::<(u8, Vec<&CharacterItem>)>::new();
+
+        let mut gems = self
+            .socketed_items
+            .iter()
+            .filter(|item| !item.abyss_jewel)
+            .collect::<Vec<_>>();
+        gems.sort_by_key(|gem| gem.socket);
+
+        for gem in gems {
+            let group = gem
+                .socket
+                .and_then(|socket| self.sockets.get(socket))
+                .map(|socket| socket.group)
+                .unwrap_or(0);
+
+            match groups.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, gems)) => gems.push(gem),
+                None => groups.push((group, vec![gem])),
+            }
+        }
+
+        groups.into_iter().map(|(_, gems)| gems).collect()
+    }
+
+    /// The gem as a Path of Building `Gem` element.
+    fn to_gem(&self) -> Element {
+        let type_line = strip_markup(&self.type_line);
+
+        let mut gem = Element::new("Gem");
+        match poe_data::gems::by_name(type_line) {
+            Some((id, data)) => {
+                gem.set_attribute(
+                    "nameSpec",
+                    data.name.strip_suffix(" Support").unwrap_or(data.name),
+                );
+                gem.set_attribute("gemId", id);
+            }
+            // Unknown supports keep their suffix, so they are still recognized as supports.
+            None => gem.set_attribute("nameSpec", type_line),
+        }
+
+        let number = |name| {
+            self.property(name)
+                .map(|value| value.trim_start_matches('+'))
+                .map(|value| {
+                    value
+                        .split(|c: char| !c.is_ascii_digit())
+                        .next()
+                        .unwrap_or("")
+                })
+                .filter(|value| !value.is_empty())
+                .unwrap_or("0")
+        };
+        gem.set_attribute("level", number("Level"));
+        gem.set_attribute("quality", number("Quality"));
+        gem.set_attribute("enabled", "true");
+
+        gem
+    }
+}
+
+/// Properties written into the item text, named like in Path of Building.
+const ITEM_PROPERTIES: &[&str] = &[
+    "Quality",
+    "Armour",
+    "Evasion Rating",
+    "Energy Shield",
+    "Ward",
+    "Chance to Block",
+    "Physical Damage",
+    "Elemental Damage",
+    "Chaos Damage",
+    "Critical Strike Chance",
+    "Attacks per Second",
+    "Weapon Range",
+    "Radius",
+];
+
+fn find_property<'a>(properties: &'a [ItemProperty], name: &str) -> Option<&'a str> {
+    properties
+        .iter()
+        .find(|property| property.name == name)
+        .and_then(|property| property.values.first())
+        .map(|(value, _)| value.as_str())
+}
+
+/// Removes the formatting prefix of names, e.g. `<<set:MS>><<set:M>><<set:S>>Foo` becomes `Foo`.
+fn strip_markup(value: &str) -> &str {
+    value.rsplit(">>").next().unwrap_or(value)
+}
+
+/// Mods with a Path of Building tag, mods spanning multiple lines are tagged on every line.
+fn mod_lines<'a>(mods: &'a [String], tag: &'a str) -> impl Iterator<Item = String> + 'a {
+    mods.iter()
+        .flat_map(|m| m.lines())
+        .map(move |line| format!("{tag}{line}"))
+}
+
+fn deserialize_mastery_effects<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<(u32, u32)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Number(u32),
+        String(String),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Effects {
+        Map(HashMap<String, Value>),
+        List(Vec<Value>),
+    }
+
+    let values = match Effects::deserialize(deserializer)? {
+        Effects::Map(map) => map.into_values().collect(),
+        Effects::List(list) => list,
+    };
+
+    // Each value packs the mastery node into the lower and the effect into the upper 16 bits.
+    let mut effects = values
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::Number(value) => Some(value),
+            Value::String(value) => value.parse().ok(),
+        })
+        .map(|value| (value & 0xFFFF, value >> 16))
+        .collect::<Vec<_>>();
+    effects.sort_unstable();
+
+    Ok(effects)
+}
+
+/// Builds the Path of Building document of a character.
+pub(crate) fn to_xml(items: &CharacterItems, passives: &CharacterPassives) -> Result<String> {
+    let character = &items.character;
+
+    let mut item_elements = Vec::new();
+    let mut slots = Vec::new();
+    let mut skills = Vec::new();
+    let mut add_item = |item: &CharacterItem| -> Option<u16> {
+        let content = item.to_pob()?;
+        let id = item_elements.len() as u16 + 1;
+
+        let mut element = Element::new("Item");
+        element.set_attribute("id", &id.to_string());
+        element.set_text(&content);
+        item_elements.push(element);
+
+        Some(id)
+    };
+
+    for item in &items.items {
+        let Some(slot) = item.slot() else {
+            continue;
+        };
+        let Some(id) = add_item(item) else {
+            continue;
+        };
+        slots.push((slot.clone(), id));
+
+        let abyss_jewels = item.socketed_items.iter().filter(|item| item.abyss_jewel);
+        for (index, jewel) in abyss_jewels.enumerate() {
+            if let Some(id) = add_item(jewel) {
+                slots.push((format!("{slot} Abyssal Socket {}", index + 1), id));
+            }
+        }
+
+        for gems in item.gem_groups() {
+            let mut skill = Element::new("Skill");
+            skill.set_attribute("slot", &slot);
+            skill.set_attribute("enabled", "true");
+            skill.set_attribute("mainActiveSkill", "1");
+            skill.children = gems
+                .into_iter()
+                .map(|gem| Node::Element(gem.to_gem()))
+                .collect();
+            skills.push(skill);
+        }
+    }
+
+    let jewel_slots = poe_tree::jewel_slots(poe_tree::Version::latest());
+    let mut sockets = Vec::new();
+    for jewel in &passives.items {
+        let Some(&node_id) = jewel.x.and_then(|x| jewel_slots.get(x as usize)) else {
+            continue;
+        };
+        if let Some(id) = add_item(jewel) {
+            sockets.push((node_id, id));
+        }
+    }
+
+    // The group with the most links is most likely the main skill.
+    let main_socket_group = skills
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, skill)| (skill.children.len(), std::cmp::Reverse(*index)))
+        .map(|(index, _)| index + 1)
+        .unwrap_or(1);
+
+    let tree = TreeUrl {
+        game_version: None,
+        class_id: character.class_id,
+        ascendancy_id: character.ascendancy_class,
+        alternate_ascendancy_id: 0,
+        nodes: passives.hashes.clone(),
+        cluster_nodes: passives
+            .hashes_ex
+            .iter()
+            .map(|node| node + CLUSTER_NODE_OFFSET)
+            .collect(),
+        mastery_effects: passives.mastery_effects.clone(),
+    };
+
+    let mut build = Element::new("Build");
+    build.set_attribute("level", &character.level.to_string());
+    build.set_attribute("className", tree.class_name());
+    build.set_attribute("ascendClassName", tree.ascendancy_name().unwrap_or("None"));
+    build.set_attribute("mainSocketGroup", &main_socket_group.to_string());
+
+    let mut skill_set = Element::new("SkillSet");
+    skill_set.set_attribute("id", "1");
+    skill_set.children = skills.into_iter().map(Node::Element).collect();
+    let mut skills = Element::new("Skills");
+    skills.set_attribute("activeSkillSet", "1");
+    skills.children.push(Node::Element(skill_set));
+
+    let mut spec = Element::new("Spec");
+    for (name, value) in tree.spec_attributes() {
+        spec.set_attribute(name, &value);
+    }
+    spec.child_or_insert("URL").set_text(&tree.to_url()?);
+    let spec_sockets = spec.child_or_insert("Sockets");
+    for (node_id, item_id) in sockets {
+        let mut socket = Element::new("Socket");
+        socket.set_attribute("nodeId", &node_id.to_string());
+        socket.set_attribute("itemId", &item_id.to_string());
+        spec_sockets.children.push(Node::Element(socket));
+    }
+    let mut tree_element = Element::new("Tree");
+    tree_element.set_attribute("activeSpec", "1");
+    tree_element.children.push(Node::Element(spec));
+
+    let mut item_set = Element::new("ItemSet");
+    item_set.set_attribute("id", "1");
+    item_set.set_attribute("useSecondWeaponSet", "false");
+    for (name, item_id) in slots {
+        let mut slot = Element::new("Slot");
+        slot.set_attribute("name", &name);
+        slot.set_attribute("itemId", &item_id.to_string());
+        item_set.children.push(Node::Element(slot));
+    }
+    let mut items_element = Element::new("Items");
+    items_element.set_attribute("activeItemSet", "1");
+    items_element.children = item_elements.into_iter().map(Node::Element).collect();
+    items_element.children.push(Node::Element(item_set));
+
+    let mut root = Element::new("PathOfBuilding");
+    root.children = vec![
+        Node::Element(build),
+        Node::Element(skills),
+        Node::Element(tree_element),
+        Node::Element(items_element),
+    ];
+
+    root.to_xml()
+}
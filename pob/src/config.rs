@@ -1,46 +1,74 @@
-#[derive(Debug, Copy, Clone)]
-pub enum Config {
-    Boss,
-    BrittleEffect,
-    CoveredInAsh,
+include!(concat!(env!("OUT_DIR"), "/configs.rs"));
+
+impl Config {
+    /// Looks up a configuration option by the name of its Path of Building input, e.g. `enemyIsBoss`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        CONFIGS.get(name).copied()
+    }
+}
+
+/// Section of the Path of Building configuration tab an option belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConfigSection {
+    General,
+    /// Bandit and pantheon choices.
+    Bandit,
+    Skills,
+    Charges,
+    Conditions,
+    Enemy,
+    MapMods,
     CustomMods,
-    EnemeyHit,
-    EnemyBrittled,
-    EnemySapped,
-    EnemyScorched,
-    EnemyShocked,
-    Focused,
-    FrenzyCharges,
-    FrenzyChargesAmount,
-    PowerCharges,
-    PowerChargesAmount,
-    SapEffect,
-    ScorchedEffect,
-    WitherStacks,
-    ShockEffect,
 }
 
-impl Config {
-    fn name(&self) -> &'static str {
+impl ConfigSection {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::BrittleEffect => "conditionBrittleEffect",
-            Self::Boss => "enemyIsBoss",
-            Self::CoveredInAsh => "conditionEnemyCoveredInAsh",
-            Self::CustomMods => "customMods",
-            Self::EnemyBrittled => "conditionEnemyBrittle",
-            Self::EnemeyHit => "enemyHit",
-            Self::EnemySapped => "conditionEnemySapped",
-            Self::EnemyScorched => "conditionEnemyScorched",
-            Self::EnemyShocked => "conditionEnemyShocked",
-            Self::Focused => "conditionFocused",
-            Self::FrenzyCharges => "useFrenzyCharges",
-            Self::FrenzyChargesAmount => "overrideFrenzyCharges",
-            Self::PowerCharges => "usePowerCharges",
-            Self::PowerChargesAmount => "overrideFrenzyCharges",
-            Self::WitherStacks => "multiplierWitheredStackCount",
-            Self::SapEffect => "conditionSapEffect",
-            Self::ScorchedEffect => "conditionScorchedEffect",
-            Self::ShockEffect => "conditionShockEffect",
+            Self::General => "General",
+            Self::Bandit => "Bandit & Pantheon",
+            Self::Skills => "Skill Options",
+            Self::Charges => "Charges",
+            Self::Conditions => "Conditions",
+            Self::Enemy => "Enemy Stats",
+            Self::MapMods => "Map Modifiers",
+            Self::CustomMods => "Custom Modifiers",
+        }
+    }
+}
+
+/// The type of value a configuration option takes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConfigType {
+    /// A checkbox.
+    Boolean,
+    /// A whole number, e.g. a charge count.
+    Integer,
+    Number,
+    /// One of a fixed list of choices.
+    List,
+    /// Free text, e.g. custom modifiers.
+    Text,
+}
+
+impl ConfigType {
+    /// Converts a value into the type of the option, values which cannot be converted become [`ConfigValue::None`].
+    pub fn coerce<'a>(&self, value: ConfigValue<'a>) -> ConfigValue<'a> {
+        match (self, value) {
+            (Self::Boolean, ConfigValue::Bool(_)) => value,
+            (Self::Boolean, ConfigValue::Number(n)) => ConfigValue::Bool(n != 0.0),
+            (Self::Boolean, ConfigValue::String(s)) => match s {
+                "true" => ConfigValue::Bool(true),
+                "false" => ConfigValue::Bool(false),
+                _ => ConfigValue::None,
+            },
+            (Self::Integer, ConfigValue::Number(n)) => ConfigValue::Number(n.trunc()),
+            (Self::Number, ConfigValue::Number(_)) => value,
+            (Self::Integer | Self::Number, ConfigValue::String(s)) => match s.trim().parse() {
+                Ok(n) => self.coerce(ConfigValue::Number(n)),
+                Err(_) => ConfigValue::None,
+            },
+            (Self::List | Self::Text, ConfigValue::String(s)) if !s.is_empty() => value,
+            _ => ConfigValue::None,
         }
     }
 }
@@ -75,6 +103,7 @@ impl PartialEq<Config> for String {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ConfigValue<'a> {
     String(&'a str),
     Number(f32),
@@ -108,3 +137,54 @@ impl<'a> ConfigValue<'a> {
         !matches!(self, Self::None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        for config in Config::ALL {
+            assert_eq!(Some(*config), Config::from_name(config.name()));
+        }
+        assert_eq!(
+            Some(Config::PowerChargesAmount),
+            Config::from_name("overridePowerCharges")
+        );
+        assert_eq!(
+            Some(Config::FrenzyChargesAmount),
+            Config::from_name("overrideFrenzyCharges")
+        );
+        assert_eq!(None, Config::from_name("notAnOption"));
+    }
+
+    #[test]
+    fn coerce() {
+        let int = Config::PowerChargesAmount.value_type();
+        assert_eq!(
+            ConfigValue::Number(3.0),
+            int.coerce(ConfigValue::Number(3.4))
+        );
+        assert_eq!(
+            ConfigValue::Number(2.0),
+            int.coerce(ConfigValue::String("2"))
+        );
+        assert_eq!(ConfigValue::None, int.coerce(ConfigValue::Bool(true)));
+
+        let boolean = Config::Focused.value_type();
+        assert_eq!(
+            ConfigValue::Bool(true),
+            boolean.coerce(ConfigValue::Number(1.0))
+        );
+        assert_eq!(
+            ConfigValue::Bool(false),
+            boolean.coerce(ConfigValue::Bool(false))
+        );
+
+        let list = Config::Boss.value_type();
+        assert_eq!(
+            ConfigValue::String("Pinnacle"),
+            list.coerce(ConfigValue::String("Pinnacle"))
+        );
+    }
+}
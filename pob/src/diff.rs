@@ -0,0 +1,582 @@
+//! Structured differences between two builds.
+//!
+//! All types are owned and serializable, the diff is computed on the server
+//! and sent to the client as is.
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, ConfigValue, Gear, Item, PathOfBuilding, Skill, Stat};
+
+/// Computes the difference from the `before` build to the `after` build.
+pub fn diff<A, B>(before: &A, after: &B) -> BuildDiff
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    BuildDiff {
+        stats: diff_stats(before, after),
+        trees: diff_trees(before, after),
+        skill_sets: diff_skill_sets(before, after),
+        item_sets: diff_item_sets(before, after),
+        configs: diff_configs(before, after),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildDiff {
+    pub stats: Vec<StatDiff>,
+    /// Tree specs, matched by position.
+    pub trees: Vec<TreeDiff>,
+    /// Skill sets, matched by position.
+    pub skill_sets: Vec<SkillSetDiff>,
+    /// Item sets, matched by position.
+    pub item_sets: Vec<ItemSetDiff>,
+    pub configs: Vec<ConfigDiff>,
+}
+
+impl BuildDiff {
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+            && self.trees.iter().all(TreeDiff::is_empty)
+            && self.skill_sets.iter().all(|ss| ss.skills.is_empty())
+            && self.item_sets.iter().all(|is| is.slots.is_empty())
+            && self.configs.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatDiff {
+    /// PoB name of the stat.
+    pub stat: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl StatDiff {
+    /// Numeric change of the stat, if both sides are numbers.
+    pub fn delta(&self) -> Option<f64> {
+        let before = self.before.as_deref().unwrap_or("0").parse::<f64>().ok()?;
+        let after = self.after.as_deref().unwrap_or("0").parse::<f64>().ok()?;
+        Some(after - before)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeDiff {
+    pub title: Option<String>,
+    pub added: Vec<u32>,
+    pub removed: Vec<u32>,
+    /// Mastery effects as `(node, effect)` pairs.
+    pub mastery_effects_added: Vec<(u32, u32)>,
+    pub mastery_effects_removed: Vec<(u32, u32)>,
+}
+
+impl TreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.mastery_effects_added.is_empty()
+            && self.mastery_effects_removed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillSetDiff {
+    pub title: Option<String>,
+    /// Only contains skills which changed.
+    pub skills: Vec<SkillDiff>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillDiff {
+    /// Display name of the skill, either its label or its active gems.
+    pub name: String,
+    pub change: Change,
+    pub gems_added: Vec<String>,
+    pub gems_removed: Vec<String>,
+    pub gems_changed: Vec<GemDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GemDiff {
+    pub name: String,
+    pub before: GemState,
+    pub after: GemState,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GemState {
+    pub level: u8,
+    pub quality: u8,
+    pub is_enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemSetDiff {
+    pub title: Option<String>,
+    /// Only contains slots which changed.
+    pub slots: Vec<SlotDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotDiff {
+    pub slot: String,
+    /// Name of the item previously in the slot.
+    pub before: Option<String>,
+    /// Name of the item now in the slot.
+    pub after: Option<String>,
+    /// Mod lines of the item now in the slot which the previous item did not have.
+    pub mods_added: Vec<String>,
+    /// Mod lines of the previous item which the item now in the slot does not have.
+    pub mods_removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// PoB name of the config option.
+    pub config: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+fn diff_stats<A, B>(before: &A, after: &B) -> Vec<StatDiff>
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    Stat::ALL
+        .iter()
+        .filter_map(|&stat| {
+            let b = before.stat(stat);
+            let a = after.stat(stat);
+            (a != b).then(|| StatDiff {
+                stat: <&'static str>::from(stat).to_owned(),
+                before: b.map(ToOwned::to_owned),
+                after: a.map(ToOwned::to_owned),
+            })
+        })
+        .collect()
+}
+
+fn diff_trees<A, B>(before: &A, after: &B) -> Vec<TreeDiff>
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    let before = before.tree_specs();
+    let after = after.tree_specs();
+
+    (0..before.len().max(after.len()))
+        .map(|i| {
+            let b = before.get(i);
+            let a = after.get(i);
+
+            let b_nodes = b.map_or(&[][..], |spec| spec.nodes);
+            let a_nodes = a.map_or(&[][..], |spec| spec.nodes);
+            let b_masteries = b.map_or(&[][..], |spec| spec.mastery_effects);
+            let a_masteries = a.map_or(&[][..], |spec| spec.mastery_effects);
+
+            TreeDiff {
+                title: a.or(b).and_then(|spec| spec.title).map(ToOwned::to_owned),
+                added: missing(a_nodes, b_nodes),
+                removed: missing(b_nodes, a_nodes),
+                mastery_effects_added: missing(a_masteries, b_masteries),
+                mastery_effects_removed: missing(b_masteries, a_masteries),
+            }
+        })
+        .collect()
+}
+
+fn diff_skill_sets<A, B>(before: &A, after: &B) -> Vec<SkillSetDiff>
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    let before = before.skill_sets();
+    let after = after.skill_sets();
+
+    (0..before.len().max(after.len()))
+        .map(|i| {
+            let b = before.get(i);
+            let a = after.get(i);
+
+            let b_skills = b.map_or(&[][..], |ss| &ss.skills);
+            let a_skills = a.map_or(&[][..], |ss| &ss.skills);
+
+            SkillSetDiff {
+                title: a.or(b).and_then(|ss| ss.title).map(ToOwned::to_owned),
+                skills: diff_skills(b_skills, a_skills),
+            }
+        })
+        .collect()
+}
+
+fn diff_skills(before: &[Skill], after: &[Skill]) -> Vec<SkillDiff> {
+    let mut unmatched: Vec<Option<&Skill>> = before.iter().map(Some).collect();
+    let mut result = Vec::new();
+
+    for a in after {
+        let name = skill_name(a);
+        let matched = unmatched
+            .iter_mut()
+            .find(|b| matches!(b, Some(b) if skill_name(b) == name))
+            .and_then(Option::take);
+
+        match matched {
+            Some(b) => {
+                let diff = diff_gems(name, b, a);
+                if !diff.gems_added.is_empty()
+                    || !diff.gems_removed.is_empty()
+                    || !diff.gems_changed.is_empty()
+                {
+                    result.push(diff);
+                }
+            }
+            None => result.push(SkillDiff {
+                name,
+                change: Change::Added,
+                gems_added: a.gems.iter().map(|g| g.name.to_owned()).collect(),
+                gems_removed: Vec::new(),
+                gems_changed: Vec::new(),
+            }),
+        }
+    }
+
+    result.extend(unmatched.into_iter().flatten().map(|b| SkillDiff {
+        name: skill_name(b),
+        change: Change::Removed,
+        gems_added: Vec::new(),
+        gems_removed: b.gems.iter().map(|g| g.name.to_owned()).collect(),
+        gems_changed: Vec::new(),
+    }));
+
+    result
+}
+
+fn diff_gems(name: String, before: &Skill, after: &Skill) -> SkillDiff {
+    let mut unmatched: Vec<Option<&crate::Gem>> = before.gems.iter().map(Some).collect();
+    let mut gems_added = Vec::new();
+    let mut gems_changed = Vec::new();
+
+    for a in &after.gems {
+        let matched = unmatched
+            .iter_mut()
+            .find(|b| matches!(b, Some(b) if b.name == a.name))
+            .and_then(Option::take);
+
+        match matched {
+            Some(b) => {
+                let (before, after) = (gem_state(b), gem_state(a));
+                if before != after {
+                    gems_changed.push(GemDiff {
+                        name: a.name.to_owned(),
+                        before,
+                        after,
+                    });
+                }
+            }
+            None => gems_added.push(a.name.to_owned()),
+        }
+    }
+
+    let gems_removed = unmatched
+        .into_iter()
+        .flatten()
+        .map(|g| g.name.to_owned())
+        .collect();
+
+    SkillDiff {
+        name,
+        change: Change::Changed,
+        gems_added,
+        gems_removed,
+        gems_changed,
+    }
+}
+
+fn gem_state(gem: &crate::Gem) -> GemState {
+    GemState {
+        level: gem.level,
+        quality: gem.quality,
+        is_enabled: gem.is_enabled,
+    }
+}
+
+/// Name used to match up skills between builds.
+fn skill_name(skill: &Skill) -> String {
+    if let Some(label) = skill.label.filter(|l| !l.is_empty()) {
+        return label.to_owned();
+    }
+
+    let mut names = skill.gems.iter().filter(|g| g.is_active).map(|g| g.name);
+    let mut name = names.next().or_else(|| skill.gems.first().map(|g| g.name));
+    if name.is_none() {
+        name = skill.slot;
+    }
+    let mut name = name.unwrap_or_default().to_owned();
+    for other in names {
+        name.push_str(", ");
+        name.push_str(other);
+    }
+    name
+}
+
+fn diff_item_sets<A, B>(before: &A, after: &B) -> Vec<ItemSetDiff>
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    let before = before.item_sets();
+    let after = after.item_sets();
+
+    (0..before.len().max(after.len()))
+        .map(|i| {
+            let b = before.get(i);
+            let a = after.get(i);
+
+            let b_slots = b.map(|set| gear_slots(&set.gear)).unwrap_or_default();
+            let a_slots = a.map(|set| gear_slots(&set.gear)).unwrap_or_default();
+
+            let mut slots: Vec<&str> = b_slots.iter().map(|(slot, _)| *slot).collect();
+            slots.extend(
+                a_slots
+                    .iter()
+                    .map(|(slot, _)| *slot)
+                    .filter(|slot| !b_slots.iter().any(|(s, _)| s == slot)),
+            );
+
+            let slots = slots
+                .into_iter()
+                .filter_map(|slot| {
+                    let b = find_slot(&b_slots, slot);
+                    let a = find_slot(&a_slots, slot);
+                    if b == a {
+                        return None;
+                    }
+
+                    let b_mods = item_mods(b);
+                    let a_mods = item_mods(a);
+                    Some(SlotDiff {
+                        slot: slot.to_owned(),
+                        before: b.map(item_name),
+                        after: a.map(item_name),
+                        mods_added: missing(&a_mods, &b_mods)
+                            .into_iter()
+                            .map(ToOwned::to_owned)
+                            .collect(),
+                        mods_removed: missing(&b_mods, &a_mods)
+                            .into_iter()
+                            .map(ToOwned::to_owned)
+                            .collect(),
+                    })
+                })
+                .collect();
+
+            ItemSetDiff {
+                title: a.or(b).and_then(|set| set.title).map(ToOwned::to_owned),
+                slots,
+            }
+        })
+        .collect()
+}
+
+fn gear_slots<'a>(gear: &Gear<'a>) -> Vec<(&'a str, &'a str)> {
+    gear.slots
+        .iter()
+        .map(|slot| (slot.name, slot.item))
+        .collect()
+}
+
+fn find_slot<'a>(slots: &[(&str, &'a str)], slot: &str) -> Option<&'a str> {
+    slots
+        .iter()
+        .find(|(s, _)| *s == slot)
+        .map(|(_, item)| *item)
+}
+
+fn item_name(item: &str) -> String {
+    match Item::parse(item) {
+        Ok(item) => item.name.unwrap_or(item.base).to_owned(),
+        Err(_) => item.lines().next().unwrap_or_default().to_owned(),
+    }
+}
+
+/// Mod lines of an item without the PoB line prefixes, only of the selected variant.
+///
+/// Items which fail to parse are compared line by line.
+fn item_mods(item: Option<&str>) -> Vec<&str> {
+    let Some(item) = item else {
+        return Vec::new();
+    };
+    match Item::parse(item) {
+        Ok(item) => item.mods().map(|m| m.line).collect(),
+        Err(_) => item.lines().collect(),
+    }
+}
+
+fn diff_configs<A, B>(before: &A, after: &B) -> Vec<ConfigDiff>
+where
+    A: PathOfBuilding + ?Sized,
+    B: PathOfBuilding + ?Sized,
+{
+    let mut diffs = Config::ALL
+        .iter()
+        .filter_map(|&config| {
+            let name: &'static str = config.into();
+            let b = config_value(before.config(config));
+            let a = config_value(after.config(config));
+            (a != b).then(|| ConfigDiff {
+                config: name.to_owned(),
+                before: b,
+                after: a,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Options unknown to this version are compared by their raw values.
+    let b = before
+        .unknown_configs()
+        .map(|(name, value)| (name, config_value(value)))
+        .collect::<BTreeMap<_, _>>();
+    let a = after
+        .unknown_configs()
+        .map(|(name, value)| (name, config_value(value)))
+        .collect::<BTreeMap<_, _>>();
+    for name in b.keys().chain(a.keys()).collect::<BTreeSet<_>>() {
+        let b = b.get(name).cloned().flatten();
+        let a = a.get(name).cloned().flatten();
+        if a != b {
+            diffs.push(ConfigDiff {
+                config: (*name).to_owned(),
+                before: b,
+                after: a,
+            });
+        }
+    }
+
+    diffs
+}
+
+fn config_value(value: ConfigValue) -> Option<String> {
+    match value {
+        ConfigValue::String(value) => Some(value.to_owned()),
+        ConfigValue::Number(value) => Some(value.to_string()),
+        ConfigValue::Bool(value) => Some(value.to_string()),
+        ConfigValue::None => None,
+    }
+}
+
+/// Elements of `a` which are not in `b`.
+fn missing<T: PartialEq + Copy>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().filter(|x| !b.contains(x)).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+    static V319_MASTERY_EFFECTS: &str = include_str!("../test/319_mastery_effects.xml");
+
+    #[test]
+    fn diff_same_is_empty() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert!(diff(&pob, &pob).is_empty());
+    }
+
+    #[test]
+    fn diff_edited() {
+        let before = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let mut after = SerdePathOfBuilding::from_xml_editable(V316_POISON_OCC).unwrap();
+        after
+            .set_config(Config::Boss, ConfigValue::String("Pinnacle"))
+            .unwrap();
+        assert!(after.set_item(8, "Rarity: NORMAL\nLion Pelt").unwrap());
+
+        let diff = diff(&before, &after);
+        assert!(diff.stats.is_empty());
+        assert_eq!(
+            diff.configs,
+            vec![ConfigDiff {
+                config: "enemyIsBoss".to_owned(),
+                before: before.config(Config::Boss).string().map(ToOwned::to_owned),
+                after: Some("Pinnacle".to_owned()),
+            }]
+        );
+
+        let slots = diff
+            .item_sets
+            .iter()
+            .flat_map(|set| &set.slots)
+            .collect::<Vec<_>>();
+        assert!(!slots.is_empty());
+        assert!(slots
+            .iter()
+            .all(|slot| slot.after.as_deref() == Some("Lion Pelt")));
+    }
+
+    #[test]
+    fn diff_item_mods() {
+        let before = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let mut after = SerdePathOfBuilding::from_xml_editable(V316_POISON_OCC).unwrap();
+        let item = before
+            .item_by_id(8)
+            .unwrap()
+            .replace("+99 to maximum Life", "+109 to maximum Life");
+        assert!(after.set_item(8, item).unwrap());
+
+        let diff = diff(&before, &after);
+        let slot = diff
+            .item_sets
+            .iter()
+            .flat_map(|set| &set.slots)
+            .next()
+            .unwrap();
+        assert_eq!(slot.before, slot.after);
+        assert_eq!(vec!["+109 to maximum Life"], slot.mods_added);
+        assert_eq!(vec!["+99 to maximum Life"], slot.mods_removed);
+    }
+
+    #[test]
+    fn diff_different_builds() {
+        let before = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let after = SerdePathOfBuilding::from_xml(V319_MASTERY_EFFECTS).unwrap();
+
+        let diff = diff(&before, &after);
+        assert!(!diff.stats.is_empty());
+        assert!(diff
+            .stats
+            .iter()
+            .filter(|s| s.before.is_some() && s.after.is_some())
+            .all(|s| s.delta().is_some()));
+
+        let tree = &diff.trees[0];
+        assert!(!tree.added.is_empty());
+        assert!(!tree.removed.is_empty());
+
+        let after_tree = after.tree_specs().into_iter().next().unwrap();
+        assert!(tree.added.iter().all(|n| after_tree.nodes.contains(n)));
+        assert!(tree
+            .mastery_effects_added
+            .iter()
+            .all(|me| after_tree.mastery_effects.contains(me)));
+
+        // The second build has no skills at all.
+        let skills = diff
+            .skill_sets
+            .iter()
+            .flat_map(|ss| &ss.skills)
+            .collect::<Vec<_>>();
+        assert!(!skills.is_empty());
+        assert!(skills.iter().all(|s| s.change == Change::Removed));
+    }
+}
@@ -13,6 +13,53 @@ pub enum Error {
     #[error("failed to deflate/decompress input: {0}")]
     Deflate(std::io::Error),
 
+    #[error("input is neither UTF-8 nor UTF-16 text, make sure to paste the build code itself")]
+    InvalidEncoding,
+
+    #[error("input starts with a UTF-16 byte order mark but is not valid UTF-16")]
+    InvalidUtf16,
+
+    #[error(
+        "build code mixes standard and URL-safe base64, it was probably modified after copying"
+    )]
+    MixedBase64,
+
+    #[error("build code is incomplete, make sure to copy all of it")]
+    Truncated,
+
+    #[error("input is not a Path of Building build")]
+    NotABuild,
+
+    #[error("build is too large")]
+    TooLarge,
+
     #[error("failed to parse build at: {0} ({1})")]
     ParseXml(String, quick_xml::de::DeError),
+
+    #[error("invalid xml: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("failed to compress input: {0}")]
+    Compress(std::io::Error),
+
+    #[error("build was not parsed as editable")]
+    NotEditable,
+
+    #[error(transparent)]
+    TreeUrl(#[from] crate::InvalidTreeUrl),
+}
+
+/// A part of a build which failed to parse and was skipped or replaced with a default,
+/// see [`SerdePathOfBuilding::from_xml_lenient`](crate::SerdePathOfBuilding::from_xml_lenient).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Location of the broken part, e.g. `Build.ascendClassName` or `Items.ItemSet[1]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
 }
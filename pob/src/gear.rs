@@ -0,0 +1,204 @@
+use crate::{Item, ItemSet};
+
+/// A stat summed up over the gear of an item set.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GearStat {
+    Life,
+    EnergyShield,
+    Mana,
+    Strength,
+    Dexterity,
+    Intelligence,
+    FireResistance,
+    ColdResistance,
+    LightningResistance,
+    ChaosResistance,
+    MovementSpeed,
+    SpellSuppression,
+}
+
+impl GearStat {
+    pub const ALL: [GearStat; 12] = [
+        Self::Life,
+        Self::EnergyShield,
+        Self::Mana,
+        Self::Strength,
+        Self::Dexterity,
+        Self::Intelligence,
+        Self::FireResistance,
+        Self::ColdResistance,
+        Self::LightningResistance,
+        Self::ChaosResistance,
+        Self::MovementSpeed,
+        Self::SpellSuppression,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Life => "Life",
+            Self::EnergyShield => "Energy Shield",
+            Self::Mana => "Mana",
+            Self::Strength => "Strength",
+            Self::Dexterity => "Dexterity",
+            Self::Intelligence => "Intelligence",
+            Self::FireResistance => "Fire Resistance",
+            Self::ColdResistance => "Cold Resistance",
+            Self::LightningResistance => "Lightning Resistance",
+            Self::ChaosResistance => "Chaos Resistance",
+            Self::MovementSpeed => "Movement Speed",
+            Self::SpellSuppression => "Spell Suppression",
+        }
+    }
+
+    /// Whether the stat is a percentage, e.g. resistances.
+    pub fn is_percent(&self) -> bool {
+        matches!(
+            self,
+            Self::FireResistance
+                | Self::ColdResistance
+                | Self::LightningResistance
+                | Self::ChaosResistance
+                | Self::MovementSpeed
+                | Self::SpellSuppression
+        )
+    }
+
+    /// The gear stats a mod stat contributes to, e.g. all elemental resistances.
+    fn from_stat_id(id: &str) -> &'static [GearStat] {
+        use GearStat::*;
+
+        match id {
+            "base_maximum_life" => &[Life],
+            "base_maximum_energy_shield" => &[EnergyShield],
+            "base_maximum_mana" => &[Mana],
+            "additional_strength" => &[Strength],
+            "additional_dexterity" => &[Dexterity],
+            "additional_intelligence" => &[Intelligence],
+            "additional_all_attributes" => &[Strength, Dexterity, Intelligence],
+            "additional_strength_and_dexterity" => &[Strength, Dexterity],
+            "additional_strength_and_intelligence" => &[Strength, Intelligence],
+            "additional_dexterity_and_intelligence" => &[Dexterity, Intelligence],
+            "base_fire_damage_resistance_%" => &[FireResistance],
+            "base_cold_damage_resistance_%" => &[ColdResistance],
+            "base_lightning_damage_resistance_%" => &[LightningResistance],
+            "base_chaos_damage_resistance_%" => &[ChaosResistance],
+            "base_resist_all_elements_%" => &[FireResistance, ColdResistance, LightningResistance],
+            "fire_and_cold_damage_resistance_%" => &[FireResistance, ColdResistance],
+            "fire_and_lightning_damage_resistance_%" => &[FireResistance, LightningResistance],
+            "cold_and_lightning_damage_resistance_%" => &[ColdResistance, LightningResistance],
+            "fire_and_chaos_damage_resistance_%" => &[FireResistance, ChaosResistance],
+            "cold_and_chaos_damage_resistance_%" => &[ColdResistance, ChaosResistance],
+            "lightning_and_chaos_damage_resistance_%" => &[LightningResistance, ChaosResistance],
+            "base_movement_velocity_+%" => &[MovementSpeed],
+            "base_spell_suppression_chance_%" => &[SpellSuppression],
+            _ => &[],
+        }
+    }
+}
+
+/// Values of all [`GearStat`]s.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GearStats([f64; GearStat::ALL.len()]);
+
+impl GearStats {
+    /// Sums up the stats of all mods of an item, including enchants,
+    /// implicits and the selected variant of uniques.
+    pub fn from_item(item: &Item) -> Self {
+        let mut stats = Self::default();
+
+        for stat in item.mods().filter_map(|m| m.stats()).flatten() {
+            for &gear_stat in GearStat::from_stat_id(stat.id) {
+                stats.0[gear_stat as usize] += stat.value;
+            }
+        }
+
+        // Local energy shield mods are already part of the energy shield of armour pieces.
+        stats.0[GearStat::EnergyShield as usize] += item.energy_shield as f64;
+
+        stats
+    }
+
+    pub fn get(&self, stat: GearStat) -> f64 {
+        self.0[stat as usize]
+    }
+
+    /// All stats with a non zero value.
+    pub fn iter(&self) -> impl Iterator<Item = (GearStat, f64)> + '_ {
+        GearStat::ALL
+            .into_iter()
+            .map(|stat| (stat, self.get(stat)))
+            .filter(|(_, value)| *value != 0.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+impl std::ops::AddAssign<&GearStats> for GearStats {
+    fn add_assign(&mut self, rhs: &GearStats) {
+        for (value, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *value += rhs;
+        }
+    }
+}
+
+/// Stats contributed by the item in a gear slot.
+#[derive(Debug)]
+pub struct SlotStats<'a> {
+    /// Name of the slot as used by Path of Building, e.g. `Body Armour`.
+    pub slot: &'a str,
+    pub item: Item<'a>,
+    pub stats: GearStats,
+    /// Mod lines with values which are not in the stat description table
+    /// and therefore missing from `stats`.
+    pub untranslated: Vec<&'a str>,
+}
+
+impl<'a> ItemSet<'a> {
+    /// Stats contributed by each slot of the active weapon set, items which fail to parse are skipped.
+    pub fn gear_stats(&self) -> Vec<SlotStats<'a>> {
+        self.gear_stats_with(self.use_second_weapon_set)
+    }
+
+    /// Like [`Self::gear_stats`] but with the given weapon set instead of the active one.
+    pub fn gear_stats_with(&self, second_weapon_set: bool) -> Vec<SlotStats<'a>> {
+        self.gear
+            .slots
+            .iter()
+            .filter(|slot| {
+                if slot.is_weapon_swap() {
+                    second_weapon_set
+                } else if slot.name.starts_with("Weapon ") {
+                    !second_weapon_set
+                } else {
+                    true
+                }
+            })
+            .filter_map(|slot| {
+                let item = Item::parse(slot.item).ok()?;
+                let untranslated = item
+                    .mods()
+                    .filter(|m| m.line.bytes().any(|b| b.is_ascii_digit()))
+                    .filter(|m| m.stats().is_none())
+                    .map(|m| m.line)
+                    .collect();
+                Some(SlotStats {
+                    slot: slot.name,
+                    stats: GearStats::from_item(&item),
+                    item,
+                    untranslated,
+                })
+            })
+            .collect()
+    }
+
+    /// Sum of the stats of all slots of the active weapon set.
+    pub fn total_gear_stats(&self) -> GearStats {
+        let mut total = GearStats::default();
+        for slot in self.gear_stats() {
+            total += &slot.stats;
+        }
+        total
+    }
+}
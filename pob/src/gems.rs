@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// A support/gem can grant additional skills which can be configured in PoB.
 ///
 /// PoB treates those granted skills as normal skills 'attached' to the gem.
@@ -56,3 +58,198 @@ pub fn pob_id_as_game_id(id: &str) -> Option<&'static str> {
         _ => None,
     }
 }
+
+const GEM_ID_PREFIX: &str = "Metadata/Items/Gems/";
+
+/// Suffixes of the skill ids of transfigured gems.
+const TRANSFIGURED_SUFFIXES: [&str; 3] = ["AltX", "AltY", "AltZ"];
+
+/// Variant of a gem, derived from its gem id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GemVariant {
+    Normal,
+    /// A vaal gem, which also grants the skill of the gem it is paired with.
+    Vaal,
+    /// An awakened support gem, e.g. `Awakened Added Fire Damage`.
+    Awakened,
+    /// A transfigured gem, e.g. `Fireball of Meteors`.
+    Transfigured,
+}
+
+impl GemVariant {
+    pub(crate) fn from_ids(gem_id: Option<&str>, skill_id: Option<&str>, name: &str) -> Self {
+        let is_transfigured = |id: &str| TRANSFIGURED_SUFFIXES.iter().any(|s| id.ends_with(s));
+
+        match gem_id {
+            Some(id) if id.starts_with("Metadata/Items/Gems/SkillGemVaal") => Self::Vaal,
+            Some(id)
+                if id.starts_with("Metadata/Items/Gems/SupportGem") && id.ends_with("Plus") =>
+            {
+                Self::Awakened
+            }
+            Some(id) if is_transfigured(id) => Self::Transfigured,
+            _ if skill_id.is_some_and(is_transfigured) => Self::Transfigured,
+            // Gems without a gem id, e.g. from very old builds.
+            None if name.starts_with("Vaal ") => Self::Vaal,
+            None if name.starts_with("Awakened ") => Self::Awakened,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// Alternate quality of a gem, Path of Building stores it as `qualityId`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlternateQuality {
+    Anomalous,
+    Divergent,
+    Phantasmal,
+}
+
+impl AlternateQuality {
+    pub const ALL: [AlternateQuality; 3] = [Self::Anomalous, Self::Divergent, Self::Phantasmal];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Anomalous => "Anomalous",
+            Self::Divergent => "Divergent",
+            Self::Phantasmal => "Phantasmal",
+        }
+    }
+
+    pub(crate) fn from_quality_id(quality_id: &str) -> Option<Self> {
+        match quality_id {
+            "Alternate1" => Some(Self::Anomalous),
+            "Alternate2" => Some(Self::Divergent),
+            "Alternate3" => Some(Self::Phantasmal),
+            _ => None,
+        }
+    }
+}
+
+/// Id of the gem a variant is based on, e.g. `Added Fire Damage` for `Awakened Added Fire Damage`
+/// or `Arc` for `Vaal Arc`. Returns `None` for gems which are not a variant.
+pub(crate) fn base_gem_id(gem_id: &str, variant: GemVariant) -> Option<String> {
+    let id = gem_id.strip_prefix(GEM_ID_PREFIX)?;
+    let base = match variant {
+        GemVariant::Normal => return None,
+        GemVariant::Vaal => format!("SkillGem{}", id.strip_prefix("SkillGemVaal")?),
+        GemVariant::Awakened => id.strip_suffix("Plus")?.to_owned(),
+        // Path of Building either uses the id of the transfigured gem or of the base gem.
+        GemVariant::Transfigured => TRANSFIGURED_SUFFIXES
+            .iter()
+            .find_map(|suffix| id.strip_suffix(suffix))
+            .unwrap_or(id)
+            .to_owned(),
+    };
+    Some(format!("{GEM_ID_PREFIX}{base}"))
+}
+
+/// Name of the gem a variant is based on, as Path of Building names it (without ` Support`).
+///
+/// Uses the gem data and falls back to removing the variant prefix from the name.
+pub(crate) fn base_gem_name<'a>(
+    name: &'a str,
+    gem_id: Option<&str>,
+    variant: GemVariant,
+) -> &'a str {
+    let data = gem_id
+        .and_then(|id| base_gem_id(id, variant))
+        .and_then(|id| poe_data::gems::by_id(&id));
+    if let Some(data) = data {
+        return data.name.strip_suffix(" Support").unwrap_or(data.name);
+    }
+
+    match variant {
+        GemVariant::Normal | GemVariant::Transfigured => name,
+        GemVariant::Vaal => name.strip_prefix("Vaal ").unwrap_or(name),
+        GemVariant::Awakened => name.strip_prefix("Awakened ").unwrap_or(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gem_variants() {
+        let variant = |gem_id: &str, skill_id: &str, name: &str| {
+            GemVariant::from_ids(Some(gem_id), Some(skill_id), name)
+        };
+
+        assert_eq!(
+            GemVariant::Awakened,
+            variant(
+                "Metadata/Items/Gems/SupportGemAddedFireDamagePlus",
+                "SupportAddedFireDamagePlus",
+                "Awakened Added Fire Damage"
+            )
+        );
+        assert_eq!(
+            GemVariant::Vaal,
+            variant(
+                "Metadata/Items/Gems/SkillGemVaalColdResistAura",
+                "ColdResistAuraVaal",
+                "Vaal Impurity of Ice"
+            )
+        );
+        assert_eq!(
+            GemVariant::Transfigured,
+            variant(
+                "Metadata/Items/Gems/SkillGemFireball",
+                "FireballAltX",
+                "Fireball of Meteors"
+            )
+        );
+        assert_eq!(
+            GemVariant::Normal,
+            variant("Metadata/Items/Gems/SkillGemArc", "Arc", "Arc")
+        );
+        assert_eq!(
+            GemVariant::Vaal,
+            GemVariant::from_ids(None, None, "Vaal Arc")
+        );
+    }
+
+    #[test]
+    fn base_gem_names() {
+        assert_eq!(
+            "Added Fire Damage",
+            base_gem_name(
+                "Awakened Added Fire Damage",
+                Some("Metadata/Items/Gems/SupportGemAddedFireDamagePlus"),
+                GemVariant::Awakened
+            )
+        );
+        // Not the same name as the vaal skill without the prefix.
+        assert_eq!(
+            "Purity of Ice",
+            base_gem_name(
+                "Vaal Impurity of Ice",
+                Some("Metadata/Items/Gems/SkillGemVaalColdResistAura"),
+                GemVariant::Vaal
+            )
+        );
+        assert_eq!(
+            "Fireball",
+            base_gem_name(
+                "Fireball of Meteors",
+                Some("Metadata/Items/Gems/SkillGemFireballAltX"),
+                GemVariant::Transfigured
+            )
+        );
+        // No gem data for the base gem.
+        assert_eq!(
+            "Ancestral Call",
+            base_gem_name(
+                "Awakened Ancestral Call",
+                Some("Metadata/Items/Gems/SupportGemAncestralCallPlus"),
+                GemVariant::Awakened
+            )
+        );
+        assert_eq!(
+            AlternateQuality::from_quality_id("Alternate2"),
+            Some(AlternateQuality::Divergent)
+        );
+        assert_eq!(AlternateQuality::from_quality_id("Default"), None);
+    }
+}
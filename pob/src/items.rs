@@ -1,5 +1,7 @@
 use std::iter::FusedIterator;
 
+use crate::ModStat;
+
 #[derive(Debug, thiserror::Error)]
 #[error("cannot parse item {0}")]
 pub struct InvalidItem(&'static str);
@@ -63,6 +65,168 @@ impl Influence {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocketColor {
+    Red,
+    Green,
+    Blue,
+    White,
+    Abyss,
+    /// Delve resonator sockets.
+    Delve,
+}
+
+impl SocketColor {
+    fn parse(value: &str) -> Option<Self> {
+        let color = match value {
+            "R" => Self::Red,
+            "G" => Self::Green,
+            "B" => Self::Blue,
+            "W" => Self::White,
+            "A" => Self::Abyss,
+            "DV" => Self::Delve,
+            _ => return None,
+        };
+
+        Some(color)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Red => "R",
+            Self::Green => "G",
+            Self::Blue => "B",
+            Self::White => "W",
+            Self::Abyss => "A",
+            Self::Delve => "DV",
+        }
+    }
+}
+
+/// Sockets of an item, e.g. `B-B-R G`.
+///
+/// Groups are separated by whitespace, sockets within a group are linked with `-`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sockets<'a>(&'a str);
+
+impl<'a> Sockets<'a> {
+    pub fn groups(&self) -> impl Iterator<Item = SocketGroup<'a>> {
+        self.0.split_whitespace().map(SocketGroup)
+    }
+
+    /// Total amount of sockets.
+    pub fn len(&self) -> usize {
+        self.groups().map(|group| group.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Size of the biggest linked group.
+    pub fn max_links(&self) -> usize {
+        self.groups().map(|group| group.len()).max().unwrap_or(0)
+    }
+}
+
+/// A group of linked sockets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SocketGroup<'a>(&'a str);
+
+impl<'a> SocketGroup<'a> {
+    pub fn colors(&self) -> impl Iterator<Item = SocketColor> + 'a {
+        self.0.split('-').filter_map(SocketColor::parse)
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JewelRadius {
+    Small,
+    Medium,
+    Large,
+    VeryLarge,
+    Massive,
+    Variable,
+}
+
+impl JewelRadius {
+    fn parse(value: &str) -> Option<Self> {
+        let radius = match value {
+            "Small" => Self::Small,
+            "Medium" => Self::Medium,
+            "Large" => Self::Large,
+            "Very Large" => Self::VeryLarge,
+            "Massive" => Self::Massive,
+            "Variable" => Self::Variable,
+            _ => return None,
+        };
+
+        Some(radius)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+            Self::VeryLarge => "Very Large",
+            Self::Massive => "Massive",
+            Self::Variable => "Variable",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Requirements {
+    pub level: u8,
+    pub strength: u16,
+    pub dexterity: u16,
+    pub intelligence: u16,
+}
+
+impl Requirements {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Parses requirement lines in the format of `Requires Level 68, 212 Dex`.
+    fn parse_requires(&mut self, line: &str) {
+        for requirement in line.split(',') {
+            let mut words = requirement.split_whitespace();
+            let (Some(a), Some(b)) = (words.next(), words.next()) else {
+                continue;
+            };
+            // Both `Level 68` and `212 Dex` exist.
+            let (name, value) = match a.parse::<u16>() {
+                Ok(value) => (b, value),
+                Err(_) => match b.parse::<u16>() {
+                    Ok(value) => (a, value),
+                    Err(_) => continue,
+                },
+            };
+            self.set(name, value);
+        }
+    }
+
+    fn set(&mut self, name: &str, value: u16) {
+        match name {
+            "Level" => self.level = value.min(u8::MAX as u16) as u8,
+            "Str" | "Strength" => self.strength = value,
+            "Dex" | "Dexterity" => self.dexterity = value,
+            "Int" | "Intelligence" => self.intelligence = value,
+            _ => (),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Item<'a> {
     pub rarity: Rarity,
@@ -76,6 +240,11 @@ pub struct Item<'a> {
     pub armour: u16,
     pub evasion: u16,
     pub energy_shield: u16,
+    pub ward: u16,
+
+    pub radius: Option<JewelRadius>,
+    pub sockets: Sockets<'a>,
+    pub requirements: Requirements,
 
     pub influence1: Option<Influence>,
     pub influence2: Option<Influence>,
@@ -87,6 +256,7 @@ pub struct Item<'a> {
     selected_variant: &'a str,
     implicits: &'a str,
     explicits: &'a str,
+    affixes: [Option<Affix<'a>>; 6],
 }
 
 impl<'a> Item<'a> {
@@ -124,12 +294,18 @@ impl<'a> Item<'a> {
         let mut armour = 0;
         let mut evasion = 0;
         let mut energy_shield = 0;
+        let mut ward = 0;
+
+        let mut radius = None;
+        let mut sockets = Sockets::default();
+        let mut requirements = Requirements::default();
 
         let mut influence1 = None;
         let mut influence2 = None;
 
         let mut selected_variant = "";
         let mut implicits = "";
+        let mut affixes = [None; 6];
 
         loop {
             let Some(line) = lines.peek() else {
@@ -140,25 +316,37 @@ impl<'a> Item<'a> {
                 let _ = lines.next();
 
                 macro_rules! p {
-                    ($name:ident) => {
-                        $name = arg.parse().unwrap_or($name)
+                    ($name:expr) => {
+                        $name = property_value(arg).parse().unwrap_or($name)
                     };
                 }
 
                 match cmd {
                     "Item Level" => p!(item_level),
-                    "LevelReq" => p!(level_requirement),
+                    "LevelReq" | "Level" => p!(level_requirement),
+                    "Str" | "Strength" => p!(requirements.strength),
+                    "Dex" | "Dexterity" => p!(requirements.dexterity),
+                    "Int" | "Intelligence" => p!(requirements.intelligence),
                     "Quality" => p!(quality),
                     "Catalyst" => alt_quality = Some(catalyst_to_alt_quality(arg)),
                     "CatalystQuality" => p!(quality),
                     "Armour" => p!(armour),
-                    "Evasion" => p!(evasion),
+                    "Evasion" | "Evasion Rating" => p!(evasion),
                     "Energy Shield" => p!(energy_shield),
+                    "Ward" => p!(ward),
+                    "Radius" => radius = JewelRadius::parse(arg),
+                    "Sockets" => sockets = Sockets(arg),
                     "Implicits" => {
                         let num = arg.parse().unwrap_or(0);
                         implicits = unsafe { get_n_lines(item, &mut lines, num) };
                     }
                     "Selected Variant" => selected_variant = arg,
+                    "Prefix" | "Suffix" => {
+                        let affix = Affix::parse(cmd, arg);
+                        if let Some(slot) = affixes.iter_mut().find(|slot| slot.is_none()) {
+                            *slot = affix;
+                        }
+                    }
                     _ => {
                         if let Some((a, q)) = parse_alt_quality(cmd, arg) {
                             alt_quality = Some(a);
@@ -174,6 +362,9 @@ impl<'a> Item<'a> {
                 } else if influence2.is_none() {
                     influence2 = Some(influence);
                 }
+            } else if let Some(requires) = line.strip_prefix("Requires ") {
+                let _ = lines.next();
+                requirements.parse_requires(requires);
             } else if line == &base {
                 // Skip random base names which are not mods or commands,
                 // bugged pob?
@@ -222,6 +413,13 @@ impl<'a> Item<'a> {
             influence2 = influence1;
         }
 
+        // `LevelReq` and `Requires` can both be present, prefer whichever has a value.
+        if requirements.level == 0 {
+            requirements.level = level_requirement;
+        } else if level_requirement == 0 {
+            level_requirement = requirements.level;
+        }
+
         Ok(Item {
             rarity,
             name,
@@ -233,6 +431,10 @@ impl<'a> Item<'a> {
             armour,
             evasion,
             energy_shield,
+            ward,
+            radius,
+            sockets,
+            requirements,
             influence1,
             influence2,
             corrupted,
@@ -241,6 +443,7 @@ impl<'a> Item<'a> {
             selected_variant,
             implicits,
             explicits,
+            affixes,
         })
     }
 
@@ -253,46 +456,134 @@ impl<'a> Item<'a> {
     }
 
     pub fn enchants(&self) -> impl Iterator<Item = Mod<'a>> {
+        let local = self.has_local_defences();
         ModLines::new(self.implicits)
-            .map(Mod::parse)
+            .map(move |line| Mod::parse(line, local))
             .filter(|m| m.has_variant(self.selected_variant))
             .take_while(|m| m.crafted)
     }
 
     pub fn implicits(&self) -> impl Iterator<Item = Mod<'a>> {
+        let local = self.has_local_defences();
         ModLines::new(self.implicits)
-            .map(Mod::parse)
+            .map(move |line| Mod::parse(line, local))
             .filter(|m| m.has_variant(self.selected_variant))
             .skip_while(|m| m.crafted)
     }
 
     pub fn explicits(&self) -> impl Iterator<Item = Mod<'a>> {
+        let local = self.has_local_defences();
         ModLines::new(self.explicits)
-            .map(Mod::parse)
+            .map(move |line| Mod::parse(line, local))
             .filter(|m| m.has_variant(self.selected_variant))
     }
 
+    /// Prefixes and suffixes recorded by the Path of Building item crafter.
+    pub fn affixes(&self) -> impl Iterator<Item = Affix<'a>> + '_ {
+        self.affixes.iter().flatten().copied()
+    }
+
+    /// All enchants, implicits and explicits.
+    pub fn mods(&self) -> impl Iterator<Item = Mod<'a>> {
+        let selected_variant = self.selected_variant;
+        let local = self.has_local_defences();
+        ModLines::new(self.implicits)
+            .chain(ModLines::new(self.explicits))
+            .map(move |line| Mod::parse(line, local))
+            .filter(move |m| m.has_variant(selected_variant))
+    }
+
+    /// Sum of a stat over all mods of the item, e.g. `base_maximum_life` for flat life.
+    pub fn stat_total(&self, id: &str) -> f64 {
+        self.mods()
+            .filter_map(|m| m.stats())
+            .flatten()
+            .filter(|stat| stat.id == id)
+            .map(|stat| stat.value)
+            .sum()
+    }
+
+    /// Whether defence mods of the item are local, which is the case for armour pieces.
+    ///
+    /// Weapons are not detected, their local mods translate to the global stats.
+    fn has_local_defences(&self) -> bool {
+        self.armour > 0 || self.evasion > 0 || self.energy_shield > 0 || self.ward > 0
+    }
+
     pub fn is_cluster_jewel(&self) -> bool {
         self.base.contains("Cluster Jewel")
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+/// A prefix or suffix of an item crafted in Path of Building, e.g. `Prefix: {range:1}IncreasedLife10`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affix<'a> {
+    pub kind: AffixKind,
+    /// Id of the modifier, e.g. `IncreasedLife10`.
+    pub id: &'a str,
+    /// Position of the values within their range.
+    pub roll: Option<f32>,
+}
+
+impl<'a> Affix<'a> {
+    fn parse(kind: &str, value: &'a str) -> Option<Self> {
+        let kind = match kind {
+            "Prefix" => AffixKind::Prefix,
+            "Suffix" => AffixKind::Suffix,
+            _ => return None,
+        };
+
+        let (roll, id) = match value.strip_prefix("{range:") {
+            Some(value) => {
+                let (roll, id) = value.split_once('}')?;
+                (roll.parse().ok(), id)
+            }
+            None => (None, value),
+        };
+
+        if id.is_empty() || id == "None" {
+            return None;
+        }
+
+        Some(Self { kind, id, roll })
+    }
+
+    /// Tier of the modifier within its group, the number at the end of the id,
+    /// e.g. `10` for `IncreasedLife10`. Higher tiers require a higher item level.
+    pub fn tier(&self) -> Option<u8> {
+        let id = self.id.trim_end_matches('_');
+        let digits = id.bytes().rev().take_while(u8::is_ascii_digit).count();
+        id[id.len() - digits..].parse().ok()
+    }
+}
+
 #[derive(Debug)]
 pub struct Mod<'a> {
     pub fractured: bool,
     pub crafted: bool,
     pub line: &'a str,
     pub tag: Option<&'a str>,
+    /// Position of the values within their range, e.g. `+(50-70) to maximum Life`.
+    pub roll: Option<f32>,
 
     variant: Option<&'a str>,
+    /// Whether local lines apply to the item itself, see [`crate::mods`].
+    local: bool,
 }
 
 impl<'a> Mod<'a> {
-    fn parse(mut mod_line: &'a str) -> Self {
+    fn parse(mut mod_line: &'a str, local: bool) -> Self {
         let mut fractured = false;
         let mut crafted = false;
         let mut variant = None;
         let mut tag = None;
+        let mut roll = None;
 
         while let Some((attr, other)) = mod_line.trim_start_matches('{').split_once('}') {
             mod_line = other;
@@ -302,7 +593,8 @@ impl<'a> Mod<'a> {
                 "variant" => variant = Some(value),
                 "fractured" => fractured = true,
                 "crafted" => crafted = true,
-                "tags" | "custom" | "range" => (),
+                "range" => roll = value.parse().ok(),
+                "tags" | "custom" => (),
                 t => tag = Some(t),
             }
         }
@@ -312,10 +604,17 @@ impl<'a> Mod<'a> {
             crafted,
             line: mod_line,
             tag,
+            roll,
             variant,
+            local,
         }
     }
 
+    /// The stats of the mod, `None` if the mod is not in the stat description table.
+    pub fn stats(&self) -> Option<Vec<ModStat>> {
+        crate::mods::translate(self.line, self.roll, self.local)
+    }
+
     fn has_variant(&self, target: &str) -> bool {
         if target.is_empty() {
             return true;
@@ -415,6 +714,15 @@ unsafe fn extract_slice_between<'a>(s: &'a str, start: &'a str, end: Option<&'a
     }
 }
 
+/// Extracts the numeric part of a property value.
+///
+/// Strips decorations added by the game, e.g. `+20% (augmented)` -> `20`
+/// or `1.3 metres` -> `1.3`.
+fn property_value(value: &str) -> &str {
+    let value = value.split_whitespace().next().unwrap_or(value);
+    value.trim_start_matches('+').trim_end_matches('%')
+}
+
 /// Parses an alt quality string into the type of quality and it's value.
 fn parse_alt_quality<'a>(cmd: &'a str, arg: &'a str) -> Option<(&'a str, u8)> {
     let s = cmd.strip_prefix("Quality (")?.strip_suffix(')')?;
@@ -940,6 +1248,164 @@ Implicits: 0
         assert_eq!(chaos_res.tag, Some("crucible"));
     }
 
+    #[test]
+    fn sockets_and_requirements() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Gloom Vise
+Dragonscale Gauntlets
+Armour: 209
+Evasion: 208
+Item Level: 85
+Quality: 20
+Sockets: B-B-R G A W-DV
+LevelReq: 67
+Implicits: 0
++85 to maximum Life"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.sockets.len(), 7);
+        assert_eq!(item.sockets.max_links(), 3);
+        let groups = item
+            .sockets
+            .groups()
+            .map(|g| g.colors().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                vec![SocketColor::Blue, SocketColor::Blue, SocketColor::Red],
+                vec![SocketColor::Green],
+                vec![SocketColor::Abyss],
+                vec![SocketColor::White, SocketColor::Delve],
+            ]
+        );
+        assert_eq!(item.requirements.level, 67);
+        assert_eq!(item.explicits().count(), 1);
+    }
+
+    #[test]
+    fn requires_line() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Doom Bane
+Thicket Bow
+Requires Level 68, 212 Dex
+Implicits: 0
+Adds 20 to 40 Physical Damage"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            item.requirements,
+            Requirements {
+                level: 68,
+                dexterity: 212,
+                ..Default::default()
+            }
+        );
+        assert_eq!(item.level_requirement, 68);
+        assert_eq!(item.explicits().count(), 1);
+    }
+
+    #[test]
+    fn jewel_radius() {
+        let item = Item::parse(
+            r#"Rarity: UNIQUE
+Thread of Hope
+Crimson Jewel
+Radius: Variable
+Implicits: 0
+Only affects Passives in Massive Ring"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.radius, Some(JewelRadius::Variable));
+        assert_eq!(item.explicits().count(), 1);
+    }
+
+    #[test]
+    fn mod_stats_and_affixes() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Evasion/Energy Shield Shield
+Supreme Spiked Shield
+Evasion: 520
+Energy Shield: 107
+Crafted: true
+Prefix: {range:1}LocalIncreasedEvasionAndEnergyShield4
+Prefix: None
+Prefix: {range:1}IncreasedLife10
+Suffix: {range:1}LightningResist4
+Suffix: ChanceToSuppressSpellsHigh5___
+Suffix: None
+Quality: 20
+Implicits: 1
+5% chance to Suppress Spell Damage
++34% chance to Suppress Spell Damage
+67% increased Evasion and Energy Shield
++109 to maximum Life
+{range:0.5}+(20-30) to maximum Life
++29% to Lightning Resistance"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.stat_total("base_maximum_life"), 134.0);
+        assert_eq!(item.stat_total("base_spell_suppression_chance_%"), 39.0);
+        assert_eq!(item.stat_total("base_cold_damage_resistance_%"), 0.0);
+
+        let life = item.explicits().nth(3).unwrap();
+        assert_eq!(life.roll, Some(0.5));
+        assert_eq!(
+            life.stats().unwrap()[0].range,
+            Some(crate::RollRange {
+                min: 20.0,
+                max: 30.0
+            })
+        );
+
+        let affixes = item.affixes().collect::<Vec<_>>();
+        assert_eq!(affixes.len(), 4);
+        assert_eq!(affixes[1].kind, AffixKind::Prefix);
+        assert_eq!(affixes[1].id, "IncreasedLife10");
+        assert_eq!(affixes[1].roll, Some(1.0));
+        assert_eq!(affixes[1].tier(), Some(10));
+        assert_eq!(affixes[3].kind, AffixKind::Suffix);
+        assert_eq!(affixes[3].roll, None);
+        assert_eq!(affixes[3].tier(), Some(5));
+    }
+
+    #[test]
+    fn local_mod_stats() {
+        let armour = Item::parse(
+            r#"Rarity: RARE
+Doom Shell
+Vaal Regalia
+Energy Shield: 250
+Implicits: 0
++50 to maximum Energy Shield
++20 to Evasion Rating"#,
+        )
+        .unwrap();
+        assert_eq!(armour.stat_total("local_energy_shield"), 50.0);
+        assert_eq!(armour.stat_total("local_base_evasion_rating"), 20.0);
+        assert_eq!(armour.stat_total("base_maximum_energy_shield"), 0.0);
+
+        let ring = Item::parse(
+            r#"Rarity: RARE
+Doom Band
+Moonstone Ring
+Implicits: 1
++50 to maximum Energy Shield
++20 to Evasion Rating"#,
+        )
+        .unwrap();
+        assert_eq!(ring.stat_total("base_maximum_energy_shield"), 50.0);
+        assert_eq!(ring.stat_total("base_evasion_rating"), 20.0);
+        assert_eq!(ring.stat_total("local_energy_shield"), 0.0);
+    }
+
     #[test]
     fn mod_lines() {
         let lines = ModLines::new("foo\nbar\nfirst you've\nsecond\nbaz").collect::<Vec<_>>();
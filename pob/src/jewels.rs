@@ -0,0 +1,431 @@
+//! Jewels which change the passive tree, cluster jewels and timeless jewels.
+use crate::{Item, Keystone};
+
+/// Bit set in the ids of all nodes added by cluster jewels,
+/// which prevents conflicts with the ids of the passive tree.
+const CLUSTER_NODE_BIT: u32 = 0x10000;
+
+/// A jewel socketed into the passive tree.
+#[derive(Debug)]
+pub struct SocketedJewel<'a> {
+    /// Id of the jewel socket.
+    pub node_id: u32,
+    pub item: Item<'a>,
+    pub kind: JewelKind<'a>,
+}
+
+#[derive(Debug)]
+pub enum JewelKind<'a> {
+    Cluster(ClusterJewel<'a>),
+    Timeless(TimelessJewel<'a>),
+    Other,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClusterJewelSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl ClusterJewelSize {
+    pub const ALL: [ClusterJewelSize; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+        }
+    }
+
+    fn from_base(base: &str) -> Option<Self> {
+        match base {
+            "Small Cluster Jewel" => Some(Self::Small),
+            "Medium Cluster Jewel" => Some(Self::Medium),
+            "Large Cluster Jewel" => Some(Self::Large),
+            _ => None,
+        }
+    }
+
+    fn index(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// A cluster jewel, which adds a small sub tree to the passive tree.
+#[derive(Debug)]
+pub struct ClusterJewel<'a> {
+    pub size: ClusterJewelSize,
+    /// Amount of passives added, including notables and jewel sockets.
+    pub passives: u8,
+    pub jewel_sockets: u8,
+    /// Stats granted by each of the added small passives, e.g. `12% increased Chaos Damage`.
+    pub small_passive_stats: Vec<&'a str>,
+    pub notables: Vec<ClusterNotable<'a>>,
+    /// Allocated nodes of the added sub tree, only known for socketed jewels.
+    pub allocated: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub struct ClusterNotable<'a> {
+    pub name: &'a str,
+    /// The notable in the tree data, only known for socketed jewels.
+    pub node: Option<(u32, &'static poe_tree::Node)>,
+}
+
+impl<'a> ClusterJewel<'a> {
+    pub fn from_item(item: &Item<'a>) -> Option<Self> {
+        let size = ClusterJewelSize::from_base(item.base)?;
+
+        let mut jewel = Self {
+            size,
+            passives: 0,
+            jewel_sockets: 0,
+            small_passive_stats: Vec::new(),
+            notables: Vec::new(),
+            allocated: Vec::new(),
+        };
+
+        for m in item.mods() {
+            let line = m.line;
+            if let Some(passives) = strip_affixes(line, "Adds ", " Passive Skills") {
+                jewel.passives = passives.parse().unwrap_or_default();
+            } else if let Some(stat) = line
+                .strip_prefix("Added Small Passive Skills grant: ")
+                .or_else(|| line.strip_prefix("Added Small Passive Skills also grant: "))
+            {
+                jewel.small_passive_stats.push(stat);
+            } else if line == "1 Added Passive Skill is a Jewel Socket" {
+                jewel.jewel_sockets = 1;
+            } else if let Some(sockets) =
+                strip_affixes(line, "", " Added Passive Skills are Jewel Sockets")
+            {
+                jewel.jewel_sockets = sockets.parse().unwrap_or_default();
+            } else if let Some(name) = line.strip_prefix("1 Added Passive Skill is ") {
+                jewel.notables.push(ClusterNotable { name, node: None });
+            }
+        }
+
+        Some(jewel)
+    }
+}
+
+/// Position of a node added by a cluster jewel, decoded from its id.
+///
+/// Path of Building assigns ids to the added nodes as follows:
+///
+/// * bits 0-3: position of the node in the sub tree
+/// * bits 4-5: size of the cluster jewel
+/// * bits 6-8: index of the large socket
+/// * bits 9-10: index of the medium socket
+/// * bit 16: always set
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ClusterNode {
+    pub size: ClusterJewelSize,
+    pub large_index: u8,
+    pub medium_index: u8,
+    pub index: u8,
+}
+
+impl ClusterNode {
+    pub fn from_id(id: u32) -> Option<Self> {
+        if id & CLUSTER_NODE_BIT == 0 || id > 0x1ffff {
+            return None;
+        }
+
+        let size = match (id >> 4) & 0b11 {
+            0 => ClusterJewelSize::Small,
+            1 => ClusterJewelSize::Medium,
+            2 => ClusterJewelSize::Large,
+            _ => return None,
+        };
+
+        Some(Self {
+            size,
+            large_index: ((id >> 6) & 0b111) as u8,
+            medium_index: ((id >> 9) & 0b11) as u8,
+            index: (id & 0b1111) as u8,
+        })
+    }
+}
+
+/// Prefix of the ids of all nodes added by a cluster jewel of size `size` in the socket `socket`.
+///
+/// Returns `None` if the socket does not fit cluster jewels.
+pub(crate) fn cluster_id_prefix(
+    version: poe_tree::Version,
+    socket: u32,
+    size: ClusterJewelSize,
+) -> Option<u32> {
+    let mut prefix = CLUSTER_NODE_BIT | size.index() << 4;
+
+    let mut current = poe_tree::get_node(version, socket)?
+        .expansion_jewel
+        .as_ref()?;
+    loop {
+        prefix |= match current.size {
+            2 => u32::from(current.index) << 6,
+            1 => u32::from(current.index) << 9,
+            _ => 0,
+        };
+
+        let Some(parent) = current.parent else {
+            break;
+        };
+        match poe_tree::get_node(version, parent).and_then(|p| p.expansion_jewel.as_ref()) {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+
+    Some(prefix)
+}
+
+/// Nodes in `nodes` which were added by the cluster jewel of size `size` in the socket `socket`.
+pub(crate) fn cluster_nodes(
+    version: poe_tree::Version,
+    socket: u32,
+    size: ClusterJewelSize,
+    nodes: &[u32],
+) -> Vec<u32> {
+    let Some(prefix) = cluster_id_prefix(version, socket, size) else {
+        return Vec::new();
+    };
+
+    nodes
+        .iter()
+        .copied()
+        .filter(|&id| id & !0b1111 == prefix)
+        .collect()
+}
+
+/// A cluster notable in the tree data, they are not placed on the tree.
+pub(crate) fn cluster_notable(
+    version: poe_tree::Version,
+    name: &str,
+) -> Option<(u32, &'static poe_tree::Node)> {
+    poe_tree::nodes(version)
+        .find(|(_, node)| node.position.is_none() && node.kind.is_notable() && node.name == name)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimelessJewelKind {
+    GloriousVanity,
+    LethalPride,
+    BrutalRestraint,
+    MilitantFaith,
+    ElegantHubris,
+    HeroicTragedy,
+}
+
+impl TimelessJewelKind {
+    pub const ALL: [TimelessJewelKind; 6] = [
+        Self::GloriousVanity,
+        Self::LethalPride,
+        Self::BrutalRestraint,
+        Self::MilitantFaith,
+        Self::ElegantHubris,
+        Self::HeroicTragedy,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GloriousVanity => "Glorious Vanity",
+            Self::LethalPride => "Lethal Pride",
+            Self::BrutalRestraint => "Brutal Restraint",
+            Self::MilitantFaith => "Militant Faith",
+            Self::ElegantHubris => "Elegant Hubris",
+            Self::HeroicTragedy => "Heroic Tragedy",
+        }
+    }
+
+    /// The faction which conquers the passives in radius, e.g. `Vaal`.
+    pub fn faction(&self) -> &'static str {
+        match self {
+            Self::GloriousVanity => "Vaal",
+            Self::LethalPride => "Karui",
+            Self::BrutalRestraint => "Maraketh",
+            Self::MilitantFaith => "Templar",
+            Self::ElegantHubris => "Eternal Empire",
+            Self::HeroicTragedy => "Kalguur",
+        }
+    }
+
+    /// Text around the seed and conqueror of the jewel's mod, e.g.
+    /// `Bathed in the blood of 2000 sacrificed in the name of Xibaqua`.
+    fn seed_text(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::GloriousVanity => ("Bathed in the blood of ", " sacrificed in the name of "),
+            Self::LethalPride => ("Commanded leadership over ", " warriors under "),
+            Self::BrutalRestraint => ("Denoted service of ", " dekhara in the akhara of "),
+            Self::MilitantFaith => (
+                "Carved to glorify ",
+                " new faithful converted by High Templar ",
+            ),
+            Self::ElegantHubris => ("Commissioned ", " coins to commemorate "),
+            Self::HeroicTragedy => ("Remembrancing ", " songworthy deeds by the line of "),
+        }
+    }
+}
+
+/// A timeless jewel, which conquers all passives in its radius.
+#[derive(Debug)]
+pub struct TimelessJewel<'a> {
+    pub kind: TimelessJewelKind,
+    pub seed: u32,
+    /// Name of the conqueror, e.g. `Xibaqua`.
+    pub conqueror: &'a str,
+    /// Allocated nodes in the radius of the jewel, only known for socketed jewels.
+    pub conquered: Vec<(u32, &'static poe_tree::Node)>,
+}
+
+impl<'a> TimelessJewel<'a> {
+    pub fn from_item(item: &Item<'a>) -> Option<Self> {
+        if item.base != "Timeless Jewel" {
+            return None;
+        }
+
+        item.mods().find_map(|m| {
+            TimelessJewelKind::ALL.into_iter().find_map(|kind| {
+                let (prefix, infix) = kind.seed_text();
+                let (seed, conqueror) = m.line.strip_prefix(prefix)?.split_once(infix)?;
+                Some(Self {
+                    kind,
+                    seed: seed.parse().ok()?,
+                    conqueror,
+                    conquered: Vec::new(),
+                })
+            })
+        })
+    }
+
+    /// The keystone of the conqueror, it replaces keystones conquered by the jewel.
+    ///
+    /// `None` if the keystone of the conqueror is not known, the conquered keystones are kept then.
+    pub fn keystone(&self) -> Option<Keystone> {
+        use TimelessJewelKind::*;
+
+        let keystone = match (self.kind, self.conqueror) {
+            (GloriousVanity, "Xibaqua") => Keystone::DivineFlesh,
+            (GloriousVanity, "Zerphi") => Keystone::ImmortalAmbition,
+            (GloriousVanity, "Doryani") => Keystone::CorruptedSoul,
+            (LethalPride, "Kaom") => Keystone::StrengthOfBlood,
+            (LethalPride, "Rakiata") => Keystone::TemperedByWar,
+            (LethalPride, "Kiloava") => Keystone::Chainbreaker,
+            (BrutalRestraint, "Asenath") => Keystone::DanceWithDeath,
+            (BrutalRestraint, "Nasima") => Keystone::SecondSight,
+            (BrutalRestraint, "Balbala") => Keystone::TheTraitor,
+            (MilitantFaith, "Avarius") => Keystone::PowerOfPurpose,
+            (MilitantFaith, "Dominus") => Keystone::InnerConviction,
+            (MilitantFaith, "Maxarius") => Keystone::Transcendence,
+            (ElegantHubris, "Cadiro") => Keystone::SupremeDecadence,
+            (ElegantHubris, "Victario") => Keystone::SupremeGrandstanding,
+            (ElegantHubris, "Caspiro") => Keystone::SupremeOstentation,
+            _ => return None,
+        };
+
+        Some(keystone)
+    }
+}
+
+/// Allocated nodes in `nodes` conquered by a timeless jewel in the socket `socket`.
+pub(crate) fn conquered_nodes(
+    version: poe_tree::Version,
+    socket: u32,
+    nodes: &[u32],
+) -> Vec<(u32, &'static poe_tree::Node)> {
+    let mut conquered = poe_tree::nodes_in_radius(version, socket, version.large_jewel_radius())
+        .filter(|(id, node)| {
+            nodes.contains(id) && !node.kind.is_mastery() && !node.name.ends_with("Jewel Socket")
+        })
+        .collect::<Vec<_>>();
+    conquered.sort_unstable_by_key(|(id, _)| *id);
+    conquered
+}
+
+fn strip_affixes<'a>(s: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    s.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cluster_jewel() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Gloom Spark
+Medium Cluster Jewel
+Item Level: 84
+LevelReq: 54
+Implicits: 3
+{crafted}Adds 5 Passive Skills
+{crafted}1 Added Passive Skill is a Jewel Socket
+{crafted}Added Small Passive Skills grant: 12% increased Fire Damage
+1 Added Passive Skill is Burning Bright
+Added Small Passive Skills also grant: +5 to Strength"#,
+        )
+        .unwrap();
+
+        let jewel = ClusterJewel::from_item(&item).unwrap();
+        assert_eq!(ClusterJewelSize::Medium, jewel.size);
+        assert_eq!(5, jewel.passives);
+        assert_eq!(1, jewel.jewel_sockets);
+        assert_eq!(
+            vec!["12% increased Fire Damage", "+5 to Strength"],
+            jewel.small_passive_stats
+        );
+        assert_eq!(
+            vec!["Burning Bright"],
+            jewel.notables.iter().map(|n| n.name).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn timeless_jewel() {
+        let item = Item::parse(
+            r#"Rarity: UNIQUE
+Glorious Vanity
+Timeless Jewel
+Limited to: 1 Historic
+Radius: Large
+Implicits: 0
+Bathed in the blood of 2000 sacrificed in the name of Xibaqua
+Passives in radius are Conquered by the Vaal
+Historic"#,
+        )
+        .unwrap();
+
+        let jewel = TimelessJewel::from_item(&item).unwrap();
+        assert_eq!(TimelessJewelKind::GloriousVanity, jewel.kind);
+        assert_eq!(2000, jewel.seed);
+        assert_eq!("Xibaqua", jewel.conqueror);
+        assert_eq!("Vaal", jewel.kind.faction());
+        assert_eq!(Some(Keystone::DivineFlesh), jewel.keystone());
+    }
+
+    #[test]
+    fn cluster_node_ids() {
+        assert_eq!(
+            Some(ClusterNode {
+                size: ClusterJewelSize::Large,
+                large_index: 4,
+                medium_index: 0,
+                index: 6,
+            }),
+            ClusterNode::from_id(0x10126)
+        );
+        assert_eq!(
+            Some(ClusterNode {
+                size: ClusterJewelSize::Medium,
+                large_index: 1,
+                medium_index: 2,
+                index: 3,
+            }),
+            ClusterNode::from_id(0x10000 | 2 << 9 | 1 << 6 | 1 << 4 | 3)
+        );
+        assert_eq!(None, ClusterNode::from_id(32763));
+    }
+}
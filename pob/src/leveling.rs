@@ -0,0 +1,513 @@
+//! Leveling guides from the tree specs and skill sets of a build.
+//!
+//! Builds often contain tree specs and skill sets for different stages of the campaign,
+//! e.g. `Act 1`, `Lvl 40` or `Mapping`. These are ordered by the level they are meant for
+//! and turned into steps with the passives and gems which change between them.
+use shared::Class;
+
+use crate::loadouts::strip_link;
+use crate::{passives, PathOfBuilding, SkillSet, TreeSpec};
+
+/// Passive points rewarded by quests, the last ones are rewarded at the end of the campaign.
+const QUEST_POINTS: usize = 24;
+/// Approximate character level at the end of the campaign.
+const CAMPAIGN_LEVEL: usize = 68;
+
+/// Approximate character level at the end of each act.
+const ACT_LEVELS: [u8; 10] = [12, 23, 33, 40, 45, 50, 55, 60, 64, 68];
+
+/// Approximate character level when running each labyrinth.
+const LAB_LEVELS: [(&str, u8); 6] = [
+    ("normal", 33),
+    ("cruel", 55),
+    ("merc", 68),
+    ("merciless", 68),
+    ("uber", 75),
+    ("eternal", 75),
+];
+
+/// Steps of a leveling guide, ordered by level.
+#[derive(Debug)]
+pub struct LevelingGuide<'a> {
+    pub steps: Vec<Step<'a>>,
+}
+
+/// A stage of the build, a tree spec and/or a skill set.
+#[derive(Debug)]
+pub struct Step<'a> {
+    /// Level the step is meant for, `None` if it could not be inferred.
+    pub level: Option<Level>,
+    pub tree: Option<TreeStep<'a>>,
+    pub skills: Option<SkillStep<'a>>,
+}
+
+impl<'a> Step<'a> {
+    /// Title of the step, the title of the tree spec or the skill set.
+    pub fn title(&self) -> Option<&'a str> {
+        self.tree
+            .as_ref()
+            .and_then(|tree| tree.title)
+            .or_else(|| self.skills.as_ref().and_then(|skills| skills.title))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    pub level: u8,
+    pub source: LevelSource,
+}
+
+/// Where the level of a step was inferred from, ordered from most to least reliable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LevelSource {
+    /// The title, e.g. `Lvl 40` or `Act 3`.
+    Title,
+    /// Amount of allocated passive points.
+    Points,
+    /// Highest level requirement of the gems.
+    Gems,
+}
+
+#[derive(Debug)]
+pub struct TreeStep<'a> {
+    pub title: Option<&'a str>,
+    /// Index into [`crate::PathOfBuilding::tree_specs`].
+    pub tree_spec: usize,
+    /// Allocated passive points, excluding ascendancy points.
+    pub points: usize,
+    /// Nodes allocated compared to the previous step.
+    pub added: Vec<u32>,
+    /// Nodes refunded compared to the previous step.
+    pub removed: Vec<u32>,
+    /// Names of the notables and keystones allocated compared to the previous step.
+    pub notables_added: Vec<&'static str>,
+}
+
+#[derive(Debug)]
+pub struct SkillStep<'a> {
+    pub title: Option<&'a str>,
+    /// Index into [`crate::PathOfBuilding::skill_sets`].
+    pub skill_set: usize,
+    /// Gems used compared to the previous step.
+    pub gems_added: Vec<GemStep<'a>>,
+    /// Names of the gems no longer used compared to the previous step.
+    pub gems_removed: Vec<&'a str>,
+}
+
+#[derive(Debug)]
+pub struct GemStep<'a> {
+    pub name: &'a str,
+    pub gem_id: Option<&'a str>,
+    /// Character level required to use the gem.
+    pub required_level: Option<u8>,
+    /// Where the class of the build can buy the gem.
+    pub vendors: Vec<Vendor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vendor {
+    pub act: u8,
+    pub npc: &'static str,
+    /// Quest which needs to be completed to buy the gem.
+    pub quest: &'static str,
+}
+
+enum Entry<'s, 'a> {
+    Tree(usize, &'s TreeSpec<'a>),
+    Skills(usize, &'s SkillSet<'a>),
+}
+
+impl<'s, 'a> Entry<'s, 'a> {
+    fn title(&self) -> Option<&'a str> {
+        match self {
+            Self::Tree(_, spec) => spec.title,
+            Self::Skills(_, set) => set.title,
+        }
+    }
+}
+
+pub(crate) fn leveling_guide<P>(pob: &P) -> Option<LevelingGuide<'_>>
+where
+    P: PathOfBuilding + ?Sized,
+{
+    let tree_specs = pob.tree_specs();
+    let skill_sets = pob.skill_sets();
+
+    // A guide needs stages, which are only recognizable by their titles.
+    let titled_levels = tree_specs
+        .iter()
+        .map(|spec| spec.title)
+        .chain(skill_sets.iter().map(|set| set.title))
+        .filter(|title| title.and_then(title_level).is_some())
+        .count();
+    if titled_levels < 2 {
+        return None;
+    }
+
+    // A single tree spec or skill set is used for the entire build, not a stage.
+    let tree_specs = if tree_specs.len() >= 2 {
+        &tree_specs[..]
+    } else {
+        &[]
+    };
+    let skill_sets = if skill_sets.len() >= 2 {
+        &skill_sets[..]
+    } else {
+        &[]
+    };
+
+    let entries = tree_specs
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| (tree_level(spec), Entry::Tree(index, spec)))
+        .chain(
+            skill_sets
+                .iter()
+                .enumerate()
+                .map(|(index, set)| (skill_set_level(set), Entry::Skills(index, set))),
+        );
+
+    // Stages without a level are most likely in the right place already,
+    // they stay after the stage before them.
+    let (mut last_tree, mut last_skills) = (0, 0);
+    let mut entries = entries
+        .map(|(level, entry)| {
+            let last = match entry {
+                Entry::Tree(..) => &mut last_tree,
+                Entry::Skills(..) => &mut last_skills,
+            };
+            *last = level.map_or(*last, |level| level.level);
+            (*last, level, entry)
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(sort_level, _, _)| *sort_level);
+
+    let class = pob.class();
+    let mut steps = Vec::<Step>::new();
+    let mut previous_tree = None;
+    let mut previous_skills = None;
+
+    for (_, level, entry) in entries {
+        let title = entry.title().map(strip_link);
+
+        let mergeable = steps.last_mut().filter(|step| {
+            let same_stage = match (step.level, level) {
+                (Some(a), Some(b)) => a.level == b.level,
+                _ => false,
+            } || (title.is_some() && step.title().map(strip_link) == title);
+
+            let has_slot = match entry {
+                Entry::Tree(..) => step.tree.is_none(),
+                Entry::Skills(..) => step.skills.is_none(),
+            };
+
+            same_stage && has_slot
+        });
+
+        let step = match mergeable {
+            Some(step) => step,
+            None => {
+                steps.push(Step {
+                    level: None,
+                    tree: None,
+                    skills: None,
+                });
+                steps.last_mut().unwrap()
+            }
+        };
+
+        // Prefer the more reliable level if both, tree spec and skill set, have one.
+        step.level = match (step.level, level) {
+            (Some(a), Some(b)) if b.source < a.source => Some(b),
+            (a, b) => a.or(b),
+        };
+
+        match entry {
+            Entry::Tree(index, spec) => {
+                step.tree = Some(tree_step(index, spec, previous_tree));
+                previous_tree = Some(spec);
+            }
+            Entry::Skills(index, set) => {
+                step.skills = Some(skill_step(index, set, previous_skills, class));
+                previous_skills = Some(set);
+            }
+        }
+    }
+
+    Some(LevelingGuide { steps })
+}
+
+fn tree_step<'a>(index: usize, spec: &TreeSpec<'a>, previous: Option<&TreeSpec>) -> TreeStep<'a> {
+    let previous = previous.map_or(&[][..], |previous| previous.nodes);
+    let added = spec
+        .nodes
+        .iter()
+        .filter(|node| !previous.contains(node))
+        .copied()
+        .collect::<Vec<_>>();
+    let removed = previous
+        .iter()
+        .filter(|node| !spec.nodes.contains(node))
+        .copied()
+        .collect();
+
+    let version = passives::tree_version(spec);
+    let notables_added = added
+        .iter()
+        .filter_map(|&node| poe_tree::get_node(version, node))
+        .filter(|node| !node.is_ascendancy && (node.kind.is_notable() || node.kind.is_keystone()))
+        .map(|node| node.name)
+        .collect();
+
+    TreeStep {
+        title: spec.title,
+        tree_spec: index,
+        points: passive_points(spec),
+        added,
+        removed,
+        notables_added,
+    }
+}
+
+fn skill_step<'a>(
+    index: usize,
+    set: &SkillSet<'a>,
+    previous: Option<&SkillSet<'a>>,
+    class: Class,
+) -> SkillStep<'a> {
+    let gems = used_gems(set);
+    let previous = previous.map(used_gems).unwrap_or_default();
+
+    let gems_added = gems
+        .iter()
+        .filter(|(name, _)| !previous.iter().any(|(p, _)| p == name))
+        .map(|&(name, gem_id)| {
+            let data = gem_data(name, gem_id);
+            GemStep {
+                name,
+                gem_id,
+                required_level: data.map(|gem| gem.level),
+                vendors: data.map_or_else(Vec::new, |gem| {
+                    gem.vendors(class)
+                        .map(|vendor| Vendor {
+                            act: vendor.act,
+                            npc: vendor.npc,
+                            quest: vendor.quest,
+                        })
+                        .collect()
+                }),
+            }
+        })
+        .collect();
+    let gems_removed = previous
+        .iter()
+        .filter(|(name, _)| !gems.iter().any(|(g, _)| g == name))
+        .map(|(name, _)| *name)
+        .collect();
+
+    SkillStep {
+        title: set.title,
+        skill_set: index,
+        gems_added,
+        gems_removed,
+    }
+}
+
+/// Names and ids of all enabled gems in enabled skills, without duplicates.
+fn used_gems<'a>(set: &SkillSet<'a>) -> Vec<(&'a str, Option<&'a str>)> {
+    let mut gems = Vec::new();
+    for gem in set
+        .skills
+        .iter()
+        .filter(|skill| skill.is_enabled)
+        .flat_map(|skill| &skill.gems)
+        .filter(|gem| gem.is_enabled)
+    {
+        if !gems.iter().any(|(name, _)| *name == gem.name) {
+            gems.push((gem.name, gem.gem_id));
+        }
+    }
+    gems
+}
+
+fn gem_data(name: &str, gem_id: Option<&str>) -> Option<&'static poe_data::gems::Gem> {
+    gem_id
+        .and_then(poe_data::gems::by_id)
+        .or_else(|| poe_data::gems::by_name(name).map(|(_, gem)| gem))
+}
+
+fn tree_level(spec: &TreeSpec) -> Option<Level> {
+    if let Some(level) = spec.title.and_then(title_level) {
+        return Some(Level {
+            level,
+            source: LevelSource::Title,
+        });
+    }
+
+    let points = passive_points(spec);
+    (points > 0).then(|| Level {
+        level: points_level(points),
+        source: LevelSource::Points,
+    })
+}
+
+fn skill_set_level(set: &SkillSet) -> Option<Level> {
+    if let Some(level) = set.title.and_then(title_level) {
+        return Some(Level {
+            level,
+            source: LevelSource::Title,
+        });
+    }
+
+    used_gems(set)
+        .into_iter()
+        .filter_map(|(name, gem_id)| gem_data(name, gem_id))
+        .map(|gem| gem.level)
+        .max()
+        .map(|level| Level {
+            level,
+            source: LevelSource::Gems,
+        })
+}
+
+/// Allocated passive points, class start and ascendancy nodes don't cost passive points.
+fn passive_points(spec: &TreeSpec) -> usize {
+    let version = passives::tree_version(spec);
+    spec.nodes
+        .iter()
+        .filter(|node| !poe_tree::start_nodes(version).contains(node))
+        .filter(|&&node| !poe_tree::get_node(version, node).is_some_and(|node| node.is_ascendancy))
+        .count()
+}
+
+/// Lowest level at which `points` passive points can be allocated,
+/// assuming quest points are rewarded evenly throughout the campaign.
+fn points_level(points: usize) -> u8 {
+    (1..=100)
+        .find(|&level| {
+            let quest_points = QUEST_POINTS * level.min(CAMPAIGN_LEVEL) / CAMPAIGN_LEVEL;
+            level - 1 + quest_points >= points
+        })
+        .unwrap_or(100) as u8
+}
+
+/// Infers a level from a title like `Lvl 40`, `Level 12-24`, `Act 3`, `Cruel Lab`, `70` or `Mapping`.
+///
+/// For ranges the end of the range is used, the set is meant to be complete at that level.
+fn title_level(title: &str) -> Option<u8> {
+    let title = strip_link(title).to_lowercase();
+    let words = title
+        .split(|c: char| c.is_whitespace() || matches!(c, ':' | '(' | ')' | '[' | ']' | '|' | ','))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+
+    for (index, word) in words.iter().enumerate() {
+        let next = words.get(index + 1).copied();
+
+        for prefix in ["level", "lvl", "lv", "l"] {
+            let Some(rest) = word.strip_prefix(prefix) else {
+                continue;
+            };
+            let rest = rest.trim_start_matches(['.', '-']);
+            let number = if rest.is_empty() { next } else { Some(rest) };
+            if let Some(level) = number.and_then(range_end).filter(|l| (1..=100).contains(l)) {
+                return Some(level);
+            }
+        }
+
+        if let Some(rest) = word.strip_prefix("act") {
+            let number = if rest.is_empty() { next } else { Some(rest) };
+            if let Some(act) = number.and_then(range_end).filter(|a| (1..=10).contains(a)) {
+                return Some(ACT_LEVELS[act as usize - 1]);
+            }
+        }
+    }
+
+    if let [word] = words.as_slice() {
+        if let Some(level) = range_end(word).filter(|l| (1..=100).contains(l)) {
+            return Some(level);
+        }
+    }
+
+    let is_lab = words
+        .iter()
+        .any(|word| matches!(*word, "lab" | "labyrinth"));
+    for word in words.iter().filter(|_| is_lab) {
+        if let Some(&(_, level)) = LAB_LEVELS.iter().find(|(lab, _)| lab == word) {
+            return Some(level);
+        }
+    }
+
+    if words
+        .iter()
+        .any(|word| word.replace('-', "").starts_with("endgame"))
+    {
+        return Some(90);
+    }
+    if words.iter().any(|word| word.starts_with("map")) {
+        return Some(CAMPAIGN_LEVEL as u8 + 2);
+    }
+
+    None
+}
+
+/// Parses `40`, `40+` or `12-24`, returns the end of a range.
+fn range_end(s: &str) -> Option<u8> {
+    let s = s.trim_end_matches('+');
+    let end = s.rsplit('-').next()?;
+    end.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_levels() {
+        assert_eq!(Some(40), title_level("Lvl 40"));
+        assert_eq!(Some(40), title_level("lvl40"));
+        assert_eq!(Some(24), title_level("Level 12-24"));
+        assert_eq!(Some(90), title_level("L90+"));
+        assert_eq!(Some(33), title_level("Act 3"));
+        assert_eq!(Some(40), title_level("Act 3-4 {1}"));
+        assert_eq!(Some(70), title_level("70"));
+        assert_eq!(Some(70), title_level("Early Mapping"));
+        assert_eq!(Some(90), title_level("Endgame"));
+        assert_eq!(Some(90), title_level("End-Game Clusters"));
+        assert_eq!(Some(55), title_level("Cruel Lab"));
+        assert_eq!(Some(33), title_level("Normal Lab + Respec"));
+        assert_eq!(None, title_level("Normal Damage"));
+        assert_eq!(None, title_level("Default"));
+        assert_eq!(None, title_level("Lightning Arrow"));
+        assert_eq!(None, title_level("Act 12"));
+    }
+
+    #[test]
+    fn points_levels() {
+        assert_eq!(1, points_level(0));
+        assert_eq!(2, points_level(1));
+        assert_eq!(55, points_level(73));
+        assert_eq!(91, points_level(114));
+        assert_eq!(100, points_level(200));
+    }
+
+    #[test]
+    fn shadow_passive_points() {
+        // Shadow start node, `Damage and Energy Shield` and `Blood Siphon`.
+        let nodes = [44683, 38129, 25058];
+        let spec = TreeSpec {
+            title: None,
+            url: None,
+            version: None,
+            class_id: Some(6),
+            ascendancy_id: None,
+            alternate_ascendancy_id: None,
+            nodes: &nodes,
+            mastery_effects: &[],
+            sockets: Vec::new(),
+            overrides: Vec::new(),
+            active: true,
+        };
+        assert_eq!(2, passive_points(&spec));
+    }
+}
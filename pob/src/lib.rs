@@ -1,23 +1,60 @@
 use std::str::FromStr;
 
+pub mod archetype;
+mod calcs;
+mod character;
 mod config;
+pub mod diff;
 mod error;
+mod gear;
 mod gems;
 mod items;
+mod jewels;
+pub mod leveling;
+pub mod lint;
+mod loadouts;
+mod minion;
+mod mods;
+pub mod notes;
+mod party;
 mod passives;
 mod serde;
+pub mod snapshot;
 mod stats;
+mod tree_url;
 mod utils;
 
-use shared::{Ascendancy, AscendancyOrClass, Bandit, Class, PantheonMajorGod, PantheonMinorGod};
+use ::serde::{Deserialize, Serialize};
+use shared::{
+    AlternateAscendancy, Ascendancy, AscendancyOrClass, Bandit, Class, PantheonMajorGod,
+    PantheonMinorGod,
+};
 
-pub use self::config::{Config, ConfigValue};
-pub use self::error::{Error, Result};
-pub use self::items::{Influence, Item, Mod, Rarity};
+pub use self::calcs::{BuffMode, Calcs};
+pub use self::character::{
+    Character, CharacterItem, CharacterItems, CharacterPassives, ItemProperty, ItemSocket,
+};
+pub use self::config::{Config, ConfigSection, ConfigType, ConfigValue};
+pub use self::error::{Error, ParseWarning, Result};
+pub use self::gear::{GearStat, GearStats, SlotStats};
+pub use self::gems::{AlternateQuality, GemVariant};
+pub use self::items::{
+    Affix, AffixKind, Influence, Item, JewelRadius, Mod, Rarity, Requirements, SocketColor,
+    SocketGroup, Sockets,
+};
+pub use self::jewels::{
+    ClusterJewel, ClusterJewelSize, ClusterNode, ClusterNotable, JewelKind, SocketedJewel,
+    TimelessJewel, TimelessJewelKind,
+};
+pub use self::loadouts::Loadout;
+pub use self::minion::{Minion, MinionStats};
+pub use self::mods::{ModStat, RollRange};
+pub use self::party::Party;
 pub use self::passives::Keystone;
 pub use self::serde::SerdePathOfBuilding;
-pub use self::stats::Stat;
-pub use self::utils::decompress;
+pub use self::stats::{Stat, StatKind, StatValue};
+pub use self::tree_url::{InvalidTreeUrl, TreeUrl};
+pub use self::utils::{compress, decompress, normalize};
 
 pub trait PathOfBuilding {
     fn level(&self) -> u8;
@@ -31,8 +68,21 @@ pub trait PathOfBuilding {
 
     fn stat(&self, stat: Stat) -> Option<&str>;
     fn minion_stat(&self, stat: Stat) -> Option<&str>;
+    /// All known player stats with typed values, stats unknown to [`Stat`] are skipped.
+    fn stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_>;
+    /// All known minion stats with typed values, stats unknown to [`Stat`] are skipped.
+    fn minion_stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_>;
     fn config(&self, config: Config) -> ConfigValue;
+    /// All known configuration options which have a value set, converted to the type of the option.
+    fn configs(&self) -> Box<dyn Iterator<Item = (Config, ConfigValue<'_>)> + '_>;
+    /// Configuration options which are not known, with the name Path of Building uses for them
+    /// and their raw value.
+    fn unknown_configs(&self) -> Box<dyn Iterator<Item = (&str, ConfigValue<'_>)> + '_>;
     fn main_skill_name(&self) -> Option<&str>;
+    /// The minion of the main skill, if Path of Building calculated stats for one.
+    fn minion(&self) -> Option<Minion<'_>>;
+    /// Whether the main skill is supported by the gem `skill`, variants of the gem count as well,
+    /// e.g. `Awakened Added Fire Damage` supports `Added Fire Damage`.
     fn main_skill_supported_by(&self, skill: &str) -> bool;
 
     fn skill_sets(&self) -> Vec<SkillSet>;
@@ -43,6 +93,38 @@ pub trait PathOfBuilding {
     fn tree_specs(&self) -> Vec<TreeSpec>;
     fn has_tree_node(&self, node: u32) -> bool;
     fn has_keystone(&self, keystone: Keystone) -> bool;
+    /// Whether the keystone is allocated in `spec` or granted by the active gear.
+    fn has_keystone_in(&self, spec: &TreeSpec, keystone: Keystone) -> bool;
+    /// Whether the keystone is granted by an item of the active item set.
+    fn has_keystone_on_gear(&self, keystone: Keystone) -> bool;
+
+    /// Buffs of party members configured in the party tab.
+    fn party(&self) -> Party<'_>;
+    /// Selections of the calcs tab.
+    fn calcs(&self) -> Calcs;
+    /// The character the build was last imported from, `None` if it was never imported.
+    fn import(&self) -> Option<Import<'_>>;
+    /// Stat weights of the trade search of Path of Building.
+    fn trade_search_weights(&self) -> Vec<TradeSearchWeight<'_>>;
+}
+
+/// The character a build was imported from.
+///
+/// Path of Building only stores SHA-1 hashes of the account and character name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Import<'a> {
+    /// Realm of the character, e.g. `PC`.
+    pub realm: Option<&'a str>,
+    pub league: Option<&'a str>,
+    pub account_hash: Option<&'a str>,
+    pub character_hash: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeSearchWeight<'a> {
+    /// PoB name of the stat, e.g. `FullDPS`.
+    pub stat: &'a str,
+    pub weight: f32,
 }
 
 #[derive(Debug)]
@@ -62,7 +144,20 @@ pub struct TreeSpec<'a> {
     pub active: bool,
 }
 
-#[derive(Debug)]
+impl<'a> TreeSpec<'a> {
+    /// Decodes the spec's passive tree URL, `None` if there is no valid URL.
+    pub fn tree_url(&self) -> Option<TreeUrl> {
+        TreeUrl::parse(self.url?).ok()
+    }
+
+    /// Whether the passive tree URL allocates the same nodes as the spec,
+    /// `None` if there is no valid URL.
+    pub fn url_matches_nodes(&self) -> Option<bool> {
+        self.tree_url().map(|url| url.matches(self))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Socket {
     pub node_id: u32,
     pub item_id: u16,
@@ -89,6 +184,8 @@ pub struct Skill<'a> {
     pub is_enabled: bool,
     pub label: Option<&'a str>,
     pub slot: Option<&'a str>,
+    /// Name of the item which grants the skill, e.g. `Saqawal's Flock, Silken Hood`.
+    pub granted_by: Option<&'a str>,
     pub gems: Vec<Gem<'a>>,
 }
 
@@ -98,6 +195,11 @@ pub struct Gem<'a> {
     pub skill_id: Option<&'a str>,
     pub gem_id: Option<&'a str>,
     pub quality_id: Option<&'a str>,
+    pub variant: GemVariant,
+    /// Name of the gem this gem is a variant of, e.g. `Added Fire Damage` for
+    /// `Awakened Added Fire Damage`, the gem's own name if it is not a variant.
+    pub base_name: &'a str,
+    pub alternate_quality: Option<AlternateQuality>,
     pub level: u8,
     pub quality: u8,
     pub is_enabled: bool,
@@ -111,6 +213,8 @@ pub struct ItemSet<'a> {
     pub id: u16,
     pub title: Option<&'a str>,
     pub gear: Gear<'a>,
+    /// Whether the weapon swap is the active weapon set.
+    pub use_second_weapon_set: bool,
     pub is_selected: bool,
 }
 
@@ -118,6 +222,8 @@ pub struct ItemSet<'a> {
 pub struct Gear<'a> {
     pub weapon1: Option<&'a str>,
     pub weapon2: Option<&'a str>,
+    pub weapon1_swap: Option<&'a str>,
+    pub weapon2_swap: Option<&'a str>,
     pub helmet: Option<&'a str>,
     pub body_armour: Option<&'a str>,
     pub gloves: Option<&'a str>,
@@ -131,7 +237,56 @@ pub struct Gear<'a> {
     pub flask3: Option<&'a str>,
     pub flask4: Option<&'a str>,
     pub flask5: Option<&'a str>,
+    /// Items in slots which are not one of the named slots above,
+    /// e.g. abyss jewels socketed into items.
     pub sockets: Vec<&'a str>,
+    /// Every slot which has an item, in the order Path of Building lists them.
+    pub slots: Vec<GearSlot<'a>>,
+}
+
+impl<'a> Gear<'a> {
+    /// Items socketed into the item of the slot `slot`, e.g. abyss jewels socketed into the `Belt`.
+    pub fn socketed_in<'b>(&'b self, slot: &'b str) -> impl Iterator<Item = GearSlot<'a>> + 'b {
+        self.slots
+            .iter()
+            .filter(move |s| matches!(s.socketed_in(), Some(parent) if is_same_slot(parent, slot)))
+            .copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GearSlot<'a> {
+    /// Name of the slot as used by Path of Building, e.g. `Weapon 1 Swap` or `Helmet Abyssal Socket 1`.
+    pub name: &'a str,
+    /// Id of the item, see [`PathOfBuilding::item_by_id`].
+    pub item_id: u16,
+    pub item: &'a str,
+}
+
+impl<'a> GearSlot<'a> {
+    /// Name of the slot this item is socketed in, if the slot is a socket of another item.
+    ///
+    /// Path of Building spells the swap weapons inconsistently (`Weapon 1Swap`),
+    /// use [`Gear::socketed_in`] to match them.
+    pub fn socketed_in(&self) -> Option<&'a str> {
+        self.name
+            .split_once(" Abyssal Socket ")
+            .map(|(parent, _)| parent)
+    }
+
+    /// Whether the slot belongs to the weapon swap, including sockets of swap weapons.
+    pub fn is_weapon_swap(&self) -> bool {
+        self.name.starts_with("Weapon 1 Swap")
+            || self.name.starts_with("Weapon 2 Swap")
+            || self.name.starts_with("Weapon 1Swap")
+            || self.name.starts_with("Weapon 2Swap")
+    }
+}
+
+fn is_same_slot(a: &str, b: &str) -> bool {
+    let a = a.chars().filter(|c| *c != ' ');
+    let b = b.chars().filter(|c| *c != ' ');
+    a.eq(b)
 }
 
 pub trait PathOfBuildingExt: PathOfBuilding {
@@ -141,6 +296,15 @@ pub trait PathOfBuildingExt: PathOfBuilding {
             .unwrap_or_else(|| self.class().into())
     }
 
+    /// Alternate ascendancy chosen in the active tree spec, e.g. the Warden.
+    fn alternate_ascendancy(&self) -> Option<AlternateAscendancy> {
+        self.tree_specs()
+            .into_iter()
+            .find(|spec| spec.active)?
+            .alternate_ascendancy_id
+            .and_then(AlternateAscendancy::from_id)
+    }
+
     fn main_skill_supported_by_any<T>(&self, skills: T) -> bool
     where
         T: IntoIterator,
@@ -151,6 +315,133 @@ pub trait PathOfBuildingExt: PathOfBuilding {
             .any(|skill| self.main_skill_supported_by(skill.as_ref()))
     }
 
+    /// Gem data of the active gem of the main skill, `None` if the gem is not known.
+    fn main_skill_gem(&self) -> Option<&'static poe_data::gems::Gem> {
+        let skill_sets = self.skill_sets();
+        let skill = skill_sets
+            .iter()
+            .find(|ss| ss.is_selected)?
+            .skills
+            .iter()
+            .find(|skill| skill.is_selected)?;
+
+        let mut active = skill.gems.iter().filter(|gem| !gem.is_support);
+        let name = self.main_skill_name();
+        let gem = active
+            .clone()
+            .find(|gem| Some(gem.name) == name)
+            .or_else(|| active.next())?;
+
+        poe_data::gems::by_id(gem.gem_id?)
+    }
+
+    /// All keystones allocated in `spec` or granted by the active gear.
+    ///
+    /// Keystones conquered by a timeless jewel are replaced by the keystone of the jewel's conqueror.
+    fn keystones(&self, spec: &TreeSpec) -> Vec<Keystone> {
+        let timeless = self
+            .jewels(spec)
+            .into_iter()
+            .filter_map(|jewel| match jewel.kind {
+                JewelKind::Timeless(timeless) => Some(timeless),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Conquered keystones are only replaced if the keystone of the conqueror is known,
+        // otherwise the original keystone is kept.
+        let mut conquered = Vec::new();
+        let mut conquerors = Vec::new();
+        for jewel in &timeless {
+            let Some(keystone) = jewel.keystone() else {
+                continue;
+            };
+            conquered.extend(jewel.conquered.iter().map(|&(id, _)| id));
+            if jewel
+                .conquered
+                .iter()
+                .any(|(_, node)| node.kind.is_keystone())
+            {
+                conquerors.push(keystone);
+            }
+        }
+
+        Keystone::ALL
+            .iter()
+            .copied()
+            .filter(|&keystone| {
+                let is_conquered = keystone
+                    .node()
+                    .is_some_and(|node| conquered.contains(&node));
+                conquerors.contains(&keystone)
+                    || (!is_conquered && self.has_keystone_in(spec, keystone))
+            })
+            .collect()
+    }
+
+    /// All jewels socketed in `spec`, cluster and timeless jewels are decoded
+    /// together with the nodes they add or conquer.
+    fn jewels(&self, spec: &TreeSpec) -> Vec<SocketedJewel<'_>> {
+        let version = passives::tree_version(spec);
+
+        spec.sockets
+            .iter()
+            .filter(|socket| spec.nodes.contains(&socket.node_id))
+            .filter_map(|socket| {
+                let item = Item::parse(self.item_by_id(socket.item_id)?).ok()?;
+
+                let kind = if let Some(mut cluster) = ClusterJewel::from_item(&item) {
+                    cluster.allocated =
+                        jewels::cluster_nodes(version, socket.node_id, cluster.size, spec.nodes);
+                    for notable in &mut cluster.notables {
+                        notable.node = jewels::cluster_notable(version, notable.name);
+                    }
+                    JewelKind::Cluster(cluster)
+                } else if let Some(mut timeless) = TimelessJewel::from_item(&item) {
+                    timeless.conquered =
+                        jewels::conquered_nodes(version, socket.node_id, spec.nodes);
+                    JewelKind::Timeless(timeless)
+                } else {
+                    JewelKind::Other
+                };
+
+                Some(SocketedJewel {
+                    node_id: socket.node_id,
+                    item,
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// Tree specs, item sets and skill sets linked into loadouts, tree specs
+    /// without a matching item set and skill set are not part of a loadout.
+    fn loadouts(&self) -> Vec<Loadout<'_>> {
+        loadouts::loadouts(&self.tree_specs(), &self.item_sets(), &self.skill_sets())
+    }
+
+    /// Tree specs and skill sets ordered into a leveling guide, `None` if the build
+    /// does not have multiple stages with a level in their titles.
+    fn leveling_guide(&self) -> Option<leveling::LevelingGuide<'_>> {
+        leveling::leveling_guide(self)
+    }
+
+    /// All notables allocated in `spec`, with their node ids.
+    ///
+    /// Older builds only store the tree URL instead of the nodes, the nodes are taken from the URL.
+    fn allocated_notables(&self, spec: &TreeSpec) -> Vec<(u32, &'static poe_tree::Node)> {
+        let version = passives::tree_version(spec);
+        let nodes = match spec.nodes {
+            [] => spec.tree_url().unwrap_or_default().nodes,
+            nodes => nodes.to_vec(),
+        };
+        nodes
+            .into_iter()
+            .filter_map(|id| Some((id, poe_tree::get_node(version, id)?)))
+            .filter(|(_, node)| node.kind.is_notable())
+            .collect()
+    }
+
     fn stat_parse<T: FromStr>(&self, name: Stat) -> Option<T> {
         PathOfBuilding::stat(self, name).and_then(|x| x.parse().ok())
     }
@@ -192,7 +483,7 @@ pub trait PathOfBuildingExt: PathOfBuilding {
     }
 }
 
-impl<T: PathOfBuilding> PathOfBuildingExt for T {}
+impl<T: PathOfBuilding + ?Sized> PathOfBuildingExt for T {}
 
 impl std::fmt::Debug for dyn PathOfBuilding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
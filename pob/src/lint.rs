@@ -0,0 +1,426 @@
+//! Checks for common mistakes in builds.
+//!
+//! Every check is a [`Lint`], running all checks over a build with [`lint`]
+//! reports a [`Finding`] for every mistake found.
+use crate::{Gem, Keystone, PathOfBuilding, Skill, SkillSet, Stat};
+
+/// Resistance every build should reach, the default maximum resistance.
+const RESISTANCE_CAP: f64 = 75.0;
+
+/// Attacks which don't use the weapon.
+const UNARMED_ATTACKS: &[&str] = &["Poisonous Concoction", "Explosive Concoction"];
+
+/// Skills which move the character, including skills commonly used for movement.
+const MOVEMENT_SKILLS: &[&str] = &[
+    "Blink Arrow",
+    "Bodyswap",
+    "Chain Hook",
+    "Charged Dash",
+    "Dash",
+    "Flame Dash",
+    "Flicker Strike",
+    "Frostblink",
+    "Leap Slam",
+    "Lightning Warp",
+    "Phase Run",
+    "Shield Charge",
+    "Smoke Mine",
+    "Whirling Blades",
+    "Withering Step",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [Self::Info, Self::Warning, Self::Error];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UncappedResistance,
+    NegativeChaosResistance,
+    DisabledMainSkillGem,
+    InapplicableSupport,
+    EmptyGearSlot,
+    NoMovementSkill,
+    MissingFlasks,
+    NoPantheon,
+    NoBandit,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 9] = [
+        Self::UncappedResistance,
+        Self::NegativeChaosResistance,
+        Self::DisabledMainSkillGem,
+        Self::InapplicableSupport,
+        Self::EmptyGearSlot,
+        Self::NoMovementSkill,
+        Self::MissingFlasks,
+        Self::NoPantheon,
+        Self::NoBandit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UncappedResistance => "Uncapped Resistance",
+            Self::NegativeChaosResistance => "Negative Chaos Resistance",
+            Self::DisabledMainSkillGem => "Disabled Gem in Main Skill",
+            Self::InapplicableSupport => "Support does not apply",
+            Self::EmptyGearSlot => "Empty Gear Slot",
+            Self::NoMovementSkill => "No Movement Skill",
+            Self::MissingFlasks => "Missing Flasks",
+            Self::NoPantheon => "No Pantheon",
+            Self::NoBandit => "No Bandit",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::UncappedResistance => Severity::Error,
+            Self::NegativeChaosResistance => Severity::Warning,
+            Self::DisabledMainSkillGem => Severity::Warning,
+            Self::InapplicableSupport => Severity::Error,
+            Self::EmptyGearSlot => Severity::Warning,
+            Self::NoMovementSkill => Severity::Info,
+            Self::MissingFlasks => Severity::Warning,
+            Self::NoPantheon => Severity::Info,
+            // Killing all bandits is a valid choice.
+            Self::NoBandit => Severity::Info,
+        }
+    }
+}
+
+/// A mistake found in a build.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub lint: Lint,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(lint: Lint, message: impl Into<String>) -> Self {
+        Self {
+            lint,
+            message: message.into(),
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.lint.severity()
+    }
+}
+
+/// Runs all checks over the build, findings are ordered by severity, most severe first.
+pub fn lint<P>(pob: &P) -> Vec<Finding>
+where
+    P: PathOfBuilding + ?Sized,
+{
+    let mut findings = Vec::new();
+
+    resistances(pob, &mut findings);
+    main_skill(pob, &mut findings);
+    gear(pob, &mut findings);
+    movement_skill(pob, &mut findings);
+
+    if pob.pantheon_major_god().is_none() || pob.pantheon_minor_god().is_none() {
+        findings.push(Finding::new(
+            Lint::NoPantheon,
+            "No major or minor pantheon god selected",
+        ));
+    }
+    if pob.bandit().is_none() {
+        findings.push(Finding::new(
+            Lint::NoBandit,
+            "No bandit selected, all bandits are killed",
+        ));
+    }
+
+    // Stable sort, keeps the order of the checks within a severity.
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity()));
+    findings
+}
+
+fn resistances<P: PathOfBuilding + ?Sized>(pob: &P, findings: &mut Vec<Finding>) {
+    let stats = pob.stats().collect::<Vec<_>>();
+    let stat = |stat: Stat| {
+        stats
+            .iter()
+            .find(|(s, _)| *s == stat)
+            .map(|(_, value)| value.as_f64())
+    };
+
+    let elemental = [
+        (Stat::FireResistance, "Fire"),
+        (Stat::ColdResistance, "Cold"),
+        (Stat::LightningResistance, "Lightning"),
+    ];
+    for (resistance, name) in elemental {
+        let Some(value) = stat(resistance) else {
+            continue;
+        };
+        if value < RESISTANCE_CAP {
+            findings.push(Finding::new(
+                Lint::UncappedResistance,
+                format!("{name} resistance is {value}%, {RESISTANCE_CAP}% is the cap"),
+            ));
+        }
+    }
+
+    if let Some(chaos) = stat(Stat::ChaosResistance) {
+        if chaos < 0.0 && !pob.has_keystone(Keystone::ChaosInoculation) {
+            findings.push(Finding::new(
+                Lint::NegativeChaosResistance,
+                format!("Chaos resistance is {chaos}%"),
+            ));
+        }
+    }
+}
+
+fn main_skill<P: PathOfBuilding + ?Sized>(pob: &P, findings: &mut Vec<Finding>) {
+    let skill_sets = pob.skill_sets();
+    let Some(skill) = selected_skills(&skill_sets).find(|skill| skill.is_selected) else {
+        return;
+    };
+
+    for gem in skill.gems.iter().filter(|gem| !gem.is_enabled) {
+        findings.push(Finding::new(
+            Lint::DisabledMainSkillGem,
+            format!("{} is disabled", gem.name),
+        ));
+    }
+
+    let Some(active) = main_skill_gem(pob, skill) else {
+        return;
+    };
+    let Some(active_data) = gem_data(active) else {
+        return;
+    };
+
+    let supports = skill
+        .gems
+        .iter()
+        .filter(|gem| gem.is_support && gem.is_enabled);
+    for gem in supports {
+        let Some(support) = gem_data(gem) else {
+            continue;
+        };
+        if active_data.is_supported_by(support) == Some(false) {
+            findings.push(Finding::new(
+                Lint::InapplicableSupport,
+                format!("{} does not support {}", gem.name, active.name),
+            ));
+        }
+    }
+}
+
+fn gear<P: PathOfBuilding + ?Sized>(pob: &P, findings: &mut Vec<Finding>) {
+    let item_sets = pob.item_sets();
+    let Some(item_set) = item_sets.iter().find(|set| set.is_selected) else {
+        return;
+    };
+    let gear = &item_set.gear;
+
+    let skill_sets = pob.skill_sets();
+    let needs_weapon = selected_skills(&skill_sets)
+        .find(|skill| skill.is_selected)
+        .and_then(|skill| main_skill_gem(pob, skill))
+        .is_none_or(uses_weapon);
+
+    let slots = [
+        ("Weapon", gear.weapon1),
+        ("Helmet", gear.helmet),
+        ("Body Armour", gear.body_armour),
+        ("Gloves", gear.gloves),
+        ("Boots", gear.boots),
+        ("Amulet", gear.amulet),
+        ("Ring 1", gear.ring1),
+        ("Ring 2", gear.ring2),
+        ("Belt", gear.belt),
+    ];
+    let empty = slots
+        .into_iter()
+        .filter(|(name, _)| needs_weapon || *name != "Weapon")
+        .filter(|(_, item)| item.is_none());
+    for (name, _) in empty {
+        findings.push(Finding::new(
+            Lint::EmptyGearSlot,
+            format!("{name} slot is empty"),
+        ));
+    }
+
+    let flasks = [
+        gear.flask1,
+        gear.flask2,
+        gear.flask3,
+        gear.flask4,
+        gear.flask5,
+    ]
+    .into_iter()
+    .flatten()
+    .count();
+    if flasks < 5 {
+        findings.push(Finding::new(
+            Lint::MissingFlasks,
+            format!("Only {flasks} of 5 flask slots are used"),
+        ));
+    }
+}
+
+fn movement_skill<P: PathOfBuilding + ?Sized>(pob: &P, findings: &mut Vec<Finding>) {
+    let skill_sets = pob.skill_sets();
+    let has_movement_skill = selected_skills(&skill_sets)
+        .filter(|skill| skill.is_enabled)
+        .flat_map(|skill| &skill.gems)
+        .filter(|gem| gem.is_enabled)
+        .any(|gem| MOVEMENT_SKILLS.contains(&gem.base_name));
+
+    if !has_movement_skill {
+        findings.push(Finding::new(
+            Lint::NoMovementSkill,
+            "No movement skill in the active skill set",
+        ));
+    }
+}
+
+/// The active gem of the main skill, the first active gem if the main skill is not found.
+fn main_skill_gem<'a, P: PathOfBuilding + ?Sized>(
+    pob: &P,
+    skill: &'a Skill<'a>,
+) -> Option<&'a Gem<'a>> {
+    let mut active = skill.gems.iter().filter(|gem| !gem.is_support);
+    let name = pob.main_skill_name();
+    active
+        .clone()
+        .find(|gem| Some(gem.name) == name)
+        .or_else(|| active.next())
+}
+
+fn gem_data(gem: &Gem) -> Option<&'static poe_data::gems::Gem> {
+    poe_data::gems::by_id(gem.gem_id?)
+}
+
+/// Whether the skill uses the weapon, only attacks do.
+///
+/// Skills which are not in the gem data are assumed to use the weapon.
+fn uses_weapon(gem: &Gem) -> bool {
+    if UNARMED_ATTACKS.contains(&gem.base_name) {
+        return false;
+    }
+    gem_data(gem).is_none_or(|data| data.tags.contains(&"attack"))
+}
+
+fn selected_skills<'a>(skill_sets: &'a [SkillSet<'a>]) -> impl Iterator<Item = &'a Skill<'a>> {
+    skill_sets
+        .iter()
+        .find(|ss| ss.is_selected)
+        .into_iter()
+        .flat_map(|ss| &ss.skills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_EMPTY: &str = include_str!("../test/316_empty.xml");
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+
+    #[test]
+    fn lint_poison_occ() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+
+        let findings = lint(&pob);
+        assert_eq!(
+            findings,
+            [Finding::new(
+                Lint::NoBandit,
+                "No bandit selected, all bandits are killed"
+            )]
+        );
+    }
+
+    #[test]
+    fn lint_unusual_support() {
+        let xml = V316_POISON_OCC
+            .replace("SupportGemGreaterVolley", "SupportGemCullingStrike")
+            .replace("SupportGreaterVolley", "SupportCullingStrike")
+            .replace("nameSpec=\"Greater Volley\"", "nameSpec=\"Culling Strike\"");
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+
+        let lints = lint(&pob).into_iter().map(|f| f.lint).collect::<Vec<_>>();
+        assert!(!lints.contains(&Lint::InapplicableSupport));
+    }
+
+    #[test]
+    fn support_rules() {
+        use poe_data::gems::{Gem, SupportRules};
+        use shared::Color;
+
+        let gem = |types, support| Gem {
+            name: "",
+            color: Color::White,
+            level: 1,
+            tags: &[],
+            types,
+            support,
+            vendors: &[],
+        };
+
+        let projectile_attack = gem(&["Attack", "Projectile"], None);
+        let totem = gem(&["Spell", "SummonsTotem"], None);
+        let unknown = gem(&[], None);
+
+        let projectiles = gem(
+            &[],
+            Some(SupportRules {
+                allowed_types: &["Projectile"],
+                excluded_types: &[],
+            }),
+        );
+        let no_totems = gem(
+            &[],
+            Some(SupportRules {
+                allowed_types: &[],
+                excluded_types: &["SummonsTotem"],
+            }),
+        );
+        let no_rules = gem(&[], None);
+
+        assert_eq!(projectile_attack.is_supported_by(&projectiles), Some(true));
+        assert_eq!(projectile_attack.is_supported_by(&no_totems), Some(true));
+        assert_eq!(totem.is_supported_by(&projectiles), Some(false));
+        assert_eq!(totem.is_supported_by(&no_totems), Some(false));
+        assert_eq!(totem.is_supported_by(&no_rules), None);
+        assert_eq!(unknown.is_supported_by(&projectiles), None);
+    }
+
+    #[test]
+    fn lint_empty() {
+        let pob = SerdePathOfBuilding::from_xml(V316_EMPTY).unwrap();
+
+        let findings = lint(&pob);
+        assert!(findings
+            .windows(2)
+            .all(|w| w[0].severity() >= w[1].severity()));
+
+        let lints = findings.iter().map(|f| f.lint).collect::<Vec<_>>();
+        assert!(lints.contains(&Lint::EmptyGearSlot));
+        assert!(lints.contains(&Lint::MissingFlasks));
+        assert!(lints.contains(&Lint::NoMovementSkill));
+        assert!(!lints.contains(&Lint::InapplicableSupport));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{ItemSet, SkillSet, TreeSpec};
+
+/// Title Path of Building uses for sets without a title.
+const DEFAULT_TITLE: &str = "Default";
+
+/// A tree spec with its matching item set and skill set.
+///
+/// Path of Building links sets into a loadout either by giving them the same title,
+/// or explicitly with an id in braces, e.g. a tree spec `Mapping {1}` is linked
+/// to the item set `Gear {1}` and the skill set `{1,2} Skills`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loadout<'a> {
+    /// Title of the tree spec without the link ids.
+    pub title: &'a str,
+    /// Index into [`crate::PathOfBuilding::tree_specs`].
+    pub tree_spec: usize,
+    /// Index into [`crate::PathOfBuilding::item_sets`].
+    pub item_set: usize,
+    /// Index into [`crate::PathOfBuilding::skill_sets`].
+    pub skill_set: usize,
+    /// Whether the tree spec, item set and skill set are all selected.
+    pub is_selected: bool,
+}
+
+pub(crate) fn loadouts<'a>(
+    tree_specs: &[TreeSpec<'a>],
+    item_sets: &[ItemSet<'a>],
+    skill_sets: &[SkillSet<'a>],
+) -> Vec<Loadout<'a>> {
+    let item_titles = item_sets
+        .iter()
+        .map(|set| set.title.unwrap_or(DEFAULT_TITLE))
+        .collect::<Vec<_>>();
+    let skill_titles = skill_sets
+        .iter()
+        .map(|set| set.title.unwrap_or(DEFAULT_TITLE))
+        .collect::<Vec<_>>();
+
+    tree_specs
+        .iter()
+        .enumerate()
+        .filter_map(|(tree_spec, spec)| {
+            let title = spec.title.unwrap_or(DEFAULT_TITLE);
+
+            let item_set = find_linked(title, &item_titles)?;
+            let skill_set = find_linked(title, &skill_titles)?;
+
+            Some(Loadout {
+                title: strip_link(title),
+                tree_spec,
+                item_set,
+                skill_set,
+                is_selected: spec.active
+                    && item_sets[item_set].is_selected
+                    && skill_sets[skill_set].is_selected,
+            })
+        })
+        .collect()
+}
+
+/// Finds the title linked to `title`, by link id or by the same title if `title` has no link.
+fn find_linked(title: &str, titles: &[&str]) -> Option<usize> {
+    match link_ids(title) {
+        Some(ids) => titles.iter().position(|other| {
+            link_ids(other).is_some_and(|mut other| other.any(|id| ids.clone().any(|i| i == id)))
+        }),
+        None => titles.iter().position(|other| *other == title),
+    }
+}
+
+/// Ids of a link like `{1,2}` in a title, ids are alphanumeric.
+fn link_ids(title: &str) -> Option<impl Iterator<Item = &str> + Clone> {
+    let (_, rest) = title.split_once('{')?;
+    let (ids, _) = rest.split_once('}')?;
+
+    let is_valid = !ids.is_empty() && ids.chars().all(|c| c.is_alphanumeric() || c == ',');
+    is_valid.then(|| ids.split(',').filter(|id| !id.is_empty()))
+}
+
+/// Removes the link from a title, e.g. `Mapping {1}` becomes `Mapping`.
+pub(crate) fn strip_link(title: &str) -> &str {
+    let Some((start, rest)) = title.split_once('{') else {
+        return title;
+    };
+    let Some((_, end)) = rest.split_once('}') else {
+        return title;
+    };
+
+    let stripped = match (start.trim(), end.trim()) {
+        (start, "") => start,
+        ("", end) => end,
+        // Link in the middle of the title, keep the title as is.
+        _ => title,
+    };
+
+    if stripped.is_empty() {
+        title
+    } else {
+        stripped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_ids_test() {
+        let ids = |title| link_ids(title).map(|ids| ids.collect::<Vec<_>>());
+        assert_eq!(Some(vec!["1"]), ids("Mapping {1}"));
+        assert_eq!(Some(vec!["1", "2"]), ids("{1,2} Skills"));
+        assert_eq!(None, ids("Mapping"));
+        assert_eq!(None, ids("Mapping {}"));
+        assert_eq!(None, ids("Mapping {not valid}"));
+    }
+
+    #[test]
+    fn strip_link_test() {
+        assert_eq!("Mapping", strip_link("Mapping {1}"));
+        assert_eq!("Skills", strip_link("{1,2} Skills"));
+        assert_eq!("{1}", strip_link("{1}"));
+        assert_eq!("Mapping", strip_link("Mapping"));
+    }
+
+    #[test]
+    fn find_linked_test() {
+        let titles = ["Default", "Leveling", "Gear {2}", "{1} Gear"];
+        assert_eq!(Some(0), find_linked("Default", &titles));
+        assert_eq!(Some(1), find_linked("Leveling", &titles));
+        assert_eq!(Some(3), find_linked("Mapping {1}", &titles));
+        assert_eq!(Some(2), find_linked("Bossing {3,2}", &titles));
+        assert_eq!(None, find_linked("Bossing {4}", &titles));
+        assert_eq!(None, find_linked("Mapping", &titles));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Stat, StatValue};
+
+/// The minion Path of Building calculated stats for, summoned by the main skill.
+#[derive(Debug)]
+pub struct Minion<'a> {
+    /// Path of Building's id of the selected minion, e.g. `SummonedPhantasm` or
+    /// the monster variety of a spectre `Metadata/Monsters/...`.
+    pub id: Option<&'a str>,
+    /// Name of the skill which summons the minion, e.g. `Raise Spectre`.
+    pub summoned_by: Option<&'a str>,
+    /// Index of the minion's skill used for the calculations, starts at 1.
+    pub skill_index: Option<u8>,
+    pub stats: MinionStats,
+}
+
+impl<'a> Minion<'a> {
+    /// Human readable name of the minion derived from its id, e.g. `Summoned Phantasm`.
+    pub fn name(&self) -> Option<String> {
+        let id = self.id?.rsplit('/').next()?;
+
+        let mut name = String::with_capacity(id.len() + 4);
+        for (i, c) in id.char_indices() {
+            let prev = id[..i].chars().next_back();
+            if c.is_uppercase() && prev.is_some_and(|p| p.is_lowercase()) {
+                name.push(' ');
+            }
+            name.push(c);
+        }
+        Some(name)
+    }
+}
+
+/// Offense and defense of a minion, from the minion stats of a build.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MinionStats {
+    pub life: Option<f64>,
+    pub energy_shield: Option<f64>,
+    pub fire_resistance: Option<f64>,
+    pub cold_resistance: Option<f64>,
+    pub lightning_resistance: Option<f64>,
+    pub chaos_resistance: Option<f64>,
+    /// Combined DPS of the minion, including damage over time.
+    pub dps: Option<f64>,
+    /// Attack or cast speed.
+    pub speed: Option<f64>,
+    pub movement_speed: Option<f64>,
+}
+
+impl MinionStats {
+    pub fn from_stats<'a>(stats: impl IntoIterator<Item = (Stat<'a>, StatValue)>) -> Self {
+        let mut result = Self::default();
+        let mut total_dps = None;
+
+        for (stat, value) in stats {
+            let value = Some(value.as_f64()).filter(|v| v.is_finite());
+            match stat {
+                Stat::Life => result.life = value,
+                Stat::EnergyShield => result.energy_shield = value,
+                Stat::FireResistance => result.fire_resistance = value,
+                Stat::ColdResistance => result.cold_resistance = value,
+                Stat::LightningResistance => result.lightning_resistance = value,
+                Stat::ChaosResistance => result.chaos_resistance = value,
+                Stat::CombinedDps => result.dps = value,
+                Stat::TotalDps => total_dps = value,
+                Stat::Speed => result.speed = value,
+                Stat::MovementSpeed => result.movement_speed = value,
+                _ => (),
+            }
+        }
+
+        // Older versions of Path of Building only export the hit DPS of minions.
+        result.dps = result.dps.or(total_dps);
+        result
+    }
+
+    /// Whether Path of Building calculated a minion at all,
+    /// builds without a minion skill still export a few minion stats.
+    pub fn is_empty(&self) -> bool {
+        self.life.is_none() && self.dps.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minion_name() {
+        let minion = |id| Minion {
+            id: Some(id),
+            summoned_by: None,
+            skill_index: None,
+            stats: MinionStats::default(),
+        };
+
+        assert_eq!(
+            minion("SummonedPhantasm").name().as_deref(),
+            Some("Summoned Phantasm")
+        );
+        assert_eq!(
+            minion("Metadata/Monsters/Kiweth/KiwethSeagull")
+                .name()
+                .as_deref(),
+            Some("Kiweth Seagull")
+        );
+        assert_eq!(
+            minion("RaisedSkeletonMDAmp").name().as_deref(),
+            Some("Raised Skeleton MDAmp")
+        );
+    }
+
+    #[test]
+    fn minion_stats() {
+        let stats = MinionStats::from_stats([
+            (Stat::Life, StatValue::Integer(5000)),
+            (Stat::TotalDps, StatValue::Rate(100.0)),
+            (Stat::FireResistance, StatValue::Percent(75.0)),
+        ]);
+        assert_eq!(Some(5000.0), stats.life);
+        assert_eq!(Some(100.0), stats.dps);
+        assert_eq!(Some(75.0), stats.fire_resistance);
+        assert!(!stats.is_empty());
+
+        assert!(
+            MinionStats::from_stats([(Stat::EnduranceChargesMax, StatValue::Integer(1))])
+                .is_empty()
+        );
+    }
+}
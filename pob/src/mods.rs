@@ -0,0 +1,274 @@
+//! Translation of mod lines into stat ids and values.
+//!
+//! Mod lines are matched against a table of stat descriptions, values in the line
+//! are replaced by `#` to find the description, e.g. `+(50-70) to maximum Life`
+//! and `+109 to maximum Life` both match `# to maximum Life`.
+//!
+//! The table in `data/stat_descriptions.json` is a hand-maintained subset of
+//! Path of Building's `src/Data/StatDescriptions/stat_descriptions.lua`,
+//! lines without a matching description are not translated.
+//!
+//! Some lines are local to the item they are on, e.g. `+# to maximum Energy Shield`
+//! is `local_energy_shield` on a body armour but `base_maximum_energy_shield` on a belt.
+
+include!(concat!(env!("OUT_DIR"), "/mods.rs"));
+
+struct StatDescription {
+    stats: &'static [&'static str],
+    /// Stats of the line on items where it is local.
+    local: Option<&'static [&'static str]>,
+    /// Whether the value of the line is the negated value of the stat, e.g. `reduced` mods.
+    negate: bool,
+}
+
+/// A stat granted by a mod.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModStat {
+    /// Id of the stat as used by the game, e.g. `base_maximum_life`.
+    pub id: &'static str,
+    pub value: f64,
+    /// Range the value was rolled in, only known for items which record it,
+    /// e.g. `{range:0.5}+(50-70) to maximum Life`.
+    pub range: Option<RollRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RollRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Translates a mod line, without `{...}` attributes, into its stats.
+///
+/// `roll` is the position of the value within its range, Path of Building defaults to `0.5`.
+/// `local` selects the local stats of lines which modify the item itself.
+pub(crate) fn translate(line: &str, roll: Option<f32>, local: bool) -> Option<Vec<ModStat>> {
+    let (key, values) = split_values(line)?;
+    let description = STAT_DESCRIPTIONS.get(key.as_str())?;
+
+    let roll = roll.unwrap_or(0.5) as f64;
+    let sign = if description.negate { -1.0 } else { 1.0 };
+
+    let ids = match description.local {
+        Some(ids) if local => ids,
+        _ => description.stats,
+    };
+
+    let stats = ids
+        .iter()
+        .zip(values)
+        .map(|(&id, value)| {
+            let (value, range) = match value {
+                Value::Fixed(value) => (value, None),
+                Value::Range(min, max, decimals) => {
+                    let value = round(min + (max - min) * roll, decimals);
+                    (value, Some((min, max)))
+                }
+            };
+
+            let range = range.map(|(min, max)| {
+                let (min, max) = (min * sign, max * sign);
+                RollRange {
+                    min: min.min(max),
+                    max: min.max(max),
+                }
+            });
+
+            ModStat {
+                id,
+                value: value * sign,
+                range,
+            }
+        })
+        .collect();
+
+    Some(stats)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Fixed(f64),
+    /// Range with the amount of decimals of the values.
+    Range(f64, f64, u32),
+}
+
+/// Replaces all values in the line with `#`, returns the replaced line and the values.
+fn split_values(line: &str) -> Option<(String, Vec<Value>)> {
+    let mut key = String::with_capacity(line.len());
+    let mut values = Vec::new();
+
+    let mut rest = line;
+    let mut boundary = true;
+    while let Some(c) = rest.chars().next() {
+        if boundary {
+            if let Some((value, len)) = parse_value(rest) {
+                key.push('#');
+                values.push(value);
+                rest = &rest[len..];
+                boundary = false;
+                continue;
+            }
+        }
+
+        key.push(c);
+        rest = &rest[c.len_utf8()..];
+        boundary = !c.is_alphanumeric();
+    }
+
+    (!values.is_empty()).then_some((key, values))
+}
+
+/// Parses a value like `10`, `-1.5`, `+(50-70)` or `(-10-10)`, returns the value and its length.
+fn parse_value(s: &str) -> Option<(Value, usize)> {
+    let (sign, unsigned) = match s.as_bytes().first()? {
+        b'+' => (1.0, &s[1..]),
+        b'-' if s[1..].starts_with('(') => (-1.0, &s[1..]),
+        _ => (1.0, s),
+    };
+    let offset = s.len() - unsigned.len();
+
+    if let Some(range) = unsigned.strip_prefix('(') {
+        let (min, min_decimals, min_len) = parse_number(range)?;
+        let range = range[min_len..].strip_prefix('-')?;
+        let (max, max_decimals, max_len) = parse_number(range)?;
+        range[max_len..].strip_prefix(')')?;
+
+        let value = Value::Range(sign * min, sign * max, min_decimals.max(max_decimals));
+        // `(`, `-` and `)`
+        return Some((value, offset + min_len + max_len + 3));
+    }
+
+    let (value, _, len) = parse_number(unsigned)?;
+    Some((Value::Fixed(sign * value), offset + len))
+}
+
+/// Parses a number with an optional minus sign, returns the number, its decimals and its length.
+fn parse_number(s: &str) -> Option<(f64, u32, usize)> {
+    let bytes = s.as_bytes();
+    let mut len = usize::from(bytes.first() == Some(&b'-'));
+
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+
+    let integer = digits(len);
+    if integer == 0 {
+        return None;
+    }
+    len += integer;
+
+    let mut decimals = 0;
+    if bytes.get(len) == Some(&b'.') {
+        decimals = digits(len + 1);
+        if decimals > 0 {
+            len += decimals + 1;
+        }
+    }
+
+    let value = s[..len].parse().ok()?;
+    Some((value, decimals as u32, len))
+}
+
+fn round(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_values_test() {
+        assert_eq!(
+            split_values("+(50-70) to maximum Life"),
+            Some((
+                "# to maximum Life".to_owned(),
+                vec![Value::Range(50.0, 70.0, 0)]
+            ))
+        );
+        assert_eq!(
+            split_values("Adds 1.5 to 20 Physical Damage"),
+            Some((
+                "Adds # to # Physical Damage".to_owned(),
+                vec![Value::Fixed(1.5), Value::Fixed(20.0)]
+            ))
+        );
+        assert_eq!(
+            split_values("-10% to Fire Resistance"),
+            Some((
+                "#% to Fire Resistance".to_owned(),
+                vec![Value::Fixed(-10.0)]
+            ))
+        );
+        assert_eq!(
+            split_values("+(-10-10)% to Chaos Resistance"),
+            Some((
+                "#% to Chaos Resistance".to_owned(),
+                vec![Value::Range(-10.0, 10.0, 0)]
+            ))
+        );
+        assert_eq!(split_values("Elemental Equilibrium"), None);
+        assert_eq!(
+            split_values("Non-Channelling Skills have 5 increased Cost").map(|(k, _)| k),
+            Some("Non-Channelling Skills have # increased Cost".to_owned())
+        );
+    }
+
+    #[test]
+    fn translate_mods() {
+        let life = translate("+109 to maximum Life", None, false).unwrap();
+        assert_eq!(
+            life,
+            [ModStat {
+                id: "base_maximum_life",
+                value: 109.0,
+                range: None
+            }]
+        );
+
+        let life = translate("+(50-70) to maximum Life", Some(1.0), false).unwrap();
+        assert_eq!(70.0, life[0].value);
+        assert_eq!(
+            Some(RollRange {
+                min: 50.0,
+                max: 70.0
+            }),
+            life[0].range
+        );
+
+        let reduced = translate("(10-20)% reduced maximum Mana", None, false).unwrap();
+        assert_eq!("maximum_mana_+%", reduced[0].id);
+        assert_eq!(-15.0, reduced[0].value);
+        assert_eq!(
+            Some(RollRange {
+                min: -20.0,
+                max: -10.0
+            }),
+            reduced[0].range
+        );
+
+        let added = translate("Adds 10 to 20 Fire Damage to Attacks", None, false).unwrap();
+        assert_eq!(
+            added.iter().map(|s| (s.id, s.value)).collect::<Vec<_>>(),
+            [
+                ("attack_minimum_added_fire_damage", 10.0),
+                ("attack_maximum_added_fire_damage", 20.0)
+            ]
+        );
+
+        let es = translate("+50 to maximum Energy Shield", None, false).unwrap();
+        assert_eq!("base_maximum_energy_shield", es[0].id);
+        let es = translate("+50 to maximum Energy Shield", None, true).unwrap();
+        assert_eq!("local_energy_shield", es[0].id);
+        // Lines without local stats are the same on every item.
+        let life = translate("+109 to maximum Life", None, true).unwrap();
+        assert_eq!("base_maximum_life", life[0].id);
+
+        assert_eq!(None, translate("Elemental Equilibrium", None, false));
+        assert_eq!(None, translate("+10 to something unknown", None, false));
+    }
+}
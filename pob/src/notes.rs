@@ -0,0 +1,685 @@
+//! Parser for build notes.
+//!
+//! Notes are plain text with Path of Building's color codes (`^1`-`^9`, `^xRRGGBB`),
+//! on top of that lightweight structure which is commonly used in notes is recognized:
+//!
+//! - Headings, lines starting with `#` or wrapped in `==`/`--`, e.g. `== Leveling ==`.
+//! - Bullet lists, lines starting with `-`, `*` or `•`.
+//! - Links, e.g. to YouTube, poe.ninja or pobb.in.
+//! - Names of gems and items used in the build.
+use crate::{Item, PathOfBuilding, Rarity};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Color<'a> {
+    Hex(&'a str),
+    Named(u8),
+    #[default]
+    None,
+}
+
+/// Splits text into parts of the same color.
+pub struct ColoredText<'a> {
+    text: Option<&'a str>,
+    style: Color<'a>,
+}
+
+impl<'a> ColoredText<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text: Some(text),
+            style: Color::None,
+        }
+    }
+}
+
+impl<'a> Iterator for ColoredText<'a> {
+    type Item = (Color<'a>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.text.take()?;
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut start_search = 0;
+
+        let (end, next_start, next_style) = loop {
+            let index = match text.get(start_search..).and_then(|s| s.find('^')) {
+                Some(index) => start_search + index,
+                None => {
+                    return Some((std::mem::replace(&mut self.style, Color::None), text));
+                }
+            };
+
+            let r = match text.get(index + 1..index + 2) {
+                Some("x") => text
+                    .get(index + 2..index + 8)
+                    .filter(|hex| hex.as_bytes().iter().all(u8::is_ascii_hexdigit))
+                    .map(Color::Hex)
+                    .map(|color| (8, color)),
+                Some("0") => Some((2, Color::Named(0))),
+                Some("1") => Some((2, Color::Named(1))),
+                Some("2") => Some((2, Color::Named(2))),
+                Some("3") => Some((2, Color::Named(3))),
+                Some("4") => Some((2, Color::Named(4))),
+                Some("5") => Some((2, Color::Named(5))),
+                Some("6") => Some((2, Color::Named(6))),
+                Some("7") => Some((2, Color::Named(7))),
+                Some("8") => Some((2, Color::Named(8))),
+                Some("9") => Some((2, Color::Named(9))),
+                _ => None,
+            };
+
+            if let Some((offset, next_style)) = r {
+                break (index, index + offset, next_style);
+            }
+
+            start_search = start_search + index + 1;
+        };
+
+        let style = std::mem::replace(&mut self.style, next_style);
+        let result = (style, &text[..end]);
+
+        self.text = text.get(next_start..);
+
+        if result.1.is_empty() {
+            // e.g. "^1foo" produces an empty range
+            // or "^1^2foo" produces two empty ranges
+            self.next()
+        } else {
+            Some(result)
+        }
+    }
+}
+
+/// Parsed build notes, one block per line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Notes<'a> {
+    pub blocks: Vec<Block<'a>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Block<'a> {
+    Heading(Heading<'a>),
+    ListItem(Vec<Span<'a>>),
+    Line(Vec<Span<'a>>),
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Heading<'a> {
+    /// `1` for the most important headings.
+    pub level: u8,
+    /// Title without colors.
+    pub title: String,
+    /// Unique id of the heading within the notes, usable as an anchor.
+    pub anchor: String,
+    pub spans: Vec<Span<'a>>,
+}
+
+/// Part of a line with a single color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub color: Color<'a>,
+    pub inline: Inline<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inline<'a> {
+    Text(&'a str),
+    Link(Link<'a>),
+    /// Name of a gem used in the build.
+    Gem(GemReference<'a>),
+    /// Name of an item used in the build.
+    Item(ItemReference<'a>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link<'a> {
+    pub url: &'a str,
+    pub kind: LinkKind,
+}
+
+impl<'a> Link<'a> {
+    /// Domain of the link without a `www.` prefix.
+    pub fn domain(&self) -> &'a str {
+        let rest = self
+            .url
+            .split_once("://")
+            .map_or(self.url, |(_, rest)| rest);
+        let domain = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+        domain.strip_prefix("www.").unwrap_or(domain)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    YouTube,
+    PoeNinja,
+    Pobbin,
+    Other,
+}
+
+impl LinkKind {
+    fn from_domain(domain: &str) -> Self {
+        match domain {
+            "youtube.com" | "youtu.be" | "m.youtube.com" => Self::YouTube,
+            "poe.ninja" => Self::PoeNinja,
+            "pobb.in" => Self::Pobbin,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GemReference<'a> {
+    pub name: &'a str,
+    pub gem_id: Option<&'a str>,
+    pub level: u8,
+    pub quality: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemReference<'a> {
+    pub name: &'a str,
+    /// The item in Path of Building's item text format.
+    pub item: &'a str,
+}
+
+/// Gems and items of a build which are recognized in the notes.
+#[derive(Debug, Default)]
+pub struct References<'a> {
+    gems: Vec<(&'a str, GemReference<'a>)>,
+    items: Vec<(&'a str, ItemReference<'a>)>,
+}
+
+/// Names shorter than this are too likely to be part of regular text.
+const MIN_REFERENCE_LEN: usize = 4;
+
+impl<'a> References<'a> {
+    /// Gems of all skill sets and named items of all item sets.
+    pub fn from_build<P: PathOfBuilding>(pob: &'a P) -> Self {
+        let mut references = Self::default();
+
+        for gem in pob
+            .skill_sets()
+            .into_iter()
+            .flat_map(|ss| ss.skills)
+            .flat_map(|s| s.gems)
+        {
+            references.add_gem(GemReference {
+                name: gem.name,
+                gem_id: gem.gem_id,
+                level: gem.level,
+                quality: gem.quality,
+            });
+        }
+
+        for item_set in pob.item_sets() {
+            for item in item_set.gear.slots.iter().map(|slot| slot.item) {
+                let Ok(parsed) = Item::parse(item) else {
+                    continue;
+                };
+                let Some(name) = parsed.name else {
+                    continue;
+                };
+                if matches!(parsed.rarity, Rarity::Unique | Rarity::Relic | Rarity::Rare) {
+                    references.add_item(ItemReference { name, item });
+                }
+            }
+        }
+
+        references
+    }
+
+    pub fn add_gem(&mut self, gem: GemReference<'a>) {
+        if gem.name.len() >= MIN_REFERENCE_LEN
+            && !self.gems.iter().any(|(name, _)| *name == gem.name)
+        {
+            self.gems.push((gem.name, gem));
+        }
+    }
+
+    pub fn add_item(&mut self, item: ItemReference<'a>) {
+        if item.name.len() >= MIN_REFERENCE_LEN
+            && !self.items.iter().any(|(name, _)| *name == item.name)
+        {
+            self.items.push((item.name, item));
+        }
+    }
+
+    /// Finds the first reference in `text`, longer names win over shorter ones at the same position.
+    fn find(&self, text: &str) -> Option<(usize, usize, Inline<'a>)> {
+        let gems = self
+            .gems
+            .iter()
+            .map(|(name, gem)| (*name, Inline::Gem(*gem)));
+        let items = self
+            .items
+            .iter()
+            .map(|(name, item)| (*name, Inline::Item(*item)));
+
+        gems.chain(items)
+            .filter_map(|(name, inline)| Some((find_word(text, name)?, name.len(), inline)))
+            .min_by_key(|(start, len, _)| (*start, std::cmp::Reverse(*len)))
+    }
+}
+
+/// Finds `word` in `text` which is not part of a longer word.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+
+    text.match_indices(word)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            !is_word(text[..index].chars().next_back())
+                && !is_word(text[index + word.len()..].chars().next())
+        })
+}
+
+impl<'a> Notes<'a> {
+    /// Parses the notes of a build, gems and items of the build are recognized in the text.
+    pub fn from_build<P: PathOfBuilding>(pob: &'a P) -> Self {
+        Self::parse(pob.notes(), &References::from_build(pob))
+    }
+
+    pub fn parse(text: &'a str, references: &References<'a>) -> Self {
+        let mut blocks = Vec::new();
+        let mut anchors = Vec::<String>::new();
+
+        for line in lines(text) {
+            let stripped = line.iter().map(|(_, text)| *text).collect::<String>();
+            let trimmed = stripped.trim();
+
+            let block = if trimmed.is_empty() {
+                Block::Empty
+            } else if let Some((level, title)) = parse_heading(trimmed) {
+                let start = stripped.find(title).unwrap_or(0);
+                let spans = trim_spans(&line, start, start + title.len());
+
+                let mut anchor = slug(title);
+                let base = anchor.clone();
+                let mut count = 1;
+                while anchors.contains(&anchor) {
+                    count += 1;
+                    anchor = format!("{base}-{count}");
+                }
+                anchors.push(anchor.clone());
+
+                Block::Heading(Heading {
+                    level,
+                    title: title.to_owned(),
+                    anchor,
+                    spans: to_spans(&spans, references),
+                })
+            } else if let Some(item) = parse_list_item(trimmed) {
+                let start =
+                    stripped.len() - stripped.trim_start().len() + (trimmed.len() - item.len());
+                let spans = trim_spans(&line, start, start + item.len());
+                Block::ListItem(to_spans(&spans, references))
+            } else {
+                Block::Line(to_spans(&line, references))
+            };
+
+            blocks.push(block);
+        }
+
+        Self { blocks }
+    }
+
+    pub fn headings(&self) -> impl Iterator<Item = &Heading<'a>> {
+        self.blocks.iter().filter_map(|block| match block {
+            Block::Heading(heading) => Some(heading),
+            _ => None,
+        })
+    }
+}
+
+/// Colored parts of each line, colors carry over into the following lines.
+fn lines(text: &str) -> Vec<Vec<(Color<'_>, &str)>> {
+    let mut lines = vec![Vec::new()];
+
+    for (color, text) in ColoredText::new(text) {
+        for (index, part) in text.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(Vec::new());
+            }
+            let part = part.strip_suffix('\r').unwrap_or(part);
+            if !part.is_empty() {
+                lines.last_mut().unwrap().push((color, part));
+            }
+        }
+    }
+
+    lines
+}
+
+/// Only keeps the range `start..end` of the concatenated text of `line`.
+fn trim_spans<'a>(
+    line: &[(Color<'a>, &'a str)],
+    start: usize,
+    end: usize,
+) -> Vec<(Color<'a>, &'a str)> {
+    let mut offset = 0;
+    let mut result = Vec::new();
+
+    for &(color, text) in line {
+        let (span_start, span_end) = (offset, offset + text.len());
+        offset = span_end;
+
+        let from = start.clamp(span_start, span_end) - span_start;
+        let to = end.clamp(span_start, span_end) - span_start;
+        if from < to {
+            result.push((color, &text[from..to]));
+        }
+    }
+
+    result
+}
+
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    if let Some(rest) = line.strip_prefix('#') {
+        let level = 1 + rest.len() - rest.trim_start_matches('#').len();
+        let title = rest.trim_start_matches('#');
+        // `#1` is more likely a ranking than a heading.
+        if level <= 3 && title.starts_with(' ') {
+            return non_empty(title.trim()).map(|title| (level as u8, title));
+        }
+        return None;
+    }
+
+    for (marker, level) in [('=', 1), ('-', 2)] {
+        let inner = line.trim_start_matches(marker);
+        let prefix = line.len() - inner.len();
+        let inner = inner.trim_end_matches(marker);
+        let suffix = line.len() - prefix - inner.len();
+        if prefix >= 2 && suffix >= 2 {
+            return non_empty(inner.trim()).map(|title| (level, title));
+        }
+    }
+
+    None
+}
+
+fn parse_list_item(line: &str) -> Option<&str> {
+    ["- ", "* ", "• "]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .and_then(|item| non_empty(item.trim()))
+}
+
+fn non_empty(s: &str) -> Option<&str> {
+    (!s.is_empty()).then_some(s)
+}
+
+/// Lowercase alphanumeric words separated by `-`, e.g. `Leveling (Act 1-5)` becomes `leveling-act-1-5`.
+fn slug(title: &str) -> String {
+    let slug = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "section".to_owned()
+    } else {
+        slug
+    }
+}
+
+fn to_spans<'a>(line: &[(Color<'a>, &'a str)], references: &References<'a>) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    for &(color, text) in line {
+        for inline in inlines(text, references) {
+            spans.push(Span { color, inline });
+        }
+    }
+    spans
+}
+
+/// Splits text into links, references and the text in between.
+fn inlines<'a>(mut text: &'a str, references: &References<'a>) -> Vec<Inline<'a>> {
+    let mut result = Vec::new();
+
+    while !text.is_empty() {
+        let link = find_link(text).map(|(start, url)| {
+            let link = Link {
+                url,
+                kind: LinkKind::Other,
+            };
+            let link = Link {
+                kind: LinkKind::from_domain(link.domain()),
+                ..link
+            };
+            (start, url.len(), Inline::Link(link))
+        });
+        // References are only searched in front of the next link, never within links.
+        let search = link.map_or(text, |(start, _, _)| &text[..start]);
+        let next = references.find(search).or(link);
+
+        let Some((start, len, inline)) = next else {
+            result.push(Inline::Text(text));
+            break;
+        };
+
+        if start > 0 {
+            result.push(Inline::Text(&text[..start]));
+        }
+        result.push(inline);
+        text = &text[start + len..];
+    }
+
+    result
+}
+
+fn find_link(text: &str) -> Option<(usize, &str)> {
+    let start = ["https://", "http://"]
+        .iter()
+        .filter_map(|protocol| text.find(protocol))
+        .min()?;
+
+    let rest = &text[start..];
+    let end = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '}' | '"'))
+        .unwrap_or(rest.len());
+    let url = rest[..end].trim_end_matches(['.', ',', ':', ';', '!', '?']);
+
+    Some((start, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use Color::*;
+
+    use super::*;
+
+    #[test]
+    fn test_no_element() {
+        let x = ColoredText::new("Test").collect::<Vec<_>>();
+        assert_eq!(x, vec![(None, "Test")]);
+    }
+
+    #[test]
+    fn test_one_hex_element() {
+        let x = ColoredText::new("^x001122Test").collect::<Vec<_>>();
+        assert_eq!(x, vec![(Hex("001122"), "Test")]);
+    }
+
+    #[test]
+    fn test_one_named_element() {
+        let x = ColoredText::new("^2Test").collect::<Vec<_>>();
+        assert_eq!(x, vec![(Named(2), "Test")]);
+    }
+
+    #[test]
+    fn test_invalid_hex() {
+        let x = ColoredText::new("^x00112ZTest").collect::<Vec<_>>();
+        assert_eq!(x, vec![(None, "^x00112ZTest")]);
+    }
+
+    #[test]
+    fn test_too_short() {
+        let x = ColoredText::new("Test^").collect::<Vec<_>>();
+        assert_eq!(x, vec![(None, "Test^")]);
+    }
+
+    #[test]
+    fn test_too_short_hex() {
+        let x = ColoredText::new("Test^x00112").collect::<Vec<_>>();
+        assert_eq!(x, vec![(None, "Test^x00112")]);
+    }
+
+    #[test]
+    fn test_multiple() {
+        let x = ColoredText::new("First^x001122Hex^3Num").collect::<Vec<_>>();
+        assert_eq!(
+            x,
+            vec![(None, "First"), (Hex("001122"), "Hex"), (Named(3), "Num")]
+        );
+    }
+
+    #[test]
+    fn test_multiple_empty() {
+        let x = ColoredText::new("^1^2^3^4^5").collect::<Vec<_>>();
+        assert_eq!(x, vec![]);
+    }
+
+    #[test]
+    fn test_invalid_interleaved() {
+        let x = ColoredText::new("^1A^B^2C").collect::<Vec<_>>();
+        assert_eq!(x, vec![(Named(1), "A^B"), (Named(2), "C")]);
+    }
+
+    #[test]
+    fn test_start_end_regression() {
+        let x = ColoredText::new("^^^^^ Test ^^^^^").collect::<Vec<_>>();
+        assert_eq!(x, vec![(None, "^^^^^ Test ^^^^^")]);
+    }
+
+    fn colored<'a>(color: Color<'a>, text: &'a str) -> Span<'a> {
+        Span {
+            color,
+            inline: Inline::Text(text),
+        }
+    }
+
+    #[test]
+    fn structure() {
+        let notes = Notes::parse(
+            "== Leveling ==\n- ^1Act^7 1\n# Mapping\n\n^0Plain\r\n#1 not a heading",
+            &References::default(),
+        );
+
+        let headings = notes
+            .headings()
+            .map(|h| (h.level, h.anchor.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(headings, vec![(1, "leveling"), (1, "mapping")]);
+
+        assert_eq!(
+            notes.blocks[1],
+            Block::ListItem(vec![colored(Named(1), "Act"), colored(Named(7), " 1"),])
+        );
+        assert_eq!(notes.blocks[3], Block::Empty);
+        assert_eq!(
+            notes.blocks[4],
+            Block::Line(vec![colored(Named(0), "Plain")])
+        );
+        assert_eq!(
+            notes.blocks[5],
+            Block::Line(vec![colored(Named(0), "#1 not a heading")])
+        );
+    }
+
+    #[test]
+    fn colors_carry_over_lines() {
+        let notes = Notes::parse("^2First\nSecond", &References::default());
+        let Block::Line(spans) = &notes.blocks[1] else {
+            panic!("expected a line");
+        };
+        assert_eq!(Named(2), spans[0].color);
+    }
+
+    #[test]
+    fn duplicate_anchors() {
+        let notes = Notes::parse("# Bossing\n-- Bossing --", &References::default());
+        let anchors = notes
+            .headings()
+            .map(|h| h.anchor.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(anchors, vec!["bossing", "bossing-2"]);
+    }
+
+    #[test]
+    fn links() {
+        let notes = Notes::parse(
+            "Guide (https://www.youtube.com/watch?v=abc), https://poe.ninja/builds.",
+            &References::default(),
+        );
+        let Block::Line(spans) = &notes.blocks[0] else {
+            panic!("expected a line");
+        };
+
+        let links = spans
+            .iter()
+            .filter_map(|span| match span.inline {
+                Inline::Link(link) => Some((link.kind, link.url, link.domain())),
+                _ => Option::None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                (
+                    LinkKind::YouTube,
+                    "https://www.youtube.com/watch?v=abc",
+                    "youtube.com"
+                ),
+                (LinkKind::PoeNinja, "https://poe.ninja/builds", "poe.ninja"),
+            ]
+        );
+    }
+
+    #[test]
+    fn references() {
+        let mut references = References::default();
+        let gem = GemReference {
+            name: "Vortex",
+            gem_id: Some("Metadata/Items/Gems/SkillGemFrostBoltNova"),
+            level: 21,
+            quality: 20,
+        };
+        references.add_gem(gem);
+        references.add_gem(GemReference {
+            name: "Vortex Support",
+            ..gem
+        });
+        references.add_item(ItemReference {
+            name: "Shavronne's Wrappings",
+            item: "Rarity: UNIQUE",
+        });
+
+        let notes = Notes::parse(
+            "Use Vortex with Shavronne's Wrappings, Vortexes are not gems.",
+            &references,
+        );
+        let Block::Line(spans) = &notes.blocks[0] else {
+            panic!("expected a line");
+        };
+        let inlines = spans.iter().map(|span| span.inline).collect::<Vec<_>>();
+        assert_eq!(
+            inlines,
+            vec![
+                Inline::Text("Use "),
+                Inline::Gem(gem),
+                Inline::Text(" with "),
+                Inline::Item(ItemReference {
+                    name: "Shavronne's Wrappings",
+                    item: "Rarity: UNIQUE"
+                }),
+                Inline::Text(", Vortexes are not gems."),
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/// Buffs of party members and enemy modifiers configured in the party tab of Path of Building.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Party<'a> {
+    /// Names of auras provided by party members, e.g. `Haste`.
+    pub auras: Vec<&'a str>,
+    /// Names of curses applied by party members, e.g. `Despair`.
+    pub curses: Vec<&'a str>,
+    /// Names of links from party members, e.g. `Flame Link`.
+    pub links: Vec<&'a str>,
+    /// Conditions party members inflict on the enemy, e.g. `Shocked`.
+    pub enemy_conditions: Vec<&'a str>,
+}
+
+impl<'a> Party<'a> {
+    pub(crate) fn parse(
+        aura: &'a str,
+        curse: &'a str,
+        link: &'a str,
+        enemy_conditions: &'a str,
+    ) -> Self {
+        Self {
+            auras: buff_names(aura),
+            curses: buff_names(curse),
+            links: buff_names(link),
+            enemy_conditions: condition_names(enemy_conditions),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.auras.is_empty()
+            && self.curses.is_empty()
+            && self.links.is_empty()
+            && self.enemy_conditions.is_empty()
+    }
+}
+
+/// Names of the buffs in a party tab buffer.
+///
+/// Buffers list each buff by name followed by its serialized mods (`Name|TYPE|value|...`)
+/// and numeric effect multipliers, buffs are separated by `---` lines.
+fn buff_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        let is_name = !line.is_empty()
+            && !line.starts_with("---")
+            && !line.contains(['|', '='])
+            && line.parse::<f32>().is_err();
+
+        if is_name && !names.contains(&line) {
+            names.push(line);
+        }
+    }
+
+    names
+}
+
+/// Names of the enemy conditions in a party tab buffer, e.g. `Condition:Shocked|FLAG|true`.
+fn condition_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        let Some(name) = line
+            .split('|')
+            .next()
+            .and_then(|name| name.strip_prefix("Condition:"))
+        else {
+            continue;
+        };
+
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffs() {
+        let aura = "--- Aura ---\nHaste\n1.5\nMovementSpeed|INC|16|Haste\n---\nWrath\nLightningMin|BASE|55|Wrath\n";
+        assert_eq!(vec!["Haste", "Wrath"], buff_names(aura));
+        assert!(buff_names("").is_empty());
+    }
+
+    #[test]
+    fn conditions() {
+        let conditions =
+            "Condition:Shocked|FLAG|true\nCondition:Blinded|FLAG|true\nCondition:Shocked|FLAG|true";
+        assert_eq!(vec!["Shocked", "Blinded"], condition_names(conditions));
+    }
+}
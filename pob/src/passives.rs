@@ -1,28 +1,13 @@
-#[derive(Copy, Clone, Debug)]
-#[non_exhaustive]
-pub enum Keystone {
-    ChaosInoculation,
-    EldritchBattery,
-    ElementalOverload,
-    MindOverMatter,
-}
+pub use poe_tree::Keystone;
 
-impl Keystone {
-    pub(crate) fn node(&self) -> u32 {
-        match self {
-            Self::ChaosInoculation => 11455,
-            Self::EldritchBattery => 56075,
-            Self::ElementalOverload => 22088,
-            Self::MindOverMatter => 34098,
-        }
-    }
+/// Text of the item modifier which grants the keystone, e.g. `Mind Over Matter`.
+pub(crate) fn keystone_item_stat(keystone: Keystone) -> &'static str {
+    keystone.name()
+}
 
-    pub(crate) fn as_item_stat(&self) -> Option<&'static str> {
-        // TODO: do we need translations here?
-        match self {
-            Self::EldritchBattery => Some("Eldritch Battery"),
-            Self::MindOverMatter => Some("Mind Over Matter"),
-            _ => None,
-        }
-    }
+/// Version of the passive tree a spec was made for, falls back to the latest tree.
+pub(crate) fn tree_version(spec: &crate::TreeSpec) -> poe_tree::Version {
+    spec.version
+        .and_then(|version| version.parse().ok())
+        .unwrap_or_else(poe_tree::Version::latest)
 }
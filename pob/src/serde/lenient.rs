@@ -0,0 +1,209 @@
+//! Section by section parsing of builds which fail to parse as a whole.
+//!
+//! Each top level section is deserialized on its own. When a section fails
+//! the offending attribute or element is removed from a copy of the section
+//! and parsing is retried, if the error can not be located the whole section
+//! falls back to its default. Every removal is recorded as a [`ParseWarning`].
+use serde::de::DeserializeOwned;
+use serde_path_to_error::{Path, Segment};
+
+use crate::serde::xml::{Element, Node};
+use crate::ParseWarning;
+
+/// Upper bound of removals per section, a build with more broken
+/// parts than this is not worth salvaging piece by piece.
+const MAX_ATTEMPTS: usize = 32;
+/// Upper bound of bytes parsed per section over all attempts. Every attempt parses
+/// the whole section again, large sections get fewer attempts.
+const MAX_PARSED_BYTES: usize = 2 * 1024 * 1024;
+
+pub(crate) struct Lenient<'a> {
+    root: &'a Element,
+    warnings: Vec<ParseWarning>,
+}
+
+impl<'a> Lenient<'a> {
+    pub fn new(root: &'a Element) -> Self {
+        Self {
+            root,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn into_warnings(self) -> Vec<ParseWarning> {
+        self.warnings
+    }
+
+    /// Parses a section which is required in a valid build, a missing section is reported.
+    pub fn required<T: DeserializeOwned + Default>(&mut self, name: &str) -> T {
+        if self.root.child(name).is_none() {
+            self.warn(name.to_owned(), "missing section, using defaults");
+        }
+        self.optional(name)
+    }
+
+    /// Parses a section which may be missing from a build.
+    pub fn optional<T: DeserializeOwned + Default>(&mut self, name: &str) -> T {
+        let Some(element) = self.root.child(name) else {
+            return T::default();
+        };
+        let mut element = element.clone();
+
+        let mut parsed = 0;
+        for attempt in 0..MAX_ATTEMPTS {
+            let xml = match element.to_xml() {
+                Ok(xml) => xml,
+                Err(err) => {
+                    self.warn(name.to_owned(), format!("{err}, using defaults"));
+                    return T::default();
+                }
+            };
+
+            // The first attempt always runs, the section is parsed at least once.
+            parsed += xml.len();
+            if attempt > 0 && parsed > MAX_PARSED_BYTES {
+                break;
+            }
+
+            let mut xd = quick_xml::de::Deserializer::from_reader(xml.as_bytes());
+            let err = match serde_path_to_error::deserialize(&mut xd) {
+                Ok(value) => return value,
+                Err(err) => err,
+            };
+
+            let path = match err.path().to_string().as_str() {
+                "." => name.to_owned(),
+                path => format!("{name}.{path}"),
+            };
+            let message = err.inner().to_string();
+
+            if !remove_path(&mut element, err.path()) {
+                self.warn(path, format!("{message}, using defaults for {name}"));
+                return T::default();
+            }
+            self.warn(path, message);
+        }
+
+        self.warn(name.to_owned(), "too many errors, using defaults");
+        T::default()
+    }
+
+    fn warn(&mut self, path: String, message: impl Into<String>) {
+        self.warnings.push(ParseWarning {
+            path,
+            message: message.into(),
+        });
+    }
+}
+
+/// Removes the attribute or element `path` points to.
+///
+/// Returns `false` if nothing could be removed, e.g. the path points
+/// to the section itself or does not exist in the document.
+fn remove_path(element: &mut Element, path: &Path) -> bool {
+    // Positions in `children` of the elements along the path.
+    let mut positions = Vec::new();
+    let mut current = &*element;
+    let mut pending: Option<&str> = None;
+
+    for segment in path {
+        match segment {
+            Segment::Map { key } => {
+                if let Some(name) = pending.replace(key) {
+                    let Some(position) = unique_child(current, name) else {
+                        return false;
+                    };
+                    current = element_at(current, position);
+                    positions.push(position);
+                }
+            }
+            Segment::Seq { index } => {
+                let Some(name) = pending.take() else {
+                    return false;
+                };
+                let Some(position) = nth_child(current, name, *index) else {
+                    return false;
+                };
+                current = element_at(current, position);
+                positions.push(position);
+            }
+            // Enum variants are represented by the element name, which is already selected.
+            Segment::Enum { .. } => pending = None,
+            Segment::Unknown => return false,
+        }
+    }
+
+    let mut target = &mut *element;
+    let Some((&last, parents)) = positions.split_last() else {
+        return match pending {
+            Some(name) => remove_attribute_or_child(target, name),
+            None => false,
+        };
+    };
+    for &position in parents {
+        target = element_at_mut(target, position);
+    }
+
+    match pending {
+        Some(name) if remove_attribute_or_child(element_at_mut(target, last), name) => true,
+        // Could not pin the error on anything below, drop the whole element.
+        _ => {
+            target.children.remove(last);
+            true
+        }
+    }
+}
+
+fn remove_attribute_or_child(element: &mut Element, name: &str) -> bool {
+    if element.attribute(name).is_some() {
+        element.remove_attribute(name);
+        return true;
+    }
+    match unique_child(element, name) {
+        Some(position) => {
+            element.children.remove(position);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Position of the only child element with that name, multiple
+/// children would be a sequence which must be resolved by index.
+fn unique_child(element: &Element, name: &str) -> Option<usize> {
+    let mut positions = child_positions(element, name);
+    match (positions.next(), positions.next()) {
+        (Some(position), None) => Some(position),
+        _ => None,
+    }
+}
+
+fn nth_child(element: &Element, name: &str, index: usize) -> Option<usize> {
+    child_positions(element, name).nth(index)
+}
+
+fn child_positions<'a>(element: &'a Element, name: &'a str) -> impl Iterator<Item = usize> + 'a {
+    element
+        .children
+        .iter()
+        .enumerate()
+        .filter_map(move |(position, node)| match node {
+            // `$value` collects all child elements regardless of their name.
+            Node::Element(child) if name == "$value" || child.name == name => Some(position),
+            _ => None,
+        })
+}
+
+fn element_at(element: &Element, position: usize) -> &Element {
+    match &element.children[position] {
+        Node::Element(element) => element,
+        _ => unreachable!("position points to an element"),
+    }
+}
+
+fn element_at_mut(element: &mut Element, position: usize) -> &mut Element {
+    match &mut element.children[position] {
+        Node::Element(element) => element,
+        _ => unreachable!("position points to an element"),
+    }
+}
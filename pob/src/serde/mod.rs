@@ -1,5 +1,8 @@
+mod lenient;
 mod model;
 mod pob;
 mod utils;
+mod xml;
 
 pub use self::pob::SerdePathOfBuilding;
+pub(crate) use self::xml::{Element, Node};
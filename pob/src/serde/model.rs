@@ -3,6 +3,7 @@ use std::{borrow::Cow, collections::HashMap, str::FromStr};
 use serde::{de, Deserialize, Deserializer};
 use shared::{Ascendancy, Bandit, Class, PantheonMajorGod, PantheonMinorGod};
 
+use crate::gems::{AlternateQuality, GemVariant};
 use crate::serde::utils;
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +25,18 @@ pub(crate) struct PathOfBuilding {
 
     #[serde(default, rename = "Config")]
     pub config: Config,
+
+    #[serde(default, rename = "Party")]
+    pub party: Party,
+
+    #[serde(default, rename = "Calcs")]
+    pub calcs: Calcs,
+
+    #[serde(default, rename = "Import")]
+    pub import: Import,
+
+    #[serde(default, rename = "TradeSearchWeights")]
+    pub trade_search_weights: TradeSearchWeights,
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,7 +46,8 @@ pub(crate) struct Build {
     pub class_name: Class,
     #[serde(default, deserialize_with = "deserialize_ascendancy")]
     pub ascend_class_name: Option<Ascendancy>,
-    #[serde(rename = "$value")]
+    // Builds without calculated stats, e.g. created from a tree URL, have no children.
+    #[serde(default, rename = "$value")]
     pub stats: Vec<StatType>,
     pub main_socket_group: u8,
     #[serde(default, deserialize_with = "deserialize_str_none")]
@@ -58,6 +72,21 @@ where
     }
 }
 
+impl Default for Build {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            class_name: Class::Scion,
+            ascend_class_name: None,
+            stats: Vec::new(),
+            main_socket_group: 1,
+            bandit: None,
+            pantheon_major_god: None,
+            pantheon_minor_god: None,
+        }
+    }
+}
+
 fn deserialize_str_none<'de, D, T: FromStr>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
@@ -101,7 +130,7 @@ pub(crate) struct BuildStat {
     pub value: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Default, Debug, Deserialize)]
 pub(crate) struct Skills {
     #[serde(default, rename = "activeSkillSet")]
     pub active_skill_set: Option<u16>,
@@ -146,6 +175,9 @@ pub(crate) struct Skill {
     pub label: Option<String>,
     #[serde(default)]
     pub slot: Option<String>,
+    /// Set for skills granted by items, e.g. `Item:30:Saqawal's Flock, Silken Hood`.
+    #[serde(default)]
+    pub source: Option<String>,
     #[serde(default, rename = "Gem")]
     pub gems: Vec<Gem>,
 }
@@ -154,6 +186,13 @@ impl Skill {
     pub fn support_gems(&self) -> impl Iterator<Item = &Gem> {
         self.gems.iter().filter(|gem| gem.is_support())
     }
+
+    /// Name of the item which grants the skill.
+    pub fn granted_by(&self) -> Option<&str> {
+        let source = self.source.as_deref()?.strip_prefix("Item:")?;
+        let (_id, name) = source.split_once(':')?;
+        Some(name)
+    }
 }
 
 #[derive(Debug)]
@@ -165,6 +204,9 @@ pub(crate) struct Gem {
     pub enabled: bool,
     pub level: u8,
     pub quality: u8,
+    /// Minion selected for minion skills, stale values are kept on non-minion gems.
+    pub skill_minion: Option<String>,
+    pub skill_minion_skill: Option<u8>,
 }
 
 impl Gem {
@@ -183,12 +225,23 @@ impl Gem {
         !self.is_support()
     }
 
+    pub fn variant(&self) -> GemVariant {
+        GemVariant::from_ids(self.gem_id.as_deref(), self.skill_id.as_deref(), &self.name)
+    }
+
     pub fn is_vaal(&self) -> bool {
-        self.name.starts_with("Vaal ")
+        self.variant() == GemVariant::Vaal
     }
 
-    pub fn non_vaal_name(&self) -> &str {
-        self.name.strip_prefix("Vaal ").unwrap_or(&self.name)
+    /// Name of the gem this gem is a variant of, e.g. the skill paired with a vaal skill.
+    pub fn base_name(&self) -> &str {
+        crate::gems::base_gem_name(&self.name, self.gem_id.as_deref(), self.variant())
+    }
+
+    pub fn alternate_quality(&self) -> Option<AlternateQuality> {
+        self.quality_id
+            .as_deref()
+            .and_then(AlternateQuality::from_quality_id)
     }
 }
 
@@ -211,6 +264,10 @@ impl<'de> Deserialize<'de> for Gem {
             level: u8,
             #[serde(default, deserialize_with = "utils::lenient")]
             quality: u8,
+            #[serde(default)]
+            skill_minion: Option<String>,
+            #[serde(default, deserialize_with = "utils::u8_or_nil")]
+            skill_minion_skill: Option<u8>,
         }
 
         let inner = Inner::deserialize(deserializer)?;
@@ -240,11 +297,13 @@ impl<'de> Deserialize<'de> for Gem {
             enabled: inner.enabled,
             level: inner.level,
             quality: inner.quality,
+            skill_minion: inner.skill_minion,
+            skill_minion_skill: inner.skill_minion_skill,
         })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Default, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct Tree {
     pub active_spec: u8,
@@ -402,6 +461,12 @@ pub(crate) struct Slot {
 pub(crate) struct ItemSet {
     pub id: u16,
     pub title: Option<String>,
+    #[serde(
+        default,
+        rename = "useSecondWeaponSet",
+        deserialize_with = "utils::lenient"
+    )]
+    pub use_second_weapon_set: bool,
     #[serde(default, rename = "$value")]
     pub gear: Gear,
 }
@@ -426,6 +491,8 @@ pub(crate) struct Gear {
     pub flask4: Option<u16>,
     pub flask5: Option<u16>,
     pub sockets: Vec<u16>,
+    /// All slots with an item, in document order.
+    pub slots: Vec<Slot>,
 }
 
 impl<'de> de::Deserialize<'de> for Gear {
@@ -485,6 +552,7 @@ impl<'de> de::Deserialize<'de> for Gear {
                         "Flask 5" => result.flask5 = Some(slot.item_id),
                         _ => result.sockets.push(slot.item_id),
                     }
+                    result.slots.push(slot);
                 }
 
                 Ok(result)
@@ -509,3 +577,99 @@ pub(crate) struct Input {
     pub boolean: Option<bool>,
     pub number: Option<f32>,
 }
+
+impl Input {
+    pub fn string(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            string: Some(value.to_owned()),
+            boolean: None,
+            number: None,
+        }
+    }
+
+    pub fn boolean(name: &str, value: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            string: None,
+            boolean: Some(value),
+            number: None,
+        }
+    }
+
+    pub fn number(name: &str, value: f32) -> Self {
+        Self {
+            name: name.to_owned(),
+            string: None,
+            boolean: None,
+            number: Some(value),
+        }
+    }
+}
+
+/// Buffs and enemy modifiers from party members, Path of Building stores the text
+/// of each party tab buffer in an `ImportedBuffs` element named after the buffer.
+#[derive(Default, Debug, Deserialize)]
+pub(crate) struct Party {
+    #[serde(default, rename = "$value")]
+    pub elements: Vec<PartyElement>,
+}
+
+impl Party {
+    /// Text of a buffer, e.g. `Aura` or `Link Skills`, empty if there is none.
+    pub(crate) fn buffs(&self, name: &str) -> &str {
+        self.elements
+            .iter()
+            .find_map(|element| match element {
+                PartyElement::ImportedBuffs(buffs) if buffs.name == name => {
+                    Some(buffs.text.as_str())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) enum PartyElement {
+    ImportedBuffs(ImportedBuffs),
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ImportedBuffs {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, rename = "$value")]
+    pub text: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub(crate) struct Calcs {
+    #[serde(default, rename = "Input")]
+    pub input: Vec<Input>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Import {
+    pub last_realm: Option<String>,
+    pub last_league: Option<String>,
+    pub last_account_hash: Option<String>,
+    pub last_character_hash: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub(crate) struct TradeSearchWeights {
+    #[serde(default, rename = "Stat")]
+    pub stats: Vec<TradeSearchWeight>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TradeSearchWeight {
+    pub stat: String,
+    #[serde(default, deserialize_with = "utils::lenient")]
+    pub weight_mult: f32,
+}
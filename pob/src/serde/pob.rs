@@ -1,32 +1,39 @@
 use shared::{Ascendancy, Bandit, Class, PantheonMajorGod, PantheonMinorGod};
 
+use crate::serde::lenient::Lenient;
 use crate::serde::model::*;
-use crate::{Config, ConfigValue, Error, Keystone, Result, Stat};
+use crate::serde::xml::{Document, Element, Node};
+use crate::{
+    CharacterItems, CharacterPassives, Config, ConfigValue, Error, Keystone, Minion, MinionStats,
+    ParseWarning, Result, Stat, StatValue, TreeUrl,
+};
 
 #[derive(Debug)]
 pub struct SerdePathOfBuilding {
     pob: PathOfBuilding,
+    /// The full document, used to write the build back out without losing
+    /// anything the typed model does not know about.
+    ///
+    /// Only kept by the `*_editable` constructors, see [`Self::from_xml_editable`].
+    xml: Option<Document>,
     // TODO: quick access list (indices) for active items (?)
 }
 
 impl SerdePathOfBuilding {
     pub fn from_xml(s: &str) -> Result<Self> {
-        let mut xd = quick_xml::de::Deserializer::from_reader(s.as_bytes());
-
-        #[cfg(any(feature = "better-errors", test))]
-        let pob = match serde_path_to_error::deserialize(&mut xd) {
-            Ok(pob) => pob,
-            Err(err) => {
-                let path = err.path().to_string();
-                return Err(Error::ParseXml(path, err.into_inner()));
-            }
-        };
-
-        #[cfg(not(any(feature = "better-errors", test)))]
-        let pob = serde::Deserialize::deserialize(&mut xd)
-            .map_err(|e| Error::ParseXml("Unknown".to_owned(), e))?;
+        Ok(Self {
+            pob: deserialize(s)?,
+            xml: None,
+        })
+    }
 
-        Ok(Self { pob })
+    /// Like [`Self::from_xml`] but also keeps the full document,
+    /// which is required to edit the build and to write it back out.
+    pub fn from_xml_editable(s: &str) -> Result<Self> {
+        Ok(Self {
+            pob: deserialize(s)?,
+            xml: Some(Document::parse(s)?),
+        })
     }
 
     pub fn from_export(data: &str) -> Result<Self> {
@@ -34,6 +41,183 @@ impl SerdePathOfBuilding {
         Self::from_xml(&data)
     }
 
+    /// Like [`Self::from_export`] but editable, see [`Self::from_xml_editable`].
+    pub fn from_export_editable(data: &str) -> Result<Self> {
+        let data = crate::utils::decompress(data)?;
+        Self::from_xml_editable(&data)
+    }
+
+    /// Like [`Self::from_xml`] but skips or defaults the parts of the build which fail to parse.
+    ///
+    /// Only fails if the input is not valid XML, everything else
+    /// is reported as a [`ParseWarning`].
+    pub fn from_xml_lenient(s: &str) -> Result<(Self, Vec<ParseWarning>)> {
+        lenient(s, false)
+    }
+
+    /// Like [`Self::from_xml_lenient`] but editable, see [`Self::from_xml_editable`].
+    pub fn from_xml_lenient_editable(s: &str) -> Result<(Self, Vec<ParseWarning>)> {
+        lenient(s, true)
+    }
+
+    /// A build with only the passive tree of an URL of the official website, see [`TreeUrl`].
+    pub fn from_tree_url(url: &str) -> Result<Self> {
+        let tree = TreeUrl::parse(url)?;
+
+        let attributes = tree
+            .spec_attributes()
+            .into_iter()
+            .map(|(name, value)| format!(r#"{name}="{value}""#))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let xml = format!(
+            r#"<PathOfBuilding>
+    <Build level="1" className="{class}" ascendClassName="{ascendancy}" mainSocketGroup="1"/>
+    <Skills activeSkillSet="1">
+        <SkillSet id="1"/>
+    </Skills>
+    <Tree activeSpec="1">
+        <Spec {attributes}>
+            <URL>{url}</URL>
+        </Spec>
+    </Tree>
+</PathOfBuilding>"#,
+            class = tree.class_name(),
+            ascendancy = tree.ascendancy_name().unwrap_or("None"),
+            url = tree.to_url()?,
+        );
+
+        Self::from_xml_editable(&xml)
+    }
+
+    /// A build of a character from the official character-window API, see [`CharacterItems`].
+    pub fn from_character(items: &CharacterItems, passives: &CharacterPassives) -> Result<Self> {
+        Self::from_xml_editable(&crate::character::to_xml(items, passives)?)
+    }
+
+    pub fn from_export_lenient(data: &str) -> Result<(Self, Vec<ParseWarning>)> {
+        let data = crate::utils::decompress(data)?;
+        Self::from_xml_lenient(&data)
+    }
+
+    /// Like [`Self::from_export_lenient`] but editable, see [`Self::from_xml_editable`].
+    pub fn from_export_lenient_editable(data: &str) -> Result<(Self, Vec<ParseWarning>)> {
+        let data = crate::utils::decompress(data)?;
+        Self::from_xml_lenient_editable(&data)
+    }
+
+    /// Serializes the build back into PoB compatible XML.
+    ///
+    /// Everything that was parsed is preserved, including elements and
+    /// attributes which are not part of the typed model.
+    ///
+    /// Fails with [`Error::NotEditable`] if the build was not parsed as editable.
+    pub fn to_xml(&self) -> Result<String> {
+        self.xml.as_ref().ok_or(Error::NotEditable)?.to_xml()
+    }
+
+    /// Serializes the build into an export code which can be imported into PoB.
+    pub fn to_export(&self) -> Result<String> {
+        crate::utils::compress(&self.to_xml()?)
+    }
+
+    /// The setters below fail with [`Error::NotEditable`] if the build was not parsed as editable.
+    pub fn set_level(&mut self, level: u8) -> Result<()> {
+        self.xml_root()?
+            .child_or_insert("Build")
+            .set_attribute("level", &level.to_string());
+        self.pob.build.level = level;
+        Ok(())
+    }
+
+    pub fn set_notes(&mut self, notes: impl Into<String>) -> Result<()> {
+        let notes = notes.into();
+        self.xml_root()?.child_or_insert("Notes").set_text(&notes);
+        self.pob.notes = notes;
+        Ok(())
+    }
+
+    /// Replaces the raw content of an existing item, returns `false` if there is no item with that id.
+    pub fn set_item(&mut self, id: u16, content: impl Into<String>) -> Result<bool> {
+        let xml = self.xml.as_mut().ok_or(Error::NotEditable)?;
+        let Some(item) = self.pob.items.items.get_mut(&id) else {
+            return Ok(false);
+        };
+        item.content.content = content.into();
+
+        let id = id.to_string();
+        let element = xml
+            .root_mut()
+            .and_then(|root| root.child_mut("Items"))
+            .and_then(|items| {
+                items
+                    .elements_mut()
+                    .find(|e| e.name == "Item" && e.attribute("id").as_deref() == Some(&id))
+            });
+        if let Some(element) = element {
+            element.set_text(&item.content.content);
+        }
+
+        Ok(true)
+    }
+
+    /// Sets or, with [`ConfigValue::None`], removes a config option.
+    pub fn set_config(&mut self, config: Config, value: ConfigValue) -> Result<()> {
+        let name: &'static str = config.into();
+        // Fail before touching the typed model.
+        self.xml_root()?;
+
+        let inputs = &mut self.pob.config.input;
+        inputs.retain(|input| input.name != name);
+        let attribute = match value {
+            ConfigValue::String(value) => {
+                inputs.push(Input::string(name, value));
+                Some(("string", value.to_owned()))
+            }
+            ConfigValue::Number(value) => {
+                inputs.push(Input::number(name, value));
+                Some(("number", value.to_string()))
+            }
+            ConfigValue::Bool(value) => {
+                inputs.push(Input::boolean(name, value));
+                Some(("boolean", value.to_string()))
+            }
+            ConfigValue::None => None,
+        };
+
+        let xml = self.xml_root()?.child_or_insert("Config");
+        let existing = xml
+            .elements_mut()
+            .find(|e| e.name == "Input" && e.attribute("name").as_deref() == Some(name));
+        match (existing, attribute) {
+            (Some(input), Some((key, value))) => {
+                for attr in ["string", "number", "boolean"] {
+                    input.remove_attribute(attr);
+                }
+                input.set_attribute(key, &value);
+            }
+            (None, Some((key, value))) => {
+                let mut input = Element::new("Input");
+                input.set_attribute("name", name);
+                input.set_attribute(key, &value);
+                xml.children.push(Node::Element(input));
+            }
+            (_, None) => xml.children.retain(|node| {
+                !matches!(node, Node::Element(e)
+                    if e.name == "Input" && e.attribute("name").as_deref() == Some(name))
+            }),
+        }
+
+        Ok(())
+    }
+
+    fn xml_root(&mut self) -> Result<&mut Element> {
+        let xml = self.xml.as_mut().ok_or(Error::NotEditable)?;
+        // Lenient parses accept documents without a root element.
+        Ok(xml.root_or_insert("PathOfBuilding"))
+    }
+
     fn main_skill(&self) -> Option<&Skill> {
         let mut index = self.pob.build.main_socket_group as usize;
         if index < 1 {
@@ -53,41 +237,6 @@ impl SerdePathOfBuilding {
         }
         self.pob.skills.active_skills().get(index - 1)
     }
-
-    fn has_keystone_on_gear(&self, keystone: Keystone) -> bool {
-        let keystone = match keystone.as_item_stat() {
-            Some(keystone) => keystone,
-            None => return false,
-        };
-
-        let Some(active_item_set) = self.pob.items.active_item_set else {
-            return false;
-        };
-        let active_item_set = self
-            .pob
-            .items
-            .item_sets
-            .iter()
-            .find(|set| set.id == active_item_set);
-        let Some(active_item_set) = active_item_set else {
-            return false;
-        };
-
-        // Item pieces that can potentially have a keystone on them
-        [
-            active_item_set.gear.body_armour,
-            active_item_set.gear.helmet,
-            active_item_set.gear.weapon1,
-            active_item_set.gear.weapon2,
-            active_item_set.gear.gloves,
-            active_item_set.gear.boots,
-            active_item_set.gear.belt,
-        ]
-        .into_iter()
-        .filter_map(|item| item.and_then(|id| self.pob.items.items.get(&id)))
-        .flat_map(|item| item.content.content.lines())
-        .any(|stat| stat == keystone)
-    }
 }
 
 impl crate::PathOfBuilding for SerdePathOfBuilding {
@@ -139,26 +288,51 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
             .map(|stat| stat.value.as_str())
     }
 
+    fn stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_> {
+        let stats = self.pob.build.stats.iter().filter_map(|stat| stat.player());
+        Box::new(stats.filter_map(|stat| typed_stat(&stat.name, &stat.value)))
+    }
+
+    fn minion_stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_> {
+        let stats = self.pob.build.stats.iter().filter_map(|stat| stat.minion());
+        Box::new(stats.filter_map(|stat| typed_stat(&stat.name, &stat.value)))
+    }
+
     fn config(&self, config: Config) -> ConfigValue {
         self.pob
             .config
             .input
             .iter()
             .find(|x| config == x.name)
-            .map(|stat| {
-                if let Some(ref value) = stat.string {
-                    ConfigValue::String(value)
-                } else if let Some(value) = stat.number {
-                    ConfigValue::Number(value)
-                } else if let Some(value) = stat.boolean {
-                    ConfigValue::Bool(value)
-                } else {
-                    ConfigValue::None
-                }
-            })
+            .map(config_value)
             .unwrap_or(ConfigValue::None)
     }
 
+    fn configs(&self) -> Box<dyn Iterator<Item = (Config, ConfigValue<'_>)> + '_> {
+        let configs = self.pob.config.input.iter().filter_map(|input| {
+            let config = Config::from_name(&input.name)?;
+            match config.value_type().coerce(config_value(input)) {
+                ConfigValue::None => None,
+                value => Some((config, value)),
+            }
+        });
+        Box::new(configs)
+    }
+
+    fn unknown_configs(&self) -> Box<dyn Iterator<Item = (&str, ConfigValue<'_>)> + '_> {
+        let configs = self
+            .pob
+            .config
+            .input
+            .iter()
+            .filter(|input| Config::from_name(&input.name).is_none())
+            .filter_map(|input| match config_value(input) {
+                ConfigValue::None => None,
+                value => Some((input.name.as_str(), value)),
+            });
+        Box::new(configs)
+    }
+
     fn main_skill_name(&self) -> Option<&str> {
         let skill = self.main_skill()?;
 
@@ -166,11 +340,34 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
         active_skill_names(&skill.gems).nth(index)
     }
 
+    fn minion(&self) -> Option<Minion<'_>> {
+        let stats = MinionStats::from_stats(self.minion_stats());
+        if stats.is_empty() {
+            return None;
+        }
+
+        let gem = self.main_skill().and_then(|skill| {
+            let index = skill.main_active_skill.unwrap_or(0).checked_sub(1)?;
+            skill
+                .gems
+                .iter()
+                .filter(|gem| gem.is_active())
+                .nth(index as usize)
+        });
+
+        Some(Minion {
+            id: gem.and_then(|gem| gem.skill_minion.as_deref()),
+            summoned_by: self.main_skill_name(),
+            skill_index: gem.and_then(|gem| gem.skill_minion_skill),
+            stats,
+        })
+    }
+
     fn main_skill_supported_by(&self, skill: &str) -> bool {
         self.main_skill()
             .iter()
             .flat_map(|x| x.support_gems())
-            .any(|gem| gem.name == skill)
+            .any(|gem| gem.name == skill || gem.base_name() == skill)
     }
 
     fn skill_sets(&self) -> Vec<crate::SkillSet<'_>> {
@@ -231,6 +428,8 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
                 let gear = crate::Gear {
                     weapon1: gear.weapon1.and_then(item),
                     weapon2: gear.weapon2.and_then(item),
+                    weapon1_swap: gear.weapon1_swap.and_then(item),
+                    weapon2_swap: gear.weapon2_swap.and_then(item),
                     helmet: gear.helmet.and_then(item),
                     body_armour: gear.body_armour.and_then(item),
                     gloves: gear.gloves.and_then(item),
@@ -245,12 +444,24 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
                     flask4: gear.flask4.and_then(item),
                     flask5: gear.flask5.and_then(item),
                     sockets: gear.sockets.iter().filter_map(|&id| item(id)).collect(),
+                    slots: gear
+                        .slots
+                        .iter()
+                        .filter_map(|slot| {
+                            Some(crate::GearSlot {
+                                name: &slot.name,
+                                item_id: slot.item_id,
+                                item: item(slot.item_id)?,
+                            })
+                        })
+                        .collect(),
                 };
 
                 crate::ItemSet {
                     id: set.id,
                     title: set.title.as_deref(),
                     gear,
+                    use_second_weapon_set: set.use_second_weapon_set,
                     is_selected: Some(set.id) == self.pob.items.active_item_set,
                 }
             })
@@ -310,8 +521,176 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
     }
 
     fn has_keystone(&self, keystone: Keystone) -> bool {
-        self.has_tree_node(keystone.node()) || self.has_keystone_on_gear(keystone)
+        keystone.node().is_some_and(|node| self.has_tree_node(node))
+            || self.has_keystone_on_gear(keystone)
+    }
+
+    fn has_keystone_in(&self, spec: &crate::TreeSpec, keystone: Keystone) -> bool {
+        keystone
+            .node()
+            .is_some_and(|node| spec.nodes.contains(&node))
+            || self.has_keystone_on_gear(keystone)
     }
+
+    fn has_keystone_on_gear(&self, keystone: Keystone) -> bool {
+        let keystone = crate::passives::keystone_item_stat(keystone);
+
+        let Some(active_item_set) = self.pob.items.active_item_set else {
+            return false;
+        };
+        let active_item_set = self
+            .pob
+            .items
+            .item_sets
+            .iter()
+            .find(|set| set.id == active_item_set);
+        let Some(active_item_set) = active_item_set else {
+            return false;
+        };
+
+        // Item pieces that can potentially have a keystone on them
+        [
+            active_item_set.gear.body_armour,
+            active_item_set.gear.helmet,
+            active_item_set.gear.weapon1,
+            active_item_set.gear.weapon2,
+            active_item_set.gear.gloves,
+            active_item_set.gear.boots,
+            active_item_set.gear.belt,
+        ]
+        .into_iter()
+        .filter_map(|item| item.and_then(|id| self.pob.items.items.get(&id)))
+        .filter_map(|item| crate::Item::parse(&item.content.content).ok())
+        // Mods strip the PoB line prefixes and only include the selected variant.
+        .any(|item| item.mods().any(|m| m.line == keystone))
+    }
+
+    fn party(&self) -> crate::Party<'_> {
+        let party = &self.pob.party;
+        crate::Party::parse(
+            party.buffs("Aura"),
+            party.buffs("Curse"),
+            party.buffs("Link Skills"),
+            party.buffs("EnemyConditions"),
+        )
+    }
+
+    fn calcs(&self) -> crate::Calcs {
+        let input = |name| self.pob.calcs.input.iter().find(|input| input.name == name);
+
+        crate::Calcs {
+            skill_number: input("skill_number")
+                .and_then(|input| input.number)
+                .map(|number| number as u16),
+            buff_mode: input("misc_buffMode")
+                .and_then(|input| input.string.as_deref())
+                .and_then(crate::BuffMode::from_pob),
+        }
+    }
+
+    fn import(&self) -> Option<crate::Import<'_>> {
+        let import = &self.pob.import;
+        let import = crate::Import {
+            realm: import.last_realm.as_deref(),
+            league: import.last_league.as_deref(),
+            account_hash: import.last_account_hash.as_deref(),
+            character_hash: import.last_character_hash.as_deref(),
+        };
+
+        (import.account_hash.is_some() || import.character_hash.is_some()).then_some(import)
+    }
+
+    fn trade_search_weights(&self) -> Vec<crate::TradeSearchWeight<'_>> {
+        self.pob
+            .trade_search_weights
+            .stats
+            .iter()
+            .map(|stat| crate::TradeSearchWeight {
+                stat: &stat.stat,
+                weight: stat.weight_mult,
+            })
+            .collect()
+    }
+}
+
+fn config_value(input: &Input) -> ConfigValue<'_> {
+    if let Some(ref value) = input.string {
+        ConfigValue::String(value)
+    } else if let Some(value) = input.number {
+        ConfigValue::Number(value)
+    } else if let Some(value) = input.boolean {
+        ConfigValue::Bool(value)
+    } else {
+        ConfigValue::None
+    }
+}
+
+/// See [`SerdePathOfBuilding::from_xml_lenient`], the document is only built
+/// if the strict parse fails or if it is kept to edit the build.
+fn lenient(s: &str, editable: bool) -> Result<(SerdePathOfBuilding, Vec<ParseWarning>)> {
+    let strict = match deserialize(s) {
+        Ok(pob) if !editable => {
+            return Ok((SerdePathOfBuilding { pob, xml: None }, Vec::new()));
+        }
+        strict => strict.ok(),
+    };
+
+    let xml = Document::parse(s)?;
+    if let Some(pob) = strict {
+        return Ok((
+            SerdePathOfBuilding {
+                pob,
+                xml: Some(xml),
+            },
+            Vec::new(),
+        ));
+    }
+
+    let root = match xml.root() {
+        Some(root) => root.clone(),
+        None => Element::new("PathOfBuilding"),
+    };
+
+    let mut lenient = Lenient::new(&root);
+    let pob = PathOfBuilding {
+        build: lenient.required("Build"),
+        skills: lenient.required("Skills"),
+        tree: lenient.required("Tree"),
+        items: lenient.optional("Items"),
+        notes: lenient.optional("Notes"),
+        config: lenient.optional("Config"),
+        party: lenient.optional("Party"),
+        calcs: lenient.optional("Calcs"),
+        import: lenient.optional("Import"),
+        trade_search_weights: lenient.optional("TradeSearchWeights"),
+    };
+
+    let xml = editable.then_some(xml);
+    Ok((SerdePathOfBuilding { pob, xml }, lenient.into_warnings()))
+}
+
+fn deserialize(s: &str) -> Result<PathOfBuilding> {
+    let mut xd = quick_xml::de::Deserializer::from_reader(s.as_bytes());
+    serde::Deserialize::deserialize(&mut xd).map_err(|err| locate_error(s, err))
+}
+
+/// Parses the build again with path tracking to locate `err`, tracking the path
+/// is not free and only done for builds which failed to parse.
+fn locate_error(s: &str, err: quick_xml::de::DeError) -> Error {
+    let mut xd = quick_xml::de::Deserializer::from_reader(s.as_bytes());
+    match serde_path_to_error::deserialize::<_, PathOfBuilding>(&mut xd) {
+        Ok(_) => Error::ParseXml("Unknown".to_owned(), err),
+        Err(err) => {
+            let path = err.path().to_string();
+            Error::ParseXml(path, err.into_inner())
+        }
+    }
+}
+
+/// Unknown stats are kept as [`Stat::Other`].
+fn typed_stat<'a>(name: &'a str, value: &str) -> Option<(Stat<'a>, StatValue)> {
+    let stat = Stat::from_name_or_other(name);
+    Some((stat, stat.parse_value(value)?))
 }
 
 /// Returns an iterator of active skills as PoB sees it.
@@ -319,7 +698,7 @@ fn active_skill_names(gems: &[Gem]) -> impl Iterator<Item = &str> {
     gems.iter().flat_map(|gem| {
         let active = gem.is_active().then_some(gem.name.as_str());
         // all vaal gems are implicitly also active
-        let vaal = gem.is_vaal().then(|| gem.non_vaal_name());
+        let vaal = gem.is_vaal().then(|| gem.base_name());
         // granted skills by gems (e.g. `Impending Doom` grantes `Doom Blast`.
         let granted = gem
             .skill_id
@@ -366,6 +745,9 @@ fn to_skill(skill: &Skill, is_selected: bool) -> crate::Skill {
                 skill_id: g.skill_id.as_deref(),
                 gem_id: g.gem_id.as_deref(),
                 quality_id: g.quality_id.as_deref(),
+                variant: g.variant(),
+                base_name: g.base_name(),
+                alternate_quality: g.alternate_quality(),
                 level: g.level,
                 quality: g.quality,
                 is_enabled: g.enabled,
@@ -380,6 +762,7 @@ fn to_skill(skill: &Skill, is_selected: bool) -> crate::Skill {
         gems,
         label: skill.label.as_deref(),
         slot: skill.slot.as_deref(),
+        granted_by: skill.granted_by(),
         is_selected,
         is_enabled: skill.enabled,
     }
@@ -390,7 +773,11 @@ mod tests {
     use shared::AscendancyOrClass;
 
     use super::*;
-    use crate::{PathOfBuilding, PathOfBuildingExt};
+    use crate::snapshot::BuildSnapshot;
+    use crate::{
+        BuffMode, ClusterJewelSize, ClusterNode, GearStat, GemVariant, Influence, Item, JewelKind,
+        PathOfBuilding, PathOfBuildingExt, Rarity,
+    };
 
     static V316_EMPTY: &str = include_str!("../../test/316_empty.xml");
     static V316_POISON_OCC: &str = include_str!("../../test/316_poison_occ.xml");
@@ -398,6 +785,645 @@ mod tests {
     static V319_MASTERY_EFFECTS: &str = include_str!("../../test/319_mastery_effects.xml");
     static V320_IMPENDING_DOOM: &str = include_str!("../../test/320_impending_doom.xml");
     static V322_OVERRIDES: &str = include_str!("../../test/322_overrides.xml");
+    static CHARACTER_ITEMS: &str = include_str!("../../test/character_items.json");
+    static CHARACTER_PASSIVES: &str = include_str!("../../test/character_passives.json");
+
+    /// Party tab with imported buffs, written like Path of Building writes it.
+    /// None of the exports have a configured party, it is added to the 3.18 export.
+    static PARTY: &str = r#"	<Party destination="All" append="false" ShowAdvanceTools="false">
+		<ImportedText>Support
+Haste
+Flame Link</ImportedText>
+		<ImportedBuffs name="Aura">--- Aura ---
+Haste
+1.5
+MovementSpeed|INC|16|Haste
+Speed|INC|12|Haste
+---
+Purity of Elements
+1
+ElementalResist|BASE|35|Purity of Elements
+</ImportedBuffs>
+		<ImportedBuffs name="Curse">--- Curse ---
+Despair
+ChaosResist|BASE|-25|Despair
+</ImportedBuffs>
+		<ImportedBuffs name="Link Skills">--- Link ---
+Flame Link
+FireMin|BASE|55|Flame Link
+</ImportedBuffs>
+		<ImportedBuffs name="EnemyConditions">Condition:Shocked|FLAG|true
+Condition:Intimidated|FLAG|true
+</ImportedBuffs>
+		<ImportedBuffs name="EnemyMods"/>
+	</Party>
+"#;
+
+    fn v318_party() -> String {
+        V318_SKILLSET.replacen("\t</Calcs>\n", &format!("\t</Calcs>\n{PARTY}"), 1)
+    }
+
+    #[test]
+    fn round_trip() {
+        let v318_party = v318_party();
+        for xml in [
+            V316_EMPTY,
+            V316_POISON_OCC,
+            V318_SKILLSET,
+            &v318_party,
+            V319_MASTERY_EFFECTS,
+            V320_IMPENDING_DOOM,
+            V322_OVERRIDES,
+        ] {
+            let pob = SerdePathOfBuilding::from_xml_editable(xml).unwrap();
+            assert_eq!(pob.to_xml().unwrap(), xml);
+
+            let export = pob.to_export().unwrap();
+            let pob = SerdePathOfBuilding::from_export_editable(&export).unwrap();
+            assert_eq!(pob.to_xml().unwrap(), xml);
+        }
+    }
+
+    #[test]
+    fn known_stats() {
+        for xml in [
+            V316_EMPTY,
+            V316_POISON_OCC,
+            V318_SKILLSET,
+            V319_MASTERY_EFFECTS,
+            V320_IMPENDING_DOOM,
+            V322_OVERRIDES,
+        ] {
+            let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+            for stat in &pob.pob.build.stats {
+                if let Some(stat) = stat.player().or_else(|| stat.minion()) {
+                    assert!(Stat::from_name(&stat.name).is_some(), "{}", stat.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_stats_are_kept() {
+        let xml = V316_POISON_OCC.replace(
+            r#"<PlayerStat stat="AverageDamage""#,
+            r#"<PlayerStat stat="SomeNewStat" value="12.5"/><PlayerStat stat="AverageDamage""#,
+        );
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+        let stats = pob.stats().collect::<Vec<_>>();
+        assert!(stats.contains(&(Stat::Other("SomeNewStat"), StatValue::Float(12.5))));
+        assert!(stats.contains(&(Stat::AverageDamage, StatValue::Float(11573.296289588))));
+    }
+
+    #[test]
+    fn edit_and_export() {
+        let mut pob = SerdePathOfBuilding::from_xml_editable(V316_POISON_OCC).unwrap();
+        pob.set_level(100).unwrap();
+        pob.set_notes("Some <new> notes & more").unwrap();
+        pob.set_config(Config::FrenzyCharges, ConfigValue::Bool(true))
+            .unwrap();
+        pob.set_config(Config::EnemeyHit, ConfigValue::None)
+            .unwrap();
+        assert!(pob.set_item(8, "Rarity: NORMAL\nLion Pelt").unwrap());
+        assert!(!pob.set_item(999, "Rarity: NORMAL\nLion Pelt").unwrap());
+
+        let pob = SerdePathOfBuilding::from_export_editable(&pob.to_export().unwrap()).unwrap();
+        assert_eq!(100, pob.level());
+        assert_eq!("Some <new> notes & more", pob.notes());
+        assert!(pob.config(Config::FrenzyCharges).is_true());
+        assert!(!pob.config(Config::EnemeyHit).is_some());
+        assert_eq!(Some("Rarity: NORMAL\nLion Pelt"), pob.item_by_id(8));
+        // Data unknown to the model is retained.
+        assert!(pob.to_xml().unwrap().contains("<TreeView "));
+    }
+
+    #[test]
+    fn read_only_is_not_editable() {
+        let mut pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert!(matches!(pob.set_level(100), Err(Error::NotEditable)));
+        assert!(matches!(
+            pob.set_item(8, "Rarity: NORMAL\nLion Pelt"),
+            Err(Error::NotEditable)
+        ));
+        assert!(matches!(pob.to_xml(), Err(Error::NotEditable)));
+        // A failed edit leaves the build untouched.
+        assert_eq!(96, pob.level());
+        assert_ne!(Some("Rarity: NORMAL\nLion Pelt"), pob.item_by_id(8));
+    }
+
+    #[test]
+    fn minion() {
+        let xml = r#"<PathOfBuilding>
+            <Build level="90" className="Witch" ascendClassName="Necromancer" mainSocketGroup="1">
+                <PlayerStat stat="Life" value="4000"/>
+                <MinionStat stat="Life" value="12000"/>
+                <MinionStat stat="CombinedDPS" value="250000.5"/>
+                <MinionStat stat="FireResist" value="75"/>
+                <MinionStat stat="Speed" value="2.5"/>
+            </Build>
+            <Skills>
+                <Skill mainActiveSkill="1" enabled="true">
+                    <Gem nameSpec="Minion Damage" skillId="SupportMinionDamage" skillMinion="SummonedPhantasm"/>
+                    <Gem nameSpec="Raise Spectre" skillId="RaiseSpectre" skillMinion="Metadata/Monsters/Kiweth/KiwethSeagull" skillMinionSkill="2"/>
+                </Skill>
+            </Skills>
+            <Tree activeSpec="1"><Spec/></Tree>
+        </PathOfBuilding>"#;
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let minion = pob.minion().unwrap();
+        assert_eq!(Some("Metadata/Monsters/Kiweth/KiwethSeagull"), minion.id);
+        assert_eq!(Some("Kiweth Seagull"), minion.name().as_deref());
+        assert_eq!(Some("Raise Spectre"), minion.summoned_by);
+        assert_eq!(Some(2), minion.skill_index);
+        assert_eq!(Some(12000.0), minion.stats.life);
+        assert_eq!(Some(250000.5), minion.stats.dps);
+        assert_eq!(Some(75.0), minion.stats.fire_resistance);
+        assert_eq!(Some(2.5), minion.stats.speed);
+        assert_eq!(None, minion.stats.energy_shield);
+
+        // Only has a stale minion selection and a single minion stat.
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert!(pob.minion().is_none());
+    }
+
+    #[test]
+    fn alternate_ascendancy() {
+        let xml = r#"<PathOfBuilding>
+            <Build level="90" className="Templar" ascendClassName="Inquisitor" mainSocketGroup="1">
+                <PlayerStat stat="Life" value="4000"/>
+            </Build>
+            <Skills/>
+            <Tree activeSpec="2">
+                <Spec ascendClassId="3" secondaryAscendClassId="2"/>
+                <Spec ascendClassId="3" secondaryAscendClassId="1"/>
+            </Tree>
+        </PathOfBuilding>"#;
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+        assert_eq!(
+            Some(shared::AlternateAscendancy::Warden),
+            pob.alternate_ascendancy()
+        );
+
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert_eq!(None, pob.alternate_ascendancy());
+    }
+
+    #[test]
+    fn loadouts() {
+        let xml = r#"<PathOfBuilding>
+            <Build level="90" className="Witch" mainSocketGroup="1">
+                <PlayerStat stat="Life" value="4000"/>
+            </Build>
+            <Skills activeSkillSet="2">
+                <SkillSet id="1" title="Leveling">
+                    <Skill enabled="true"><Gem nameSpec="Frostblink" skillId="FrostBlink"/></Skill>
+                </SkillSet>
+                <SkillSet id="2" title="{1,2} Endgame">
+                    <Skill enabled="true"><Gem nameSpec="Flame Dash" skillId="FlameDash"/></Skill>
+                </SkillSet>
+            </Skills>
+            <Items activeItemSet="3">
+                <ItemSet id="1" title="Leveling"/>
+                <ItemSet id="2" title="Unused"/>
+                <ItemSet id="3" title="Endgame {2}"/>
+            </Items>
+            <Tree activeSpec="2">
+                <Spec title="Leveling"/>
+                <Spec title="Mapping {2}"/>
+                <Spec title="No Items"/>
+            </Tree>
+        </PathOfBuilding>"#;
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let loadouts = pob.loadouts();
+        assert_eq!(2, loadouts.len());
+
+        assert_eq!("Leveling", loadouts[0].title);
+        assert_eq!(
+            (0, 0, 0),
+            (
+                loadouts[0].tree_spec,
+                loadouts[0].item_set,
+                loadouts[0].skill_set
+            )
+        );
+        assert!(!loadouts[0].is_selected);
+
+        // `{2}` links the item set and the skill set, which lists both `1` and `2`.
+        assert_eq!("Mapping", loadouts[1].title);
+        assert_eq!(
+            (1, 2, 1),
+            (
+                loadouts[1].tree_spec,
+                loadouts[1].item_set,
+                loadouts[1].skill_set
+            )
+        );
+        assert!(loadouts[1].is_selected);
+
+        // Tree specs are titled, item sets and skill sets are not.
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert!(pob.loadouts().is_empty());
+    }
+
+    #[test]
+    fn party_calcs_import() {
+        let xml = r#"<PathOfBuilding>
+            <Build level="90" className="Witch" mainSocketGroup="1"/>
+            <Skills/>
+            <Tree activeSpec="1"><Spec/></Tree>
+            <Party destination="All" append="false">
+                <ImportedText>Haste</ImportedText>
+                <ImportedBuffs name="Aura">Haste
+MovementSpeed|INC|16|Haste
+---
+Wrath</ImportedBuffs>
+                <ImportedBuffs name="Curse">Despair</ImportedBuffs>
+                <ImportedBuffs name="Link Skills"/>
+                <ImportedBuffs name="EnemyConditions">Condition:Shocked|FLAG|true</ImportedBuffs>
+            </Party>
+            <Calcs>
+                <Input name="skill_number" number="2"/>
+                <Input name="misc_buffMode" string="COMBAT"/>
+                <Section collapsed="false" id="SkillSelect"/>
+            </Calcs>
+            <Import lastAccountHash="abc" lastRealm="PC" lastCharacterHash="def" lastLeague="Standard"/>
+            <TradeSearchWeights>
+                <Stat stat="FullDPS" weightMult="1.00"/>
+                <Stat stat="TotalEHP" weightMult="0.50"/>
+            </TradeSearchWeights>
+        </PathOfBuilding>"#;
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let party = pob.party();
+        assert_eq!(vec!["Haste", "Wrath"], party.auras);
+        assert_eq!(vec!["Despair"], party.curses);
+        assert!(party.links.is_empty());
+        assert_eq!(vec!["Shocked"], party.enemy_conditions);
+
+        let calcs = pob.calcs();
+        assert_eq!(Some(2), calcs.skill_number);
+        assert_eq!(Some(BuffMode::InCombat), calcs.buff_mode);
+
+        let import = pob.import().unwrap();
+        assert_eq!(Some("PC"), import.realm);
+        assert_eq!(Some("Standard"), import.league);
+        assert_eq!(Some("abc"), import.account_hash);
+
+        let weights = pob.trade_search_weights();
+        assert_eq!(2, weights.len());
+        assert_eq!(("TotalEHP", 0.5), (weights[1].stat, weights[1].weight));
+
+        let pob = SerdePathOfBuilding::from_xml(V316_EMPTY).unwrap();
+        assert!(pob.party().is_empty());
+        assert!(pob.import().is_none());
+        assert_eq!(Some(BuffMode::Effective), pob.calcs().buff_mode);
+
+        let pob = SerdePathOfBuilding::from_xml(V318_SKILLSET).unwrap();
+        assert_eq!(Some("PC"), pob.import().unwrap().realm);
+
+        let pob = SerdePathOfBuilding::from_xml(&v318_party()).unwrap();
+        let party = pob.party();
+        assert_eq!(vec!["Haste", "Purity of Elements"], party.auras);
+        assert_eq!(vec!["Despair"], party.curses);
+        assert_eq!(vec!["Flame Link"], party.links);
+        assert_eq!(vec!["Shocked", "Intimidated"], party.enemy_conditions);
+    }
+
+    #[test]
+    fn leveling_guide() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let guide = pob.leveling_guide().unwrap();
+
+        let titles = guide
+            .steps
+            .iter()
+            .map(|step| step.title().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "Pre-swap Levelling",
+                "Normal Lab + Respec",
+                "Cruel Lab",
+                "Merc Lab",
+                "Uber Lab",
+                "End-Game Clusters",
+                "End-Game",
+            ],
+            titles
+        );
+
+        // There is only one skill set, which is not a stage of the build.
+        assert!(guide.steps.iter().all(|step| step.skills.is_none()));
+
+        let first = guide.steps[0].tree.as_ref().unwrap();
+        // Without the class start and ascendancy nodes.
+        assert_eq!((36, 40), (first.points, first.added.len()));
+        assert!(first.removed.is_empty());
+
+        for step in &guide.steps[1..] {
+            let tree = step.tree.as_ref().unwrap();
+            assert!(!tree.added.is_empty() || !tree.removed.is_empty());
+        }
+
+        let no_guide = SerdePathOfBuilding::from_xml(V320_IMPENDING_DOOM).unwrap();
+        assert!(no_guide.leveling_guide().is_none());
+    }
+
+    #[test]
+    fn snapshot() {
+        for xml in [
+            V316_EMPTY,
+            V316_POISON_OCC,
+            V318_SKILLSET,
+            V319_MASTERY_EFFECTS,
+            V320_IMPENDING_DOOM,
+            V322_OVERRIDES,
+        ] {
+            let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+            let snapshot = BuildSnapshot::new(&pob);
+
+            let json = serde_json::to_string(&snapshot).unwrap();
+            let snapshot: BuildSnapshot = serde_json::from_str(&json).unwrap();
+            assert_eq!(crate::snapshot::VERSION, snapshot.version);
+
+            let snapshot: &dyn PathOfBuilding = &snapshot;
+            assert!(crate::diff::diff(&pob, snapshot).is_empty());
+            assert_eq!(pob.level(), snapshot.level());
+            assert_eq!(pob.ascendancy_or_class(), snapshot.ascendancy_or_class());
+            assert_eq!(pob.main_skill_name(), snapshot.main_skill_name());
+            assert_eq!(
+                pob.minion().map(|minion| minion.stats),
+                snapshot.minion().map(|minion| minion.stats)
+            );
+            assert_eq!(pob.party(), snapshot.party());
+            assert_eq!(pob.calcs(), snapshot.calcs());
+            assert_eq!(pob.import(), snapshot.import());
+            for keystone in Keystone::ALL {
+                assert_eq!(
+                    pob.has_keystone(*keystone),
+                    snapshot.has_keystone(*keystone)
+                );
+            }
+            for spec in pob.tree_specs() {
+                assert_eq!(pob.keystones(&spec), snapshot.keystones(&spec));
+                assert_eq!(pob.jewels(&spec).len(), snapshot.jewels(&spec).len());
+            }
+            for skill in ["Added Cold Damage", "Void Manipulation", "Unleash"] {
+                assert_eq!(
+                    pob.main_skill_supported_by(skill),
+                    snapshot.main_skill_supported_by(skill)
+                );
+            }
+            assert_eq!(pob.loadouts().len(), snapshot.loadouts().len());
+        }
+    }
+
+    #[test]
+    fn snapshot_gear() {
+        let xml = r#"<PathOfBuilding>
+            <Build level="90" className="Witch" mainSocketGroup="1"/>
+            <Skills/>
+            <Tree activeSpec="1"><Spec/></Tree>
+            <Items activeItemSet="2">
+                <Item id="1">Rarity: UNIQUE
+Shavronne's Wrappings
+Occultist's Vestment
+Chaos Damage does not bypass Energy Shield</Item>
+                <Item id="2">Rarity: UNIQUE
+Loreweave
+Elegant Ringmail
+Elemental Equilibrium</Item>
+                <Item id="3">Rarity: UNIQUE
+Test Belt
+Leather Belt
+Variant: One
+Variant: Two
+Selected Variant: 2
+{variant:1}{tags:keystone}Mind Over Matter
+{crafted}{variant:2}Iron Reflexes</Item>
+                <ItemSet id="1"><Slot name="Body Armour" itemId="1"/></ItemSet>
+                <ItemSet id="2">
+                    <Slot name="Body Armour" itemId="2"/>
+                    <Slot name="Belt" itemId="3"/>
+                </ItemSet>
+            </Items>
+        </PathOfBuilding>"#;
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+        let snapshot = BuildSnapshot::new(&pob);
+        assert_eq!(
+            vec!["Elemental Equilibrium", "Iron Reflexes"],
+            snapshot.gear_keystones
+        );
+        assert!(!pob.has_keystone_on_gear(Keystone::MindOverMatter));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let snapshot: BuildSnapshot = serde_json::from_str(&json).unwrap();
+        assert!(snapshot.item_by_id(1).is_some() && snapshot.item_by_id(2).is_some());
+
+        for keystone in Keystone::ALL {
+            assert_eq!(
+                pob.has_keystone_on_gear(*keystone),
+                snapshot.has_keystone_on_gear(*keystone)
+            );
+        }
+        for (a, b) in pob.item_sets().iter().zip(snapshot.item_sets()) {
+            assert_eq!(a.gear.slots, b.gear.slots);
+            assert_eq!(a.gear.body_armour, b.gear.body_armour);
+        }
+    }
+
+    #[test]
+    fn tree_url() {
+        let pob = SerdePathOfBuilding::from_xml(V319_MASTERY_EFFECTS).unwrap();
+        let specs = pob.tree_specs();
+        assert_eq!(Some(true), specs[0].url_matches_nodes());
+
+        let url = specs[0].url.unwrap();
+        let pob = SerdePathOfBuilding::from_tree_url(url).unwrap();
+        assert_eq!(Class::Scion, pob.class());
+        assert_eq!(None, pob.ascendancy());
+
+        let tree = &pob.tree_specs()[0];
+        assert_eq!(Some(url.trim()), tree.url);
+        assert_eq!(Some(true), tree.url_matches_nodes());
+
+        let url = TreeUrl {
+            game_version: Some("3.16.0".to_owned()),
+            class_id: 3,
+            ascendancy_id: 1,
+            ..Default::default()
+        };
+        let pob = SerdePathOfBuilding::from_tree_url(&url.to_url().unwrap()).unwrap();
+        assert_eq!(Some(Ascendancy::Occultist), pob.ascendancy());
+        assert_eq!(Some("3_16"), pob.tree_specs()[0].version);
+
+        assert!(SerdePathOfBuilding::from_tree_url("https://pobb.in/").is_err());
+    }
+
+    #[test]
+    fn lenient_skips_broken_parts() {
+        let xml = V316_POISON_OCC
+            .replace("ascendClassName=\"Occultist\"", "ascendClassName=\"Foo\"")
+            .replace(
+                "title=\"Perfect Gear\" id=\"2\"",
+                "title=\"Perfect Gear\" id=\"-2\"",
+            );
+        let err = SerdePathOfBuilding::from_xml(&xml).unwrap_err();
+        assert!(matches!(err, Error::ParseXml(path, _) if path == "Build.ascendClassName"));
+
+        let (mut pob, warnings) = SerdePathOfBuilding::from_xml_lenient_editable(&xml).unwrap();
+        let paths = warnings.iter().map(|w| w.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "Build.ascendClassName",
+                "Items.ItemSet[1].id",
+                "Items.ItemSet[1]"
+            ]
+        );
+
+        assert_eq!(96, pob.level());
+        assert_eq!(Class::Witch, pob.class());
+        assert_eq!(None, pob.ascendancy());
+        assert_eq!(Some("Poisonous Concoction"), pob.main_skill_name());
+        assert_eq!(1, pob.item_sets().len());
+        assert_eq!(8516, pob.notes().len());
+
+        // The broken parts are still written back out as they were.
+        assert_eq!(xml, pob.to_xml().unwrap());
+        pob.set_level(100).unwrap();
+        assert_eq!(100, pob.level());
+        assert!(pob.to_xml().unwrap().contains("Foo"));
+    }
+
+    #[test]
+    fn lenient_defaults_missing_sections() {
+        let (pob, warnings) = SerdePathOfBuilding::from_xml_lenient(
+            "<PathOfBuilding><Build level=\"x\"/><Notes>foo</Notes></PathOfBuilding>",
+        )
+        .unwrap();
+        let paths = warnings.iter().map(|w| w.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["Build.level", "Build", "Skills", "Tree"]);
+        assert_eq!(
+            "Build: missing field `level`, using defaults for Build",
+            warnings[1].to_string()
+        );
+
+        assert_eq!(1, pob.level());
+        assert_eq!("foo", pob.notes());
+        assert!(pob.tree_specs().is_empty());
+
+        assert!(SerdePathOfBuilding::from_xml_lenient("<PathOfBuilding>").is_err());
+    }
+
+    #[test]
+    fn lenient_limits_reparsing() {
+        // Every broken attribute causes the whole section to be parsed again,
+        // a large section runs out of attempts before all of them are removed.
+        let notes = "x".repeat(512 * 1024);
+        let sets = (0..8)
+            .map(|i| format!(r#"<ItemSet id="-{i}"/>"#))
+            .collect::<String>();
+        let xml = V316_EMPTY.replace(
+            r#"<Items activeItemSet="1" useSecondWeaponSet="nil">"#,
+            &format!(r#"<Items activeItemSet="1" useSecondWeaponSet="nil">{sets}<!-- {notes} -->"#),
+        );
+
+        let (pob, warnings) = SerdePathOfBuilding::from_xml_lenient(&xml).unwrap();
+        assert_eq!(4, warnings.len());
+        assert_eq!(
+            "Items: too many errors, using defaults",
+            warnings.last().unwrap().to_string()
+        );
+        assert!(pob.item_sets().is_empty());
+    }
+
+    #[test]
+    fn lenient_valid_build() {
+        let (_, warnings) = SerdePathOfBuilding::from_xml_lenient(V316_POISON_OCC).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn gear_stats() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let item_set = pob
+            .item_sets()
+            .into_iter()
+            .find(|set| set.is_selected)
+            .unwrap();
+
+        let slots = item_set.gear_stats();
+        let shield = slots.iter().find(|s| s.slot == "Weapon 2").unwrap();
+        assert_eq!(
+            shield.stats.iter().collect::<Vec<_>>(),
+            [
+                (GearStat::Life, 109.0),
+                (GearStat::EnergyShield, 107.0),
+                (GearStat::LightningResistance, 29.0),
+                (GearStat::SpellSuppression, 39.0),
+            ]
+        );
+        assert!(shield.untranslated.is_empty());
+        let boots = slots.iter().find(|s| s.slot == "Boots").unwrap();
+        assert_eq!(
+            boots.untranslated,
+            ["80% chance to Avoid being Stunned if you've Killed Recently"]
+        );
+        // Abyss jewels are part of the gear.
+        let jewel = slots
+            .iter()
+            .find(|s| s.slot == "Belt Abyssal Socket 1")
+            .unwrap();
+        assert_eq!(30.0, jewel.stats.get(GearStat::Life));
+
+        let total = item_set.total_gear_stats();
+        assert_eq!(765.0, total.get(GearStat::Life));
+        assert_eq!(137.0, total.get(GearStat::FireResistance));
+        assert_eq!(30.0, total.get(GearStat::MovementSpeed));
+        assert_eq!(0.0, total.get(GearStat::ChaosResistance));
+    }
+
+    #[test]
+    fn item_set_slots() {
+        let xml = V316_POISON_OCC.replacen(
+            r#"<ItemSet useSecondWeaponSet="false" id="1">"#,
+            r#"<ItemSet useSecondWeaponSet="true" id="1">"#,
+            1,
+        );
+        let xml = xml.replacen(
+            r#"<Slot name="Weapon 1 Swap" itemId="0"/>"#,
+            r#"<Slot name="Weapon 1 Swap" itemId="19"/>"#,
+            1,
+        );
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+
+        let item_sets = pob.item_sets();
+        let gear = &item_sets[0].gear;
+        assert!(item_sets[0].use_second_weapon_set);
+        assert!(!item_sets[1].use_second_weapon_set);
+
+        assert_eq!(pob.item_by_id(19), gear.weapon1_swap);
+        assert_eq!(None, gear.weapon2_swap);
+        assert_eq!(None, item_sets[1].gear.weapon1_swap);
+
+        let swap = gear
+            .slots
+            .iter()
+            .find(|slot| slot.is_weapon_swap())
+            .unwrap();
+        assert_eq!("Weapon 1 Swap", swap.name);
+        assert_eq!(None, swap.socketed_in());
+
+        let belt = gear.socketed_in("Belt").collect::<Vec<_>>();
+        assert_eq!(1, belt.len());
+        assert_eq!("Belt Abyssal Socket 1", belt[0].name);
+        assert_eq!(Some(belt[0].item), pob.item_by_id(19));
+        assert_eq!(0, gear.socketed_in("Helmet").count());
+        assert!(gear.slots.iter().any(|slot| Some(slot.item) == gear.helmet));
+    }
 
     #[test]
     fn parse_v316_empty() {
@@ -444,8 +1470,74 @@ mod tests {
         // MoM is on an active item
         assert!(pob.has_keystone(Keystone::MindOverMatter));
 
+        let specs = pob.tree_specs();
+        let active = specs.iter().find(|spec| spec.active).unwrap();
+        let keystones = pob.keystones(active);
+        assert!(keystones.contains(&Keystone::MindOverMatter));
+        assert!(!keystones.contains(&Keystone::EldritchBattery));
+        for keystone in Keystone::ALL {
+            assert_eq!(
+                pob.has_keystone(*keystone),
+                pob.has_keystone_in(active, *keystone)
+            );
+        }
+
+        // The active spec only has a tree URL.
+        let url = active.tree_url().unwrap();
+        let notables = pob.allocated_notables(active);
+        assert!(!notables.is_empty());
+        assert!(notables.iter().all(|(id, _)| url.nodes.contains(id)));
+        let notables = pob.allocated_notables(&specs[0]);
+        assert!(!notables.is_empty());
+        assert!(notables.iter().all(|(id, _)| specs[0].nodes.contains(id)));
+
         assert_eq!(Some("3.19".to_owned()), pob.max_tree_version());
 
+        let jewels = pob.jewels(&specs[0]);
+        let cluster = jewels
+            .iter()
+            .find_map(|jewel| match &jewel.kind {
+                JewelKind::Cluster(cluster) if jewel.node_id == 32763 => Some(cluster),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(ClusterJewelSize::Large, cluster.size);
+        assert_eq!(8, cluster.passives);
+        assert_eq!(2, cluster.jewel_sockets);
+        assert_eq!(
+            vec!["12% increased Chaos Damage"],
+            cluster.small_passive_stats
+        );
+        assert_eq!(vec![65830, 65831, 65824, 65826, 65834], cluster.allocated);
+        let notables = cluster
+            .notables
+            .iter()
+            .map(|notable| (notable.name, notable.node.map(|(id, _)| id)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Touch of Cruelty", Some(49273)),
+                ("Unholy Grace", Some(2264)),
+                ("Wicked Pall", Some(34595))
+            ],
+            notables
+        );
+        // Every allocated cluster node belongs to exactly one of the socketed cluster jewels.
+        let allocated = jewels
+            .iter()
+            .filter_map(|jewel| match &jewel.kind {
+                JewelKind::Cluster(cluster) => Some(cluster.allocated.len()),
+                _ => None,
+            })
+            .sum::<usize>();
+        let cluster_nodes = specs[0]
+            .nodes
+            .iter()
+            .filter(|&&id| ClusterNode::from_id(id).is_some())
+            .count();
+        assert_eq!(10, cluster_nodes);
+        assert_eq!(cluster_nodes, allocated);
+
         // Level and quality overflow the u8, they should fall back to Default::default()
         assert_eq!(0, pob.skill_sets()[0].skills[0].gems[0].level);
         assert_eq!(0, pob.skill_sets()[0].skills[0].gems[0].quality);
@@ -453,11 +1545,84 @@ mod tests {
         assert_eq!(20, pob.skill_sets()[0].skills[0].gems[1].level);
         assert_eq!(20, pob.skill_sets()[0].skills[0].gems[1].quality);
 
+        let stats = pob.stats().collect::<Vec<_>>();
+        assert!(stats.contains(&(Stat::EnduranceChargesMax, StatValue::Integer(3))));
+        assert!(stats
+            .iter()
+            .all(|(stat, value)| stat.kind() == value.kind()));
+        assert_eq!(
+            Some(&(Stat::EnduranceChargesMax, StatValue::Integer(1))),
+            pob.minion_stats().collect::<Vec<_>>().first()
+        );
+
         assert_eq!(2, pob.item_sets().len());
         assert_eq!(None, pob.item_sets()[0].title);
         assert_eq!(Some("Perfect Gear"), pob.item_sets()[1].title);
 
-        // TODO: test configs
+        assert_eq!(Some(15.0), pob.config(Config::WitherStacks).number());
+        let configs = pob.configs().collect::<Vec<_>>();
+        assert_eq!(9, configs.len());
+        assert!(configs.contains(&(Config::Boss, ConfigValue::String("Sirus"))));
+        assert!(configs.contains(&(Config::ConditionEnemyHindered, ConfigValue::Bool(true))));
+        assert!(configs.contains(&(Config::MultiplierCruelty, ConfigValue::Number(30.0))));
+        // Empty custom mods are not a set value.
+        assert!(!configs
+            .iter()
+            .any(|(config, _)| *config == Config::CustomMods));
+        assert_eq!(0, pob.unknown_configs().count());
+    }
+
+    #[test]
+    fn unknown_configs() {
+        let xml = V316_POISON_OCC.replace(
+            "<Config>",
+            r#"<Config><Input name="someNewOption" number="4"/>"#,
+        );
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+        assert_eq!(
+            vec![("someNewOption", ConfigValue::Number(4.0))],
+            pob.unknown_configs().collect::<Vec<_>>()
+        );
+
+        let snapshot = BuildSnapshot::new(&pob);
+        assert_eq!(
+            pob.unknown_configs().collect::<Vec<_>>(),
+            snapshot.unknown_configs().collect::<Vec<_>>()
+        );
+        assert_eq!(pob.configs().count(), snapshot.configs().count());
+    }
+
+    #[test]
+    fn timeless_jewel_keystones() {
+        // Lethal Pride socketed next to an allocated Glancing Blows.
+        let jewel = "Rarity: UNIQUE\nLethal Pride\nTimeless Jewel\nLimited to: 1 Historic\nRadius: Large\nImplicits: 0\nCommanded leadership over 10000 warriors under Kaom\nPassives in radius are Conquered by the Karui\nHistoric";
+        let xml = V316_EMPTY
+            .replace(
+                r#"nodes="58833" treeVersion="3_16""#,
+                r#"nodes="58833,26196,39713" treeVersion="3_15""#,
+            )
+            .replace(
+                "<Sockets/>",
+                r#"<Sockets><Socket nodeId="26196" itemId="1"/></Sockets>"#,
+            )
+            .replace(
+                r#"<Items activeItemSet="1" useSecondWeaponSet="nil">"#,
+                &format!(
+                    r#"<Items activeItemSet="1" useSecondWeaponSet="nil"><Item id="1">{jewel}</Item>"#
+                ),
+            );
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+
+        let specs = pob.tree_specs();
+        let keystones = pob.keystones(&specs[0]);
+        assert_eq!(vec![Keystone::StrengthOfBlood], keystones);
+        // The tree node is still allocated.
+        assert!(pob.has_keystone(Keystone::GlancingBlows));
+
+        // The keystone of Akoya is not known, the conquered keystone is kept.
+        let pob = SerdePathOfBuilding::from_xml(&xml.replace("under Kaom", "under Akoya")).unwrap();
+        let specs = pob.tree_specs();
+        assert_eq!(vec![Keystone::GlancingBlows], pob.keystones(&specs[0]));
     }
 
     #[test]
@@ -469,6 +1634,23 @@ mod tests {
         assert!(pob.skill_sets()[0].is_selected);
         assert_eq!(Some("Arc SS"), pob.skill_sets()[0].title);
 
+        let skill_sets = pob.skill_sets();
+        let skills = skill_sets.iter().flat_map(|ss| &ss.skills);
+        let awakened = skills
+            .clone()
+            .flat_map(|skill| &skill.gems)
+            .find(|gem| gem.name == "Awakened Deadly Ailments")
+            .unwrap();
+        assert_eq!(GemVariant::Awakened, awakened.variant);
+        assert_eq!("Deadly Ailments", awakened.base_name);
+        assert_eq!(None, awakened.alternate_quality);
+
+        let granted = skills
+            .filter_map(|skill| skill.granted_by)
+            .collect::<Vec<_>>();
+        assert!(granted.contains(&"Nightgrip, Runic Gages"));
+        assert!(granted.contains(&"Circle of Nostalgia, Amethyst Ring"));
+
         // TODO: assert skill sets, expose skill sets
     }
 
@@ -527,4 +1709,66 @@ mod tests {
         assert_eq!(wise.node_id, 50197);
         assert_eq!(wise.effect, "+1\n\t\t\t\t\tLimited to 1");
     }
+
+    #[test]
+    fn character() {
+        let items = serde_json::from_str(CHARACTER_ITEMS).unwrap();
+        let passives = serde_json::from_str(CHARACTER_PASSIVES).unwrap();
+        let pob = SerdePathOfBuilding::from_character(&items, &passives).unwrap();
+
+        assert_eq!(92, pob.level());
+        assert_eq!(Class::Witch, pob.class());
+        assert_eq!(Some(Ascendancy::Occultist), pob.ascendancy());
+
+        let gear = &pob.item_sets()[0].gear;
+        let helmet = Item::parse(gear.helmet.unwrap()).unwrap();
+        assert_eq!(Some("Dread Crest"), helmet.name);
+        assert_eq!("Hubris Circlet", helmet.base);
+        assert_eq!(20, helmet.quality);
+        assert_eq!(69, helmet.level_requirement);
+        assert_eq!(2, helmet.sockets.groups().count());
+        assert_eq!(Some(Influence::Shaper), helmet.influence1);
+        let enchants = helmet.implicits().map(|m| m.tag).collect::<Vec<_>>();
+        assert_eq!(vec![Some("enchant")], enchants);
+        assert!(helmet.explicits().any(|m| m.crafted));
+
+        let body = Item::parse(gear.body_armour.unwrap()).unwrap();
+        assert_eq!(Rarity::Unique, body.rarity);
+        assert!(body.corrupted);
+        assert_eq!(6, body.sockets.max_links());
+
+        assert!(gear.ring1.is_some());
+        assert!(gear.belt.is_some());
+        assert!(gear.flask3.is_some());
+        // Abyss jewel in the belt.
+        assert_eq!(1, gear.sockets.len());
+
+        let skills = &pob.skill_sets()[0].skills;
+        assert_eq!(3, skills.len());
+        assert_eq!(Some("Helmet"), skills[0].slot);
+        assert_eq!(
+            vec!["Frostbite", "Arcane Surge"],
+            skills[0].gems.iter().map(|g| g.name).collect::<Vec<_>>()
+        );
+        assert!(skills[0].gems[1].is_support);
+        assert_eq!(20, skills[0].gems[0].level);
+        assert_eq!(20, skills[0].gems[0].quality);
+        assert_eq!(Some("Vortex"), pob.main_skill_name());
+
+        let spec = &pob.tree_specs()[0];
+        assert_eq!(9, spec.nodes.len());
+        assert!(spec.nodes.contains(&(65536 + 1234)));
+        assert_eq!(spec.mastery_effects, &[(44298, 48385), (55485, 29248)]);
+        assert_eq!(Some(true), spec.url_matches_nodes());
+
+        let jewel_slots = poe_tree::jewel_slots(poe_tree::Version::latest());
+        assert_eq!(1, spec.sockets.len());
+        assert_eq!(jewel_slots[1], spec.sockets[0].node_id);
+        let jewel = pob.item_by_id(spec.sockets[0].item_id).unwrap();
+        assert!(jewel.contains("Storm Spark"));
+
+        // The imported build can be exported and read again.
+        let export = pob.to_export().unwrap();
+        assert!(SerdePathOfBuilding::from_export(&export).is_ok());
+    }
 }
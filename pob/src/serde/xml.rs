@@ -0,0 +1,334 @@
+//! A small lossless XML tree.
+//!
+//! The serde model only picks out the parts of a build we understand, this tree
+//! keeps everything (unknown elements, attributes, whitespace) around so
+//! a build can be modified and written back out without losing any data.
+//!
+//! Text and attribute values are stored in their escaped form, untouched
+//! nodes are written back byte for byte.
+use std::borrow::Cow;
+
+use quick_xml::escape::{escape, unescape};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+    CData(String),
+    Comment(String),
+    Decl(String),
+    PI(String),
+    DocType(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Element {
+    pub name: String,
+    /// Attributes with their raw (escaped) values.
+    attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+    /// Whether the element was written as `<Element></Element>` instead of `<Element/>`.
+    expanded: bool,
+}
+
+impl Element {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            expanded: false,
+        }
+    }
+
+    fn from_start(start: &BytesStart) -> Result<Self> {
+        let name = std::str::from_utf8(start.name()).map_err(quick_xml::Error::Utf8)?;
+
+        let mut element = Self::new(name);
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            let key = std::str::from_utf8(attribute.key).map_err(quick_xml::Error::Utf8)?;
+            let value = std::str::from_utf8(&attribute.value).map_err(quick_xml::Error::Utf8)?;
+            // The value may have been single quoted, we always write double quotes.
+            let value = value.replace('"', "&quot;");
+            element.attributes.push((key.to_owned(), value));
+        }
+
+        Ok(element)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| unescape_str(value))
+    }
+
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        let value = escape_str(value);
+        match self.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, v)) => *v = value,
+            None => self.attributes.push((name.to_owned(), value)),
+        }
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) {
+        self.attributes.retain(|(key, _)| key != name);
+    }
+
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut Element> {
+        self.children.iter_mut().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find_map(|node| match node {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.elements_mut().find(|element| element.name == name)
+    }
+
+    /// Returns the child element with the given name, appending a new one if it doesn't exist.
+    pub fn child_or_insert(&mut self, name: &str) -> &mut Element {
+        let position = self
+            .children
+            .iter()
+            .position(|node| matches!(node, Node::Element(element) if element.name == name));
+
+        let index = match position {
+            Some(index) => index,
+            None => {
+                self.children.push(Node::Element(Element::new(name)));
+                self.children.len() - 1
+            }
+        };
+
+        match &mut self.children[index] {
+            Node::Element(element) => element,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replaces all text content of the element.
+    pub fn set_text(&mut self, text: &str) {
+        self.children
+            .retain(|node| !matches!(node, Node::Text(_) | Node::CData(_)));
+        self.children.insert(0, Node::Text(escape_str(text)));
+        self.expanded = true;
+    }
+
+    /// Serializes the element and all of its children.
+    pub fn to_xml(&self) -> Result<String> {
+        let mut writer = Writer::new(Vec::new());
+        self.write(&mut writer)?;
+        into_string(writer)
+    }
+
+    fn write<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        let mut start = BytesStart::borrowed_name(self.name.as_bytes());
+        for (key, value) in &self.attributes {
+            // Values are already escaped, `push_attribute` would escape them again.
+            start.extend_attributes(std::iter::once(quick_xml::events::attributes::Attribute {
+                key: key.as_bytes(),
+                value: Cow::Borrowed(value.as_bytes()),
+            }));
+        }
+
+        if self.children.is_empty() && !self.expanded {
+            writer.write_event(Event::Empty(start))?;
+            return Ok(());
+        }
+
+        writer.write_event(Event::Start(start))?;
+        for child in &self.children {
+            child.write(writer)?;
+        }
+        writer.write_event(Event::End(BytesEnd::borrowed(self.name.as_bytes())))?;
+
+        Ok(())
+    }
+}
+
+impl Node {
+    fn write<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        let event = match self {
+            Self::Element(element) => return element.write(writer),
+            Self::Text(text) => Event::Text(BytesText::from_escaped_str(text.as_str())),
+            Self::CData(text) => Event::CData(BytesText::from_escaped_str(text.as_str())),
+            Self::Comment(text) => Event::Comment(BytesText::from_escaped_str(text.as_str())),
+            Self::Decl(text) => Event::Decl(BytesDecl::from_start(BytesStart::borrowed(
+                text.as_bytes(),
+                3,
+            ))),
+            Self::PI(text) => Event::PI(BytesText::from_escaped_str(text.as_str())),
+            Self::DocType(text) => Event::DocType(BytesText::from_escaped_str(text.as_str())),
+        };
+        writer.write_event(event)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Document {
+    nodes: Vec<Node>,
+}
+
+impl Document {
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(s);
+        reader.check_end_names(true);
+
+        // Elements which are currently open, top level nodes go into `nodes`.
+        let mut stack: Vec<Element> = Vec::new();
+        let mut nodes = Vec::new();
+
+        let mut buf = Vec::new();
+        loop {
+            let node = match reader.read_event(&mut buf)? {
+                Event::Start(start) => {
+                    let mut element = Element::from_start(&start)?;
+                    element.expanded = true;
+                    stack.push(element);
+                    buf.clear();
+                    continue;
+                }
+                Event::End(end) => match stack.pop() {
+                    Some(element) => Node::Element(element),
+                    None => {
+                        return Err(quick_xml::Error::EndEventMismatch {
+                            expected: String::new(),
+                            found: String::from_utf8_lossy(end.name()).into_owned(),
+                        }
+                        .into())
+                    }
+                },
+                Event::Empty(start) => Node::Element(Element::from_start(&start)?),
+                Event::Text(text) => Node::Text(to_string(&text)?),
+                Event::CData(text) => Node::CData(to_string(&text)?),
+                Event::Comment(text) => Node::Comment(to_string(&text)?),
+                Event::Decl(decl) => Node::Decl(to_string(&decl)?),
+                Event::PI(text) => Node::PI(to_string(&text)?),
+                Event::DocType(text) => Node::DocType(to_string(&text)?),
+                Event::Eof => break,
+            };
+
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => nodes.push(node),
+            }
+            buf.clear();
+        }
+
+        if let Some(element) = stack.pop() {
+            return Err(quick_xml::Error::UnexpectedEof(element.name).into());
+        }
+
+        Ok(Self { nodes })
+    }
+
+    pub fn root(&self) -> Option<&Element> {
+        self.nodes.iter().find_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    pub fn root_mut(&mut self) -> Option<&mut Element> {
+        self.nodes.iter_mut().find_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    /// The root element, inserted with `name` if the document has none.
+    pub fn root_or_insert(&mut self, name: &str) -> &mut Element {
+        if self.root().is_none() {
+            self.nodes.push(Node::Element(Element::new(name)));
+        }
+        self.root_mut().expect("root was just inserted")
+    }
+
+    pub fn to_xml(&self) -> Result<String> {
+        let mut writer = Writer::new(Vec::new());
+        for node in &self.nodes {
+            node.write(&mut writer)?;
+        }
+        into_string(writer)
+    }
+}
+
+fn into_string(writer: Writer<Vec<u8>>) -> Result<String> {
+    String::from_utf8(writer.into_inner())
+        .map_err(|err| Error::Xml(quick_xml::Error::Utf8(err.utf8_error())))
+}
+
+fn to_string(bytes: &[u8]) -> Result<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_owned()),
+        Err(err) => Err(quick_xml::Error::Utf8(err).into()),
+    }
+}
+
+fn escape_str(s: &str) -> String {
+    match escape(s.as_bytes()) {
+        // Escaping only ever replaces ASCII characters with ASCII sequences.
+        Cow::Borrowed(_) => s.to_owned(),
+        Cow::Owned(escaped) => String::from_utf8(escaped).expect("escaped valid utf-8"),
+    }
+}
+
+fn unescape_str(s: &str) -> Cow<'_, str> {
+    match unescape(s.as_bytes()) {
+        Ok(Cow::Borrowed(_)) | Err(_) => Cow::Borrowed(s),
+        Ok(Cow::Owned(unescaped)) => match String::from_utf8(unescaped) {
+            Ok(unescaped) => Cow::Owned(unescaped),
+            Err(_) => Cow::Borrowed(s),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_preserves_formatting() {
+        let xml = "<?xml version=\"1.0\"?>\n<A x='1' y=\"a &amp; b\">\n\t<B/>\n\t<C></C>\n\t<!-- c -->\n\t<D>1 &lt; 2</D>\n</A>";
+        let doc = Document::parse(xml).unwrap();
+        assert_eq!(doc.to_xml().unwrap(), xml.replace("x='1'", "x=\"1\""));
+    }
+
+    #[test]
+    fn edit_attributes_and_text() {
+        let mut doc = Document::parse("<A><B x=\"1\"/><C>old</C></A>").unwrap();
+        let root = doc.root_mut().unwrap();
+        root.child_mut("B").unwrap().set_attribute("x", "\"2\"");
+        root.child_mut("C").unwrap().set_text("a < b");
+        root.child_or_insert("D").set_attribute("y", "3");
+
+        assert_eq!(
+            root.child_mut("B").unwrap().attribute("x").as_deref(),
+            Some("\"2\"")
+        );
+        assert_eq!(
+            doc.to_xml().unwrap(),
+            "<A><B x=\"&quot;2&quot;\"/><C>a &lt; b</C><D y=\"3\"/></A>"
+        );
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert!(Document::parse("<A><B></A>").is_err());
+        assert!(Document::parse("<A>").is_err());
+    }
+}
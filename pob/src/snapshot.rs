@@ -0,0 +1,677 @@
+//! Owned snapshot of everything a [`PathOfBuilding`] exposes.
+//!
+//! The snapshot is captured in a single pass over the build, all types are owned
+//! and serializable. It implements [`PathOfBuilding`] itself and can be used
+//! in place of the parsed build, e.g. as a `&dyn PathOfBuilding`.
+//!
+//! The serialized form is consumed by tooling outside of this crate,
+//! [`VERSION`] is increased with every incompatible change to it.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use shared::{Ascendancy, Bandit, Class, PantheonMajorGod, PantheonMinorGod};
+
+use crate::{
+    AlternateQuality, Calcs, Config, GemVariant, Keystone, MinionStats, PathOfBuilding, Stat,
+    StatValue,
+};
+
+/// Version of the snapshot format, stored in [`BuildSnapshot::version`].
+pub const VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildSnapshot {
+    /// Format version the snapshot was created with, see [`VERSION`].
+    pub version: u32,
+    pub level: u8,
+    pub class: Class,
+    pub ascendancy: Option<Ascendancy>,
+    pub bandit: Option<Bandit>,
+    pub pantheon_major_god: Option<PantheonMajorGod>,
+    pub pantheon_minor_god: Option<PantheonMinorGod>,
+    pub notes: String,
+    /// Raw values of all known player stats, by PoB name of the stat.
+    pub stats: BTreeMap<String, String>,
+    /// Raw values of all known minion stats, by PoB name of the stat.
+    pub minion_stats: BTreeMap<String, String>,
+    /// All known configuration options which have a value set, by PoB name of the option.
+    pub configs: BTreeMap<String, ConfigValue>,
+    pub main_skill_name: Option<String>,
+    /// Names of the support gems which support the main skill, including the names
+    /// of the gems they are a variant of.
+    pub main_skill_supports: Vec<String>,
+    pub minion: Option<Minion>,
+    pub skill_sets: Vec<SkillSet>,
+    pub item_sets: Vec<ItemSet>,
+    /// Items referenced by id, the equipped gear of all item sets
+    /// and the jewels socketed into the tree specs.
+    pub items: BTreeMap<u16, String>,
+    pub tree_specs: Vec<TreeSpec>,
+    /// Keystones granted by the active gear.
+    pub gear_keystones: Vec<String>,
+    pub party: Party,
+    pub calcs: Calcs,
+    pub import: Option<Import>,
+    pub trade_search_weights: Vec<TradeSearchWeight>,
+}
+
+impl BuildSnapshot {
+    pub fn new<P: PathOfBuilding + ?Sized>(pob: &P) -> Self {
+        let skill_sets = pob.skill_sets();
+        let tree_specs = pob.tree_specs();
+
+        let stats = |stat: for<'p> fn(&'p P, Stat<'static>) -> Option<&'p str>| {
+            Stat::ALL
+                .iter()
+                .filter_map(|&s| Some((name(s), stat(pob, s)?.to_owned())))
+                .collect()
+        };
+
+        let mut main_skill_supports = skill_sets
+            .iter()
+            .flat_map(|ss| &ss.skills)
+            .flat_map(|skill| &skill.gems)
+            .filter(|gem| gem.is_support)
+            .flat_map(|gem| [gem.name, gem.base_name])
+            .filter(|&name| pob.main_skill_supported_by(name))
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        main_skill_supports.sort_unstable();
+        main_skill_supports.dedup();
+
+        let gear_keystones = Keystone::ALL
+            .iter()
+            .filter(|&&keystone| pob.has_keystone_on_gear(keystone))
+            .map(|keystone| keystone.name().to_owned())
+            .collect();
+
+        let item_sets = pob.item_sets();
+        let items = item_sets
+            .iter()
+            .flat_map(|set| &set.gear.slots)
+            .map(|slot| (slot.item_id, slot.item.to_owned()))
+            .chain(
+                tree_specs
+                    .iter()
+                    .flat_map(|spec| &spec.sockets)
+                    .filter_map(|socket| {
+                        Some((socket.item_id, pob.item_by_id(socket.item_id)?.to_owned()))
+                    }),
+            )
+            .collect();
+
+        let party = pob.party();
+        let owned = |names: Vec<&str>| names.into_iter().map(ToOwned::to_owned).collect();
+
+        Self {
+            version: VERSION,
+            level: pob.level(),
+            class: pob.class(),
+            ascendancy: pob.ascendancy(),
+            bandit: pob.bandit(),
+            pantheon_major_god: pob.pantheon_major_god(),
+            pantheon_minor_god: pob.pantheon_minor_god(),
+            notes: pob.notes().to_owned(),
+            stats: stats(P::stat),
+            minion_stats: stats(P::minion_stat),
+            configs: pob
+                .configs()
+                .map(|(config, value)| (name(config), value))
+                .chain(
+                    pob.unknown_configs()
+                        .map(|(name, value)| (name.to_owned(), value)),
+                )
+                .filter_map(|(name, value)| Some((name, ConfigValue::new(value)?)))
+                .collect(),
+            main_skill_name: pob.main_skill_name().map(ToOwned::to_owned),
+            main_skill_supports,
+            minion: pob.minion().map(|minion| Minion {
+                id: minion.id.map(ToOwned::to_owned),
+                skill_index: minion.skill_index,
+            }),
+            skill_sets: skill_sets.iter().map(SkillSet::new).collect(),
+            item_sets: item_sets.iter().map(ItemSet::new).collect(),
+            items,
+            tree_specs: tree_specs.iter().map(TreeSpec::new).collect(),
+            gear_keystones,
+            party: Party {
+                auras: owned(party.auras),
+                curses: owned(party.curses),
+                links: owned(party.links),
+                enemy_conditions: owned(party.enemy_conditions),
+            },
+            calcs: pob.calcs(),
+            import: pob.import().map(|import| Import {
+                realm: import.realm.map(ToOwned::to_owned),
+                league: import.league.map(ToOwned::to_owned),
+                account_hash: import.account_hash.map(ToOwned::to_owned),
+                character_hash: import.character_hash.map(ToOwned::to_owned),
+            }),
+            trade_search_weights: pob
+                .trade_search_weights()
+                .into_iter()
+                .map(|weight| TradeSearchWeight {
+                    stat: weight.stat.to_owned(),
+                    weight: weight.weight,
+                })
+                .collect(),
+        }
+    }
+
+    fn active_tree_spec(&self) -> Option<&TreeSpec> {
+        self.tree_specs.iter().find(|spec| spec.active)
+    }
+}
+
+/// Value of a configuration option, see [`crate::ConfigValue`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    String(String),
+    Number(f32),
+    Bool(bool),
+}
+
+impl ConfigValue {
+    fn new(value: crate::ConfigValue) -> Option<Self> {
+        match value {
+            crate::ConfigValue::String(value) => Some(Self::String(value.to_owned())),
+            crate::ConfigValue::Number(value) => Some(Self::Number(value)),
+            crate::ConfigValue::Bool(value) => Some(Self::Bool(value)),
+            crate::ConfigValue::None => None,
+        }
+    }
+
+    fn as_value(&self) -> crate::ConfigValue<'_> {
+        match self {
+            Self::String(value) => crate::ConfigValue::String(value),
+            Self::Number(value) => crate::ConfigValue::Number(*value),
+            Self::Bool(value) => crate::ConfigValue::Bool(*value),
+        }
+    }
+}
+
+/// The minion of the main skill, its stats are in [`BuildSnapshot::minion_stats`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Minion {
+    pub id: Option<String>,
+    pub skill_index: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSet {
+    pub id: u16,
+    pub title: Option<String>,
+    pub skills: Vec<Skill>,
+    pub is_selected: bool,
+}
+
+impl SkillSet {
+    fn new(skill_set: &crate::SkillSet) -> Self {
+        Self {
+            id: skill_set.id,
+            title: skill_set.title.map(ToOwned::to_owned),
+            skills: skill_set.skills.iter().map(Skill::new).collect(),
+            is_selected: skill_set.is_selected,
+        }
+    }
+
+    fn as_skill_set(&self) -> crate::SkillSet<'_> {
+        crate::SkillSet {
+            id: self.id,
+            title: self.title.as_deref(),
+            skills: self.skills.iter().map(Skill::as_skill).collect(),
+            is_selected: self.is_selected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Skill {
+    pub is_selected: bool,
+    pub is_enabled: bool,
+    pub label: Option<String>,
+    pub slot: Option<String>,
+    pub granted_by: Option<String>,
+    pub gems: Vec<Gem>,
+}
+
+impl Skill {
+    fn new(skill: &crate::Skill) -> Self {
+        Self {
+            is_selected: skill.is_selected,
+            is_enabled: skill.is_enabled,
+            label: skill.label.map(ToOwned::to_owned),
+            slot: skill.slot.map(ToOwned::to_owned),
+            granted_by: skill.granted_by.map(ToOwned::to_owned),
+            gems: skill.gems.iter().map(Gem::new).collect(),
+        }
+    }
+
+    fn as_skill(&self) -> crate::Skill<'_> {
+        crate::Skill {
+            is_selected: self.is_selected,
+            is_enabled: self.is_enabled,
+            label: self.label.as_deref(),
+            slot: self.slot.as_deref(),
+            granted_by: self.granted_by.as_deref(),
+            gems: self.gems.iter().map(Gem::as_gem).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gem {
+    pub name: String,
+    pub skill_id: Option<String>,
+    pub gem_id: Option<String>,
+    pub quality_id: Option<String>,
+    pub variant: GemVariant,
+    pub base_name: String,
+    pub alternate_quality: Option<AlternateQuality>,
+    pub level: u8,
+    pub quality: u8,
+    pub is_enabled: bool,
+    pub is_active: bool,
+    pub is_support: bool,
+    pub is_selected: bool,
+}
+
+impl Gem {
+    fn new(gem: &crate::Gem) -> Self {
+        Self {
+            name: gem.name.to_owned(),
+            skill_id: gem.skill_id.map(ToOwned::to_owned),
+            gem_id: gem.gem_id.map(ToOwned::to_owned),
+            quality_id: gem.quality_id.map(ToOwned::to_owned),
+            variant: gem.variant,
+            base_name: gem.base_name.to_owned(),
+            alternate_quality: gem.alternate_quality,
+            level: gem.level,
+            quality: gem.quality,
+            is_enabled: gem.is_enabled,
+            is_active: gem.is_active,
+            is_support: gem.is_support,
+            is_selected: gem.is_selected,
+        }
+    }
+
+    fn as_gem(&self) -> crate::Gem<'_> {
+        crate::Gem {
+            name: &self.name,
+            skill_id: self.skill_id.as_deref(),
+            gem_id: self.gem_id.as_deref(),
+            quality_id: self.quality_id.as_deref(),
+            variant: self.variant,
+            base_name: &self.base_name,
+            alternate_quality: self.alternate_quality,
+            level: self.level,
+            quality: self.quality,
+            is_enabled: self.is_enabled,
+            is_active: self.is_active,
+            is_support: self.is_support,
+            is_selected: self.is_selected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemSet {
+    pub id: u16,
+    pub title: Option<String>,
+    /// Every slot which has an item, in the order Path of Building lists them.
+    pub slots: Vec<GearSlot>,
+    pub use_second_weapon_set: bool,
+    pub is_selected: bool,
+}
+
+impl ItemSet {
+    fn new(item_set: &crate::ItemSet) -> Self {
+        Self {
+            id: item_set.id,
+            title: item_set.title.map(ToOwned::to_owned),
+            slots: item_set
+                .gear
+                .slots
+                .iter()
+                .map(|slot| GearSlot {
+                    name: slot.name.to_owned(),
+                    item_id: slot.item_id,
+                })
+                .collect(),
+            use_second_weapon_set: item_set.use_second_weapon_set,
+            is_selected: item_set.is_selected,
+        }
+    }
+
+    fn as_item_set<'a>(&'a self, items: &'a BTreeMap<u16, String>) -> crate::ItemSet<'a> {
+        let mut gear = crate::Gear::default();
+        for slot in &self.slots {
+            let Some(content) = items.get(&slot.item_id) else {
+                continue;
+            };
+            let item = Some(content.as_str());
+            match slot.name.as_str() {
+                "Weapon 1" => gear.weapon1 = item,
+                "Weapon 2" => gear.weapon2 = item,
+                "Weapon 1 Swap" => gear.weapon1_swap = item,
+                "Weapon 2 Swap" => gear.weapon2_swap = item,
+                "Helmet" => gear.helmet = item,
+                "Body Armour" => gear.body_armour = item,
+                "Gloves" => gear.gloves = item,
+                "Boots" => gear.boots = item,
+                "Amulet" => gear.amulet = item,
+                "Ring 1" => gear.ring1 = item,
+                "Ring 2" => gear.ring2 = item,
+                "Belt" => gear.belt = item,
+                "Flask 1" => gear.flask1 = item,
+                "Flask 2" => gear.flask2 = item,
+                "Flask 3" => gear.flask3 = item,
+                "Flask 4" => gear.flask4 = item,
+                "Flask 5" => gear.flask5 = item,
+                _ => gear.sockets.push(content),
+            }
+            gear.slots.push(crate::GearSlot {
+                name: &slot.name,
+                item_id: slot.item_id,
+                item: content,
+            });
+        }
+
+        crate::ItemSet {
+            id: self.id,
+            title: self.title.as_deref(),
+            gear,
+            use_second_weapon_set: self.use_second_weapon_set,
+            is_selected: self.is_selected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GearSlot {
+    /// Name of the slot as used by Path of Building, e.g. `Helmet`.
+    pub name: String,
+    /// Id of the equipped item in [`BuildSnapshot::items`].
+    pub item_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeSpec {
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub version: Option<String>,
+    pub class_id: Option<u8>,
+    pub ascendancy_id: Option<u8>,
+    pub alternate_ascendancy_id: Option<u8>,
+    pub nodes: Vec<u32>,
+    /// Mastery effects as `(node, effect)` pairs.
+    pub mastery_effects: Vec<(u32, u32)>,
+    /// Jewel sockets with the id of the socketed item in [`BuildSnapshot::items`].
+    pub sockets: Vec<crate::Socket>,
+    pub overrides: Vec<Override>,
+    pub active: bool,
+}
+
+impl TreeSpec {
+    fn new(spec: &crate::TreeSpec) -> Self {
+        Self {
+            title: spec.title.map(ToOwned::to_owned),
+            url: spec.url.map(ToOwned::to_owned),
+            version: spec.version.map(ToOwned::to_owned),
+            class_id: spec.class_id,
+            ascendancy_id: spec.ascendancy_id,
+            alternate_ascendancy_id: spec.alternate_ascendancy_id,
+            nodes: spec.nodes.to_vec(),
+            mastery_effects: spec.mastery_effects.to_vec(),
+            sockets: spec.sockets.clone(),
+            overrides: spec
+                .overrides
+                .iter()
+                .map(|o| Override {
+                    name: o.name.to_owned(),
+                    node_id: o.node_id,
+                    effect: o.effect.to_owned(),
+                })
+                .collect(),
+            active: spec.active,
+        }
+    }
+
+    fn as_tree_spec(&self) -> crate::TreeSpec<'_> {
+        crate::TreeSpec {
+            title: self.title.as_deref(),
+            url: self.url.as_deref(),
+            version: self.version.as_deref(),
+            class_id: self.class_id,
+            ascendancy_id: self.ascendancy_id,
+            alternate_ascendancy_id: self.alternate_ascendancy_id,
+            nodes: &self.nodes,
+            mastery_effects: &self.mastery_effects,
+            sockets: self.sockets.clone(),
+            overrides: self
+                .overrides
+                .iter()
+                .map(|o| crate::Override {
+                    name: &o.name,
+                    node_id: o.node_id,
+                    effect: &o.effect,
+                })
+                .collect(),
+            active: self.active,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Override {
+    pub name: String,
+    pub node_id: u32,
+    pub effect: String,
+}
+
+/// Buffs of party members, see [`crate::Party`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Party {
+    pub auras: Vec<String>,
+    pub curses: Vec<String>,
+    pub links: Vec<String>,
+    pub enemy_conditions: Vec<String>,
+}
+
+/// The character the build was imported from, see [`crate::Import`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Import {
+    pub realm: Option<String>,
+    pub league: Option<String>,
+    pub account_hash: Option<String>,
+    pub character_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeSearchWeight {
+    /// PoB name of the stat, e.g. `FullDPS`.
+    pub stat: String,
+    pub weight: f32,
+}
+
+impl PathOfBuilding for BuildSnapshot {
+    fn level(&self) -> u8 {
+        self.level
+    }
+
+    fn class(&self) -> Class {
+        self.class
+    }
+
+    fn ascendancy(&self) -> Option<Ascendancy> {
+        self.ascendancy
+    }
+
+    fn bandit(&self) -> Option<Bandit> {
+        self.bandit
+    }
+
+    fn pantheon_major_god(&self) -> Option<PantheonMajorGod> {
+        self.pantheon_major_god
+    }
+
+    fn pantheon_minor_god(&self) -> Option<PantheonMinorGod> {
+        self.pantheon_minor_god
+    }
+
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    fn stat(&self, stat: Stat) -> Option<&str> {
+        self.stats.get(<&str>::from(stat)).map(String::as_str)
+    }
+
+    fn minion_stat(&self, stat: Stat) -> Option<&str> {
+        self.minion_stats
+            .get(<&str>::from(stat))
+            .map(String::as_str)
+    }
+
+    fn stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_> {
+        Box::new(typed_stats(&self.stats))
+    }
+
+    fn minion_stats(&self) -> Box<dyn Iterator<Item = (Stat<'_>, StatValue)> + '_> {
+        Box::new(typed_stats(&self.minion_stats))
+    }
+
+    fn config(&self, config: Config) -> crate::ConfigValue<'_> {
+        self.configs
+            .get(<&str>::from(config))
+            .map(ConfigValue::as_value)
+            .unwrap_or(crate::ConfigValue::None)
+    }
+
+    fn configs(&self) -> Box<dyn Iterator<Item = (Config, crate::ConfigValue<'_>)> + '_> {
+        let configs = self
+            .configs
+            .iter()
+            .filter_map(|(name, value)| Some((Config::from_name(name)?, value.as_value())));
+        Box::new(configs)
+    }
+
+    fn unknown_configs(&self) -> Box<dyn Iterator<Item = (&str, crate::ConfigValue<'_>)> + '_> {
+        let configs = self
+            .configs
+            .iter()
+            .filter(|(name, _)| Config::from_name(name).is_none())
+            .map(|(name, value)| (name.as_str(), value.as_value()));
+        Box::new(configs)
+    }
+
+    fn main_skill_name(&self) -> Option<&str> {
+        self.main_skill_name.as_deref()
+    }
+
+    fn minion(&self) -> Option<crate::Minion<'_>> {
+        let minion = self.minion.as_ref()?;
+        Some(crate::Minion {
+            id: minion.id.as_deref(),
+            summoned_by: self.main_skill_name(),
+            skill_index: minion.skill_index,
+            stats: MinionStats::from_stats(self.minion_stats()),
+        })
+    }
+
+    fn main_skill_supported_by(&self, skill: &str) -> bool {
+        self.main_skill_supports.iter().any(|name| name == skill)
+    }
+
+    fn skill_sets(&self) -> Vec<crate::SkillSet<'_>> {
+        self.skill_sets.iter().map(SkillSet::as_skill_set).collect()
+    }
+
+    fn item_by_id(&self, id: u16) -> Option<&str> {
+        self.items.get(&id).map(String::as_str)
+    }
+
+    fn item_sets(&self) -> Vec<crate::ItemSet<'_>> {
+        self.item_sets
+            .iter()
+            .map(|set| set.as_item_set(&self.items))
+            .collect()
+    }
+
+    fn tree_specs(&self) -> Vec<crate::TreeSpec<'_>> {
+        self.tree_specs.iter().map(TreeSpec::as_tree_spec).collect()
+    }
+
+    fn has_tree_node(&self, node: u32) -> bool {
+        self.active_tree_spec()
+            .map(|spec| spec.nodes.contains(&node))
+            .unwrap_or(false)
+    }
+
+    fn has_keystone(&self, keystone: Keystone) -> bool {
+        keystone.node().is_some_and(|node| self.has_tree_node(node))
+            || self.has_keystone_on_gear(keystone)
+    }
+
+    fn has_keystone_in(&self, spec: &crate::TreeSpec, keystone: Keystone) -> bool {
+        keystone
+            .node()
+            .is_some_and(|node| spec.nodes.contains(&node))
+            || self.has_keystone_on_gear(keystone)
+    }
+
+    fn has_keystone_on_gear(&self, keystone: Keystone) -> bool {
+        self.gear_keystones.iter().any(|k| k == keystone.name())
+    }
+
+    fn party(&self) -> crate::Party<'_> {
+        crate::Party {
+            auras: borrowed(&self.party.auras),
+            curses: borrowed(&self.party.curses),
+            links: borrowed(&self.party.links),
+            enemy_conditions: borrowed(&self.party.enemy_conditions),
+        }
+    }
+
+    fn calcs(&self) -> Calcs {
+        self.calcs
+    }
+
+    fn import(&self) -> Option<crate::Import<'_>> {
+        let import = self.import.as_ref()?;
+        Some(crate::Import {
+            realm: import.realm.as_deref(),
+            league: import.league.as_deref(),
+            account_hash: import.account_hash.as_deref(),
+            character_hash: import.character_hash.as_deref(),
+        })
+    }
+
+    fn trade_search_weights(&self) -> Vec<crate::TradeSearchWeight<'_>> {
+        self.trade_search_weights
+            .iter()
+            .map(|weight| crate::TradeSearchWeight {
+                stat: &weight.stat,
+                weight: weight.weight,
+            })
+            .collect()
+    }
+}
+
+fn name<T: Into<&'static str>>(value: T) -> String {
+    value.into().to_owned()
+}
+
+fn borrowed(names: &[String]) -> Vec<&str> {
+    names.iter().map(String::as_str).collect()
+}
+
+fn typed_stats(
+    stats: &BTreeMap<String, String>,
+) -> impl Iterator<Item = (Stat<'_>, StatValue)> + '_ {
+    stats.iter().filter_map(|(name, value)| {
+        let stat = Stat::from_name_or_other(name);
+        Some((stat, stat.parse_value(value)?))
+    })
+}
@@ -1,125 +1,155 @@
-#[derive(Debug, Copy, Clone)]
-#[non_exhaustive]
-pub enum Stat {
-    Armour,
-    AttackDodgeChance,
-    AverageDamage,
-    BlockChance,
-    ChaosResistance,
-    CombinedDps,
-    ColdResistance,
-    CritChance,
-    CritMultiplier,
-    Dexterity,
-    EnduranceChargesMax,
-    EnergyShield,
-    EnergyShieldInc,
-    Evasion,
-    FireResistance,
-    FullDps,
-    Intelligence,
-    Life,
-    LifeInc,
-    LifeUnreserved,
-    LifeUnreservedPercent,
-    LightningResistance,
-    HitChance,
-    HitRate,
-    Mana,
-    ManaInc,
-    ManaUnreserved,
-    MaxHitChaos,
-    MaxHitCold,
-    MaxHitFire,
-    MaxHitLightning,
-    MaxHitPhysical,
-    MeleeEvadeChance,
-    PhysicalDamageReduction,
-    Speed,
-    SpellBlockChance,
-    SpellDodgeChance,
-    SpellSuppressionChance,
-    Strength,
-    TotalEhp,
-    Ward,
-    Custom(&'static str),
+use std::fmt;
+
+include!(concat!(env!("OUT_DIR"), "/stats.rs"));
+
+impl<'a> Stat<'a> {
+    /// Looks up a stat by the name Path of Building uses for it, e.g. `CombinedDPS`.
+    pub fn from_name(name: &str) -> Option<Stat<'static>> {
+        STATS.get(name).copied()
+    }
+
+    /// Like [`Self::from_name`] but keeps unknown stats as [`Stat::Other`].
+    pub fn from_name_or_other(name: &'a str) -> Self {
+        Self::from_name(name).unwrap_or(Self::Other(name))
+    }
+
+    /// Parses a raw stat value into a typed value according to the kind of the stat.
+    pub fn parse_value(&self, value: &str) -> Option<StatValue> {
+        let value: f64 = value.parse().ok()?;
+        let value = match self.kind() {
+            StatKind::Integer => StatValue::Integer(value.round() as i64),
+            StatKind::Percent => StatValue::Percent(value),
+            StatKind::Float => StatValue::Float(value),
+            StatKind::Rate => StatValue::Rate(value),
+        };
+        Some(value)
+    }
 }
 
-impl Stat {
-    fn name(&self) -> &'static str {
+/// The unit of a stat.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StatKind {
+    /// Whole numbers, e.g. life or attributes.
+    Integer,
+    /// Percentages, e.g. resistances or crit chance.
+    Percent,
+    /// Fractional values, e.g. average hit or cooldowns.
+    Float,
+    /// Values per second, e.g. DPS or regeneration.
+    Rate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StatValue {
+    Integer(i64),
+    Percent(f64),
+    Float(f64),
+    Rate(f64),
+}
+
+impl StatValue {
+    pub fn kind(&self) -> StatKind {
         match self {
-            Self::Armour => "Armour",
-            Self::AttackDodgeChance => "AttackDodgeChance",
-            Self::AverageDamage => "AverageDamage",
-            Self::BlockChance => "BlockChance",
-            Self::ChaosResistance => "ChaosResist",
-            Self::CombinedDps => "CombinedDPS",
-            Self::ColdResistance => "ColdResist",
-            Self::CritChance => "CritChance",
-            Self::CritMultiplier => "CritMultiplier",
-            Self::Dexterity => "Dex",
-            Self::EnduranceChargesMax => "EnduranceChargesMax",
-            Self::EnergyShield => "EnergyShield",
-            Self::EnergyShieldInc => "Spec:EnergyShieldInc",
-            Self::Evasion => "Evasion",
-            Self::FireResistance => "FireResist",
-            Self::FullDps => "FullDPS",
-            Self::Intelligence => "Int",
-            Self::Life => "Life",
-            Self::LifeInc => "Spec:LifeInc",
-            Self::LifeUnreserved => "LifeUnreserved",
-            Self::LifeUnreservedPercent => "LifeUnreservedPercent",
-            Self::LightningResistance => "LightningResist",
-            Self::HitChance => "HitChance",
-            Self::HitRate => "HitSpeed",
-            Self::Mana => "Mana",
-            Self::ManaInc => "Spec:ManaInc",
-            Self::ManaUnreserved => "ManaUnreserved",
-            Self::MaxHitChaos => "ChaosMaximumHitTaken",
-            Self::MaxHitCold => "ColdMaximumHitTaken",
-            Self::MaxHitFire => "FireMaximumHitTaken",
-            Self::MaxHitLightning => "LightningMaximumHitTaken",
-            Self::MaxHitPhysical => "PhysicalMaximumHitTaken",
-            Self::MeleeEvadeChance => "MeleeEvadeChance",
-            Self::PhysicalDamageReduction => "PhysicalDamageReduction",
-            Self::Speed => "Speed",
-            Self::SpellBlockChance => "SpellBlockChance",
-            Self::SpellDodgeChance => "SpellDodgeChance",
-            Self::SpellSuppressionChance => "SpellSuppressionChance",
-            Self::Strength => "Str",
-            Self::TotalEhp => "TotalEHP",
-            Self::Ward => "Ward",
-            Self::Custom(s) => s,
+            Self::Integer(_) => StatKind::Integer,
+            Self::Percent(_) => StatKind::Percent,
+            Self::Float(_) => StatKind::Float,
+            Self::Rate(_) => StatKind::Rate,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::Integer(value) => value as f64,
+            Self::Percent(value) | Self::Float(value) | Self::Rate(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for StatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Percent(value) => write!(f, "{}%", Trimmed(value)),
+            Self::Float(value) => write!(f, "{}", Trimmed(value)),
+            Self::Rate(value) => write!(f, "{}/s", Trimmed(value)),
         }
     }
 }
 
-impl From<Stat> for &'static str {
-    fn from(stat: Stat) -> Self {
+/// Formats a float with at most two decimal places and without trailing zeros.
+struct Trimmed(f64);
+
+impl fmt::Display for Trimmed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = format!("{:.2}", self.0);
+        let value = value.trim_end_matches('0').trim_end_matches('.');
+        f.write_str(value)
+    }
+}
+
+impl<'a> From<Stat<'a>> for &'a str {
+    fn from(stat: Stat<'a>) -> Self {
         stat.name()
     }
 }
 
-impl PartialEq<str> for Stat {
+impl PartialEq<str> for Stat<'_> {
     fn eq(&self, other: &str) -> bool {
         self.name() == other
     }
 }
 
-impl PartialEq<Stat> for &str {
-    fn eq(&self, other: &Stat) -> bool {
+impl PartialEq<Stat<'_>> for &str {
+    fn eq(&self, other: &Stat<'_>) -> bool {
         &other == self
     }
 }
 
-impl PartialEq<String> for Stat {
+impl PartialEq<String> for Stat<'_> {
     fn eq(&self, other: &String) -> bool {
         self.name() == other
     }
 }
 
-impl PartialEq<Stat> for String {
-    fn eq(&self, other: &Stat) -> bool {
+impl PartialEq<Stat<'_>> for String {
+    fn eq(&self, other: &Stat<'_>) -> bool {
         other == self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        for stat in Stat::ALL {
+            assert_eq!(Some(*stat), Stat::from_name(stat.name()));
+        }
+        assert_eq!(Some(Stat::CombinedDps), Stat::from_name("CombinedDPS"));
+        assert_eq!(Some(Stat::LifeInc), Stat::from_name("Spec:LifeInc"));
+        assert_eq!(None, Stat::from_name("NotAStat"));
+        assert_eq!(
+            Stat::Other("NotAStat"),
+            Stat::from_name_or_other("NotAStat")
+        );
+    }
+
+    #[test]
+    fn typed_values() {
+        assert_eq!(
+            Some(StatValue::Integer(5123)),
+            Stat::Life.parse_value("5122.8")
+        );
+        assert_eq!(
+            Some(StatValue::Percent(75.0)),
+            Stat::FireResistance.parse_value("75")
+        );
+        assert_eq!(None, Stat::Life.parse_value("nan?"));
+
+        assert_eq!("5123", StatValue::Integer(5123).to_string());
+        assert_eq!("75%", StatValue::Percent(75.0).to_string());
+        assert_eq!("1.5/s", StatValue::Rate(1.5).to_string());
+        assert_eq!("0.33", StatValue::Float(1.0 / 3.0).to_string());
+    }
+}
@@ -0,0 +1,449 @@
+//! Codec for the passive tree URLs of the official website,
+//! e.g. `https://www.pathofexile.com/passive-skill-tree/3.19.0/AAAABgMBAA...`.
+//!
+//! The last path segment is the url safe base64 encoded tree:
+//!
+//! | bytes      | content                                                    |
+//! |------------|------------------------------------------------------------|
+//! | 4          | format version, big endian                                 |
+//! | 1          | class id                                                   |
+//! | 1          | ascendancy id, the upper 6 bits are the alternate ascendancy |
+//! | 1 + 2 * n  | node count followed by the node ids                         |
+//! | 1 + 2 * n  | cluster node count followed by the node ids minus `65536`   |
+//! | 1 + 4 * n  | mastery count followed by pairs of effect id and node id    |
+//!
+//! Cluster nodes are part of the format since version 5, masteries since version 6.
+use crate::TreeSpec;
+
+const PREFIX: &str = "https://www.pathofexile.com/passive-skill-tree/";
+const PATHS: &[&str] = &["passive-skill-tree/", "fullscreen-passive-skill-tree/"];
+const VERSION: u32 = 6;
+/// Ids of cluster jewel nodes start at this id.
+const CLUSTER_NODE_OFFSET: u32 = 65536;
+
+/// Classes in the order of their ids.
+const CLASSES: [&str; 7] = [
+    "Scion", "Marauder", "Ranger", "Witch", "Duelist", "Templar", "Shadow",
+];
+/// Ascendancies of the classes in the order of their ids, starting at id `1`.
+const ASCENDANCIES: [&[&str]; 7] = [
+    &["Ascendant"],
+    &["Juggernaut", "Berserker", "Chieftain"],
+    &["Raider", "Deadeye", "Pathfinder"],
+    &["Occultist", "Elementalist", "Necromancer"],
+    &["Slayer", "Gladiator", "Champion"],
+    &["Inquisitor", "Hierophant", "Guardian"],
+    &["Assassin", "Trickster", "Saboteur"],
+];
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid passive tree url, {0}")]
+pub struct InvalidTreeUrl(&'static str);
+
+/// A passive tree decoded from an URL of the official website.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TreeUrl {
+    /// Game version of the tree in the URL, e.g. `3.19.0`, links without a
+    /// version are for the current tree.
+    pub game_version: Option<String>,
+    pub class_id: u8,
+    pub ascendancy_id: u8,
+    pub alternate_ascendancy_id: u8,
+    pub nodes: Vec<u32>,
+    /// Nodes added by cluster jewels, ids start at `65536`.
+    pub cluster_nodes: Vec<u32>,
+    /// Selected mastery effects as `(node_id, effect_id)`, like [`TreeSpec::mastery_effects`].
+    pub mastery_effects: Vec<(u32, u32)>,
+}
+
+impl TreeUrl {
+    /// Decodes a passive tree URL, the URL can also be just the encoded tree.
+    pub fn parse(url: &str) -> Result<Self, InvalidTreeUrl> {
+        let url = url.trim();
+        let url = url.split(['?', '#']).next().unwrap_or(url);
+
+        let path = PATHS
+            .iter()
+            .find_map(|path| url.split_once(path).map(|(_, rest)| rest))
+            .unwrap_or(url);
+
+        let (game_version, data) = match path.rsplit_once('/') {
+            Some((version, data)) => (Some(version), data),
+            None => (None, path),
+        };
+        let game_version = game_version
+            .filter(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            .map(str::to_owned);
+
+        let data = data
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_");
+        let data = base64::decode_config(data, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| InvalidTreeUrl("expected base64 encoded tree"))?;
+
+        let mut reader = Reader(&data);
+        let version = reader.u32()?;
+        if !(4..=VERSION).contains(&version) {
+            return Err(InvalidTreeUrl("unsupported version"));
+        }
+
+        let class_id = reader.u8()?;
+        if class_id as usize >= CLASSES.len() {
+            return Err(InvalidTreeUrl("unknown class"));
+        }
+        let ascendancy = reader.u8()?;
+
+        let nodes = reader.list(|r| r.u16().map(u32::from))?;
+        let cluster_nodes = match version {
+            5.. => reader.list(|r| r.u16().map(|id| id as u32 + CLUSTER_NODE_OFFSET))?,
+            _ => Vec::new(),
+        };
+        let mastery_effects = match version {
+            6.. => reader.list(|r| {
+                let effect = r.u16()? as u32;
+                let node = r.u16()? as u32;
+                Ok((node, effect))
+            })?,
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            game_version,
+            class_id,
+            ascendancy_id: ascendancy & 0b11,
+            alternate_ascendancy_id: ascendancy >> 2,
+            nodes,
+            cluster_nodes,
+            mastery_effects,
+        })
+    }
+
+    /// The tree of a spec, class and ascendancy start nodes are not part of the URL.
+    pub fn from_spec(spec: &TreeSpec) -> Self {
+        let start_nodes = poe_tree::start_nodes(crate::passives::tree_version(spec));
+        let (cluster_nodes, nodes) = spec
+            .nodes
+            .iter()
+            .filter(|node| !start_nodes.contains(node))
+            .partition(|&&node| node >= CLUSTER_NODE_OFFSET);
+
+        let game_version = spec.version.and_then(|version| {
+            let (major, minor) = version.split_once('_')?;
+            let is_numeric = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+            (is_numeric(major) && is_numeric(minor)).then(|| format!("{major}.{minor}.0"))
+        });
+
+        Self {
+            game_version,
+            class_id: spec.class_id.unwrap_or(0),
+            ascendancy_id: spec.ascendancy_id.unwrap_or(0),
+            alternate_ascendancy_id: spec.alternate_ascendancy_id.unwrap_or(0),
+            nodes,
+            cluster_nodes,
+            mastery_effects: spec.mastery_effects.to_vec(),
+        }
+    }
+
+    /// Path of Building's tree version of the URL's game version, e.g. `3_19`.
+    pub fn tree_version(&self) -> Option<String> {
+        let mut parts = self.game_version.as_deref()?.split('.');
+        Some(format!("{}_{}", parts.next()?, parts.next()?))
+    }
+
+    /// Name of the class, e.g. `Witch`.
+    pub fn class_name(&self) -> &'static str {
+        CLASSES
+            .get(self.class_id as usize)
+            .copied()
+            .unwrap_or(CLASSES[0])
+    }
+
+    /// Name of the ascendancy, e.g. `Occultist`, `None` if no ascendancy is selected.
+    pub fn ascendancy_name(&self) -> Option<&'static str> {
+        let index = (self.ascendancy_id as usize).checked_sub(1)?;
+        ASCENDANCIES
+            .get(self.class_id as usize)?
+            .get(index)
+            .copied()
+    }
+
+    /// The alternate ascendancy, e.g. `Warden`, `None` if no alternate ascendancy is selected.
+    pub fn alternate_ascendancy(&self) -> Option<shared::AlternateAscendancy> {
+        shared::AlternateAscendancy::from_id(self.alternate_ascendancy_id)
+    }
+
+    /// Whether the URL and the spec allocate the same nodes and masteries.
+    pub fn matches(&self, spec: &TreeSpec) -> bool {
+        fn sorted<T: Ord + Clone>(items: &[T]) -> Vec<T> {
+            let mut items = items.to_vec();
+            items.sort_unstable();
+            items
+        }
+
+        let other = Self::from_spec(spec);
+        sorted(&self.nodes) == sorted(&other.nodes)
+            && sorted(&self.cluster_nodes) == sorted(&other.cluster_nodes)
+            && sorted(&self.mastery_effects) == sorted(&other.mastery_effects)
+    }
+
+    /// Attributes of a Path of Building `Spec` element with this tree.
+    pub(crate) fn spec_attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![
+            ("classId", self.class_id.to_string()),
+            ("ascendClassId", self.ascendancy_id.to_string()),
+            (
+                "secondaryAscendClassId",
+                self.alternate_ascendancy_id.to_string(),
+            ),
+        ];
+        if let Some(version) = self.tree_version() {
+            attributes.push(("treeVersion", version));
+        }
+
+        // Empty lists are left out, PoB does not accept them.
+        let nodes = self
+            .nodes
+            .iter()
+            .chain(&self.cluster_nodes)
+            .map(|node| node.to_string())
+            .collect::<Vec<_>>();
+        if !nodes.is_empty() {
+            attributes.push(("nodes", nodes.join(",")));
+        }
+        let mastery_effects = self
+            .mastery_effects
+            .iter()
+            .map(|(node, effect)| format!("{{{node},{effect}}}"))
+            .collect::<Vec<_>>();
+        if !mastery_effects.is_empty() {
+            attributes.push(("masteryEffects", mastery_effects.join(",")));
+        }
+
+        attributes
+    }
+
+    /// Encodes the tree as an URL of the official website.
+    ///
+    /// Fails if a list has more than 255 entries or a node id does not fit the format.
+    pub fn to_url(&self) -> Result<String, InvalidTreeUrl> {
+        let mut url = PREFIX.to_owned();
+        if let Some(version) = &self.game_version {
+            url.push_str(version);
+            url.push('/');
+        }
+        url.push_str(&base64::encode_config(self.encode()?, base64::URL_SAFE));
+        Ok(url)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, InvalidTreeUrl> {
+        let mut writer = Writer(Vec::with_capacity(
+            9 + 2 * self.nodes.len()
+                + 2 * self.cluster_nodes.len()
+                + 4 * self.mastery_effects.len(),
+        ));
+
+        writer.0.extend(VERSION.to_be_bytes());
+        writer.0.push(self.class_id);
+        writer
+            .0
+            .push(self.ascendancy_id | self.alternate_ascendancy_id << 2);
+
+        writer.list(&self.nodes, |w, &node| w.u16(node))?;
+        writer.list(&self.cluster_nodes, |w, &node| {
+            let node = node
+                .checked_sub(CLUSTER_NODE_OFFSET)
+                .ok_or(InvalidTreeUrl("cluster node id below 65536"))?;
+            w.u16(node)
+        })?;
+        writer.list(&self.mastery_effects, |w, &(node, effect)| {
+            w.u16(effect)?;
+            w.u16(node)
+        })?;
+
+        Ok(writer.0)
+    }
+}
+
+impl std::str::FromStr for TreeUrl {
+    type Err = InvalidTreeUrl;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], InvalidTreeUrl> {
+        if self.0.len() < N {
+            return Err(InvalidTreeUrl("unexpected end of tree"));
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, InvalidTreeUrl> {
+        self.take::<1>().map(|[b]| b)
+    }
+
+    fn u16(&mut self) -> Result<u16, InvalidTreeUrl> {
+        self.take().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, InvalidTreeUrl> {
+        self.take().map(u32::from_be_bytes)
+    }
+
+    /// A list prefixed with its length.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, InvalidTreeUrl>,
+    ) -> Result<Vec<T>, InvalidTreeUrl> {
+        let len = self.u8()?;
+        (0..len).map(|_| item(self)).collect()
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u16(&mut self, value: u32) -> Result<(), InvalidTreeUrl> {
+        let value = u16::try_from(value).map_err(|_| InvalidTreeUrl("id does not fit 16 bits"))?;
+        self.0.extend(value.to_be_bytes());
+        Ok(())
+    }
+
+    /// A list prefixed with its length.
+    fn list<T>(
+        &mut self,
+        items: &[T],
+        mut item: impl FnMut(&mut Self, &T) -> Result<(), InvalidTreeUrl>,
+    ) -> Result<(), InvalidTreeUrl> {
+        let len = u8::try_from(items.len()).map_err(|_| InvalidTreeUrl("more than 255 entries"))?;
+        self.0.push(len);
+        items.iter().try_for_each(|i| item(self, i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V319_URL: &str = "https://www.pathofexile.com/passive-skill-tree/AAAABgAAD0UKIhyIWjXW_go1krc-_o9ewNi92E0wXsRwMZ5N4wACL1ciHLoaMF4=";
+
+    #[test]
+    fn parse_url() {
+        let tree = TreeUrl::parse(V319_URL).unwrap();
+        assert_eq!(None, tree.game_version);
+        assert_eq!("Scion", tree.class_name());
+        assert_eq!(None, tree.ascendancy_name());
+        assert_eq!(15, tree.nodes.len());
+        assert!(tree.nodes.contains(&8732));
+        assert!(tree.cluster_nodes.is_empty());
+        assert_eq!(vec![(8732, 12119), (12382, 47642)], tree.mastery_effects);
+
+        assert_eq!(V319_URL, tree.to_url().unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        let tree = TreeUrl {
+            game_version: Some("3.22.0".to_owned()),
+            class_id: 3,
+            ascendancy_id: 1,
+            alternate_ascendancy_id: 2,
+            nodes: vec![1, 2, 65535],
+            cluster_nodes: vec![65536, 70000],
+            mastery_effects: vec![(100, 200)],
+        };
+        let url = tree.to_url().unwrap();
+        assert!(url.starts_with("https://www.pathofexile.com/passive-skill-tree/3.22.0/"));
+
+        let parsed = TreeUrl::parse(&url).unwrap();
+        assert_eq!(tree, parsed);
+        assert_eq!(Some("3_22".to_owned()), parsed.tree_version());
+        assert_eq!(Some("Occultist"), parsed.ascendancy_name());
+
+        let fullscreen = url.replace("passive-skill-tree", "fullscreen-passive-skill-tree");
+        assert_eq!(tree, TreeUrl::parse(&fullscreen).unwrap());
+        assert_eq!(
+            tree,
+            TreeUrl::parse(&format!("{url}?accountName=foo")).unwrap()
+        );
+    }
+
+    #[test]
+    fn alternate_ascendancy_round_trip() {
+        let tree = TreeUrl {
+            game_version: Some("3.15.0".to_owned()),
+            class_id: 3,
+            ascendancy_id: 1,
+            alternate_ascendancy_id: 3,
+            // `Damage and Energy Shield` and `Blood Siphon`.
+            nodes: vec![38129, 25058],
+            ..Default::default()
+        };
+        let url = tree.to_url().unwrap();
+
+        let parsed = TreeUrl::parse(&url).unwrap();
+        assert_eq!(tree, parsed);
+        assert_eq!(Some("Occultist"), parsed.ascendancy_name());
+        assert_eq!(
+            Some(shared::AlternateAscendancy::Primalist),
+            parsed.alternate_ascendancy()
+        );
+
+        // Path of Building allocates the Witch and Occultist start nodes, they are not part of the URL.
+        let nodes = [54447, 18378, 38129, 25058];
+        let spec = TreeSpec {
+            title: None,
+            url: Some(&url),
+            version: Some("3_15"),
+            class_id: Some(3),
+            ascendancy_id: Some(1),
+            alternate_ascendancy_id: Some(3),
+            nodes: &nodes,
+            mastery_effects: &[],
+            sockets: Vec::new(),
+            overrides: Vec::new(),
+            active: true,
+        };
+        assert_eq!(tree, TreeUrl::from_spec(&spec));
+        assert_eq!(url, TreeUrl::from_spec(&spec).to_url().unwrap());
+        assert_eq!(Some(true), spec.url_matches_nodes());
+    }
+
+    #[test]
+    fn invalid_url() {
+        assert!(TreeUrl::parse("https://www.pathofexile.com/passive-skill-tree/").is_err());
+        assert!(TreeUrl::parse("not a tree").is_err());
+        // Version 7
+        assert!(TreeUrl::parse("AAAABwAAAAAA").is_err());
+        // Truncated node list
+        assert!(TreeUrl::parse("AAAABgAAAgAB").is_err());
+    }
+
+    #[test]
+    fn encode_invalid() {
+        let too_many = TreeUrl {
+            nodes: (0..256).collect(),
+            ..Default::default()
+        };
+        assert!(too_many.to_url().is_err());
+
+        let not_a_cluster_node = TreeUrl {
+            cluster_nodes: vec![100],
+            ..Default::default()
+        };
+        assert!(not_a_cluster_node.to_url().is_err());
+
+        let too_large = TreeUrl {
+            nodes: vec![65536],
+            ..Default::default()
+        };
+        assert!(too_large.to_url().is_err());
+    }
+}
@@ -1,30 +1,283 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
-use flate2::bufread::ZlibDecoder;
+use flate2::bufread::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use flate2::write::ZlibEncoder;
 
 use crate::{Error, Result};
 
+/// Upper bound for the size of a decompressed build, protects against compression bombs.
+const MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Decodes an export code into the XML of the build.
+///
+/// Besides codes exported by PoB this also accepts codes which were mangled on the way,
+/// see [`normalize`], and the raw XML of a build. Raw XML is not decoded, it is returned
+/// without the surrounding whitespace after checking it is a build.
 pub fn decompress(data: &str) -> Result<String> {
-    let data = decode(data)?;
-    deflate(&data)
+    decode_input(data).map(|(xml, _)| xml)
+}
+
+/// Compresses a build into the format PoB uses for exports, the inverse of [`decompress`].
+pub fn compress(data: &str) -> Result<String> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(data.as_bytes())
+        .map_err(Error::Compress)?;
+    let data = encoder.finish().map_err(Error::Compress)?;
+    Ok(base64::encode_config(data, base64::URL_SAFE))
 }
 
-fn decode(data: &str) -> Result<Vec<u8>> {
-    base64::decode_config(data.trim(), base64::URL_SAFE).map_err(Error::Base64Decode)
+/// Normalizes user input into an export code as PoB creates it.
+///
+/// Accepts UTF-8 or UTF-16 (with a byte order mark) text, containing either the raw XML
+/// of a build or an export code. Export codes may use standard or URL-safe base64,
+/// contain whitespace or line breaks and be compressed with zlib, gzip or raw deflate.
+///
+/// Input which already is a valid export code is returned as is, without the surrounding whitespace.
+///
+/// Returns the export code and the decoded XML of the build.
+pub fn normalize(data: &[u8]) -> Result<(String, String)> {
+    let text = decode_text(data).ok_or(Error::InvalidEncoding)??;
+
+    match decode_input(&text)? {
+        (xml, true) => Ok((trim(&text).to_owned(), xml)),
+        (xml, false) => Ok((compress(&xml)?, xml)),
+    }
 }
 
-fn deflate(inp: &[u8]) -> Result<String> {
-    let mut deflater = ZlibDecoder::new(inp);
+/// Decodes an export code or raw XML into the XML of the build,
+/// additionally returns whether the input was already a valid export code.
+fn decode_input(data: &str) -> Result<(String, bool)> {
+    let data = trim(data);
+
+    if data.starts_with('<') {
+        check_build(data)?;
+        return Ok((data.to_owned(), false));
+    }
+
+    let (data, base64_canonical) = decode(data)?;
+    let (data, compression_canonical) = inflate(&data, MAX_DECOMPRESSED_SIZE)?;
+    let xml = match decode_text(&data) {
+        Some(xml) => xml?,
+        None => windows_1252(&data)?,
+    };
+    check_build(&xml)?;
+
+    Ok((xml, base64_canonical && compression_canonical))
+}
+
+/// Trims whitespace and a byte order mark.
+fn trim(data: &str) -> &str {
+    data.trim_start_matches('\u{feff}').trim()
+}
+
+/// Decodes standard or URL-safe base64, ignoring whitespace.
+/// Additionally returns whether the input was URL-safe base64 without whitespace.
+fn decode(data: &str) -> Result<(Vec<u8>, bool)> {
+    let has_whitespace = data.contains(char::is_whitespace);
+    let is_standard = data.contains(['+', '/']);
+    if is_standard && data.contains(['-', '_']) {
+        return Err(Error::MixedBase64);
+    }
+
+    let config = match is_standard {
+        true => base64::STANDARD,
+        false => base64::URL_SAFE,
+    };
+    let data = match has_whitespace {
+        true => base64::decode_config(data.replace(char::is_whitespace, ""), config),
+        false => base64::decode_config(data, config),
+    }?;
+
+    Ok((data, !has_whitespace && !is_standard))
+}
+
+/// Decompresses zlib, gzip or raw deflate data.
+/// Decompressed data larger than `max_size` is rejected.
+/// Additionally returns whether the data was compressed with zlib, like PoB does.
+fn inflate(data: &[u8], max_size: u64) -> Result<(Vec<u8>, bool)> {
     let mut buf = Vec::new();
-    deflater.read_to_end(&mut buf).map_err(Error::Deflate)?;
-
-    match String::from_utf8(buf) {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            use encoding::{all::WINDOWS_1252, DecoderTrap, Encoding};
-            WINDOWS_1252
-                .decode(&e.into_bytes(), DecoderTrap::Strict)
-                .map_err(Error::StringDecode)
+    let is_zlib = match data {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0,
+        _ => false,
+    };
+
+    // Reads one byte past the limit to detect inputs exceeding it.
+    let limit = max_size + 1;
+    let result = match data {
+        _ if is_zlib => ZlibDecoder::new(data).take(limit).read_to_end(&mut buf),
+        [0x1f, 0x8b, ..] => GzDecoder::new(data).take(limit).read_to_end(&mut buf),
+        _ => DeflateDecoder::new(data).take(limit).read_to_end(&mut buf),
+    };
+    if let Err(err) = result {
+        // Codes which were cut off still decompress up to the point where they end.
+        return Err(match buf.starts_with(b"<") {
+            true => Error::Truncated,
+            false => Error::Deflate(err),
+        });
+    }
+
+    if buf.len() as u64 > max_size {
+        return Err(Error::TooLarge);
+    }
+
+    Ok((buf, is_zlib))
+}
+
+/// Decodes UTF-16 text with a byte order mark or UTF-8 text,
+/// `None` if the data is neither.
+fn decode_text(data: &[u8]) -> Option<Result<String>> {
+    let utf16 = |data: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let chunks = data.chunks_exact(2);
+        if !chunks.remainder().is_empty() {
+            return Err(Error::InvalidUtf16);
         }
+        let units = chunks.map(|c| from_bytes([c[0], c[1]])).collect::<Vec<_>>();
+        String::from_utf16(&units).map_err(|_| Error::InvalidUtf16)
+    };
+
+    match data {
+        [0xff, 0xfe, rest @ ..] => Some(utf16(rest, u16::from_le_bytes)),
+        [0xfe, 0xff, rest @ ..] => Some(utf16(rest, u16::from_be_bytes)),
+        _ => String::from_utf8(data.to_vec()).ok().map(Ok),
+    }
+}
+
+fn windows_1252(data: &[u8]) -> Result<String> {
+    use encoding::{all::WINDOWS_1252, DecoderTrap, Encoding};
+    WINDOWS_1252
+        .decode(data, DecoderTrap::Strict)
+        .map_err(Error::StringDecode)
+}
+
+/// Makes sure the XML is a complete build.
+fn check_build(xml: &str) -> Result<()> {
+    if xml.contains("</PathOfBuilding") {
+        Ok(())
+    } else if xml.contains("<PathOfBuilding") {
+        Err(Error::Truncated)
+    } else {
+        Err(Error::NotABuild)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static XML: &str = "<PathOfBuilding>\n\t<Notes>äöü ^7Notes</Notes>\n</PathOfBuilding>";
+
+    #[test]
+    fn compress_decompress() {
+        let compressed = compress(XML).unwrap();
+        assert!(!compressed.contains('+') && !compressed.contains('/'));
+        assert_eq!(decompress(&compressed).unwrap(), XML);
+    }
+
+    #[test]
+    fn decompress_raw_xml() {
+        assert_eq!(decompress(XML).unwrap(), XML);
+        assert_eq!(decompress(&format!("\u{feff}\n{XML}\r\n")).unwrap(), XML);
+        assert!(matches!(
+            decompress("<html><body>Hello</body></html>"),
+            Err(Error::NotABuild)
+        ));
+    }
+
+    #[test]
+    fn normalize_export() {
+        let export = compress(XML).unwrap();
+
+        assert_eq!(
+            normalize(export.as_bytes()).unwrap(),
+            (export.clone(), XML.to_owned())
+        );
+        assert_eq!(
+            normalize(format!(" {export}\r\n").as_bytes()).unwrap().0,
+            export
+        );
+
+        let wrapped = export
+            .as_bytes()
+            .chunks(20)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(normalize(wrapped.as_bytes()).unwrap().0, export);
+
+        let standard = export.replace('-', "+").replace('_', "/");
+        assert_eq!(normalize(standard.as_bytes()).unwrap().0, export);
+
+        assert_eq!(normalize(XML.as_bytes()).unwrap().0, export);
+
+        let utf16 = [0xff, 0xfe]
+            .into_iter()
+            .chain(export.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect::<Vec<_>>();
+        assert_eq!(normalize(&utf16).unwrap().0, export);
+
+        let bom = format!("\u{feff}{export}");
+        assert_eq!(normalize(bom.as_bytes()).unwrap().0, export);
+    }
+
+    #[test]
+    fn normalize_compression() {
+        use flate2::write::{DeflateEncoder, GzEncoder};
+
+        let mut gzip = GzEncoder::new(Vec::new(), Default::default());
+        gzip.write_all(XML.as_bytes()).unwrap();
+        let gzip = base64::encode_config(gzip.finish().unwrap(), base64::URL_SAFE);
+        assert_eq!(decompress(&gzip).unwrap(), XML);
+
+        let mut deflate = DeflateEncoder::new(Vec::new(), Default::default());
+        deflate.write_all(XML.as_bytes()).unwrap();
+        let deflate = base64::encode_config(deflate.finish().unwrap(), base64::URL_SAFE);
+        assert_eq!(decompress(&deflate).unwrap(), XML);
+
+        let export = compress(XML).unwrap();
+        assert_eq!(normalize(gzip.as_bytes()).unwrap().0, export);
+        assert_eq!(normalize(deflate.as_bytes()).unwrap().0, export);
+    }
+
+    #[test]
+    fn normalize_errors() {
+        let export = compress(XML).unwrap();
+
+        let mixed = format!("+{export}");
+        assert!(matches!(
+            normalize(mixed.as_bytes()),
+            Err(Error::MixedBase64)
+        ));
+
+        let truncated = &export[..export.len() / 2 / 4 * 4];
+        assert!(matches!(
+            normalize(truncated.as_bytes()),
+            Err(Error::Truncated)
+        ));
+
+        let not_a_build = "<html><body>Hello</body></html>";
+        assert!(matches!(
+            normalize(not_a_build.as_bytes()),
+            Err(Error::NotABuild)
+        ));
+
+        assert!(matches!(
+            normalize(&[0x80, 0x81]),
+            Err(Error::InvalidEncoding)
+        ));
+        assert!(matches!(
+            normalize(&[0xff, 0xfe, 0x00, 0xd8]),
+            Err(Error::InvalidUtf16)
+        ));
+        assert!(matches!(
+            normalize(b"not a build!"),
+            Err(Error::Base64Decode(_))
+        ));
+
+        let mut bomb = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        bomb.write_all(&[0; 1025]).unwrap();
+        let bomb = bomb.finish().unwrap();
+        assert!(matches!(inflate(&bomb, 1024), Err(Error::TooLarge)));
+        assert!(inflate(&bomb, 1025).is_ok());
     }
 }
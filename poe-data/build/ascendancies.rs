@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Directory of the passive tree exports, shared with the `poe-tree` crate.
+pub const TREE_DATA: &str = "../poe-tree/data";
+
+#[derive(Debug, Deserialize)]
+struct Tree {
+    nodes: BTreeMap<String, Node>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Node {
+    #[serde(default)]
+    skill: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    stats: Vec<String>,
+    ascendancy_name: Option<String>,
+    #[serde(default)]
+    is_notable: bool,
+}
+
+struct Notable {
+    id: u32,
+    name: String,
+    stats: Vec<String>,
+}
+
+/// Passive tree exports in `TREE_DATA`, ordered from the oldest to the newest version.
+fn tree_exports() -> anyhow::Result<Vec<PathBuf>> {
+    let mut trees = Vec::new();
+    for entry in std::fs::read_dir(TREE_DATA)? {
+        let path = entry?.path();
+        let version = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split_once('.'))
+            .and_then(|(major, minor)| {
+                Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?))
+            });
+        if let Some(version) = version {
+            trees.push((version, path));
+        }
+    }
+    trees.sort();
+    Ok(trees.into_iter().map(|(_, path)| path).collect())
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    // Notables of every ascendancy from the newest tree which contains the ascendancy,
+    // alternate ascendancies are only part of some trees.
+    let mut data = BTreeMap::<String, Vec<Notable>>::new();
+    for path in tree_exports()? {
+        let tree: Tree = serde_json::from_reader(std::fs::File::open(&path)?)?;
+
+        let mut notables = BTreeMap::<String, Vec<Notable>>::new();
+        for node in tree.nodes.into_values().filter(|node| node.is_notable) {
+            let Some(ascendancy) = node.ascendancy_name else {
+                continue;
+            };
+            notables.entry(ascendancy).or_default().push(Notable {
+                id: node.skill,
+                name: node.name,
+                stats: node.stats,
+            });
+        }
+        data.extend(notables);
+    }
+
+    let mut ascendancies = String::new();
+    let mut alternates = String::new();
+
+    for (ascendancy, mut notables) in data {
+        notables.sort_by_key(|notable| notable.id);
+
+        let mut arm = String::new();
+        write!(arm, "&[")?;
+        for notable in notables {
+            write!(
+                arm,
+                "Notable {{ id: {}, name: {:?}, stats: &{:?} }},",
+                notable.id, notable.name, notable.stats
+            )?;
+        }
+        write!(arm, "]")?;
+
+        // Ascendancies which can not be selected in a build are not needed.
+        if let Ok(asc) = ascendancy.parse::<shared::Ascendancy>() {
+            writeln!(ascendancies, "Ascendancy::{asc:?} => {arm},")?;
+        } else if let Ok(asc) = ascendancy.parse::<shared::AlternateAscendancy>() {
+            writeln!(alternates, "AlternateAscendancy::{asc:?} => {arm},")?;
+        }
+    }
+
+    writeln!(output, "use super::Notable;")?;
+    writeln!(output, "use shared::{{AlternateAscendancy, Ascendancy}};")?;
+
+    // Every ascendancy has to be in one of the trees, a missing ascendancy fails to compile.
+    writeln!(
+        output,
+        "pub fn notables(ascendancy: Ascendancy) -> &'static [Notable] {{ match ascendancy {{ {ascendancies} }} }}"
+    )?;
+    writeln!(
+        output,
+        "pub fn alternate_notables(ascendancy: AlternateAscendancy) -> &'static [Notable] {{ match ascendancy {{ {alternates} }} }}"
+    )?;
+
+    Ok(())
+}
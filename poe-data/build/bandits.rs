@@ -0,0 +1,36 @@
+use std::fmt::Write;
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Bandit {
+    /// `None` for killing all bandits.
+    id: Option<String>,
+    rewards: Vec<String>,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("bandits.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Bandit> = serde_json::from_reader(data)?;
+
+    let mut arms = String::new();
+
+    for bandit in data {
+        let bandit_match = match bandit.id {
+            Some(id) => format!("Some(Bandit::{:?})", id.parse::<shared::Bandit>()?),
+            None => "None".to_owned(),
+        };
+        writeln!(arms, "{bandit_match} => &{:?},", bandit.rewards)?;
+    }
+
+    writeln!(output, "use shared::Bandit;")?;
+    writeln!(
+        output,
+        "pub fn rewards(bandit: Option<Bandit>) -> &'static [&'static str] {{ match bandit {{ {arms} }} }}"
+    )?;
+
+    Ok(())
+}
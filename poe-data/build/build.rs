@@ -1,10 +1,22 @@
 use std::{env, fs::File, path::Path};
 
+mod ascendancies;
+mod bandits;
 mod gems;
+mod pantheon;
 
 pub fn main() -> anyhow::Result<()> {
-    let gems_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("gems.rs");
-    gems::generate(&mut File::create(gems_path)?)?;
+    println!("cargo:rerun-if-changed=build");
+    println!("cargo:rerun-if-changed=data");
+    println!("cargo:rerun-if-changed={}", ascendancies::TREE_DATA);
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    gems::generate(&mut File::create(out_dir.join("gems.rs"))?)?;
+    ascendancies::generate(&mut File::create(out_dir.join("ascendancies.rs"))?)?;
+    pantheon::generate(&mut File::create(out_dir.join("pantheon.rs"))?)?;
+    bandits::generate(&mut File::create(out_dir.join("bandits.rs"))?)?;
 
     Ok(())
 }
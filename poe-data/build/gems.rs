@@ -10,10 +10,22 @@ struct Gem {
     name: String,
     level: u8,
     color: String,
+    tags: Vec<String>,
+    #[serde(default)]
+    types: Vec<String>,
+    support: Option<Support>,
     #[serde(default)]
     vendors: Vec<Vendor>,
 }
 
+/// Skill types a support gem applies to, as in the `support_gem` object of the gem data export.
+#[derive(Debug, Deserialize)]
+struct Support {
+    allowed_types: Vec<String>,
+    #[serde(default)]
+    excluded_types: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Vendor {
     quest: String,
@@ -64,10 +76,20 @@ pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
         }
         write!(vendors, "]")?;
 
+        let support = match gem.support {
+            Some(support) => format!(
+                "Some(super::SupportRules {{ allowed_types: &{:?}, excluded_types: &{:?} }})",
+                support.allowed_types, support.excluded_types
+            ),
+            None => "None".to_owned(),
+        };
+
         let name = gem.name;
         let level = gem.level;
-        let value =
-            format!("Gem {{ name: {name:?}, color: {color}, level: {level}, vendors: {vendors} }}");
+        let value = format!(
+            "Gem {{ name: {name:?}, color: {color}, level: {level}, tags: &{:?}, types: &{:?}, support: {support}, vendors: {vendors} }}",
+            gem.tags, gem.types
+        );
         map.entry(gem.id, &value);
     }
 
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct God {
+    id: String,
+    souls: Vec<Soul>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Soul {
+    name: String,
+    stats: Vec<String>,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("pantheon.json");
+
+    let data = File::open(path)?;
+    let data: Vec<God> = serde_json::from_reader(data)?;
+
+    let mut major = String::new();
+    let mut minor = String::new();
+
+    for god in data {
+        // The first soul is the base soul of the god and named after it.
+        let base_soul = god.souls.first().map(|soul| soul.name.clone());
+
+        let mut souls = String::new();
+
+        write!(souls, "&[")?;
+        for soul in god.souls {
+            write!(
+                souls,
+                "Soul {{ name: {:?}, stats: &{:?} }},",
+                soul.name, soul.stats
+            )?;
+        }
+        write!(souls, "]")?;
+
+        if let Ok(major_god) = god.id.parse::<shared::PantheonMajorGod>() {
+            if base_soul.as_deref() != Some(major_god.name()) {
+                anyhow::bail!("pantheon god '{}' is missing its base soul", god.id);
+            }
+            writeln!(major, "PantheonMajorGod::{major_god:?} => {souls},")?;
+        } else if let Ok(minor_god) = god.id.parse::<shared::PantheonMinorGod>() {
+            if base_soul.as_deref() != Some(minor_god.name()) {
+                anyhow::bail!("pantheon god '{}' is missing its base soul", god.id);
+            }
+            writeln!(minor, "PantheonMinorGod::{minor_god:?} => {souls},")?;
+        } else {
+            anyhow::bail!("invalid pantheon god '{}'", god.id);
+        }
+    }
+
+    writeln!(output, "use super::Soul;")?;
+    writeln!(
+        output,
+        "use shared::{{PantheonMajorGod, PantheonMinorGod}};"
+    )?;
+
+    // Every god has to be present in the data, a missing god fails to compile.
+    writeln!(
+        output,
+        "pub fn major(god: PantheonMajorGod) -> &'static [Soul] {{ match god {{ {major} }} }}"
+    )?;
+    writeln!(
+        output,
+        "pub fn minor(god: PantheonMinorGod) -> &'static [Soul] {{ match god {{ {minor} }} }}"
+    )?;
+
+    Ok(())
+}
@@ -5,10 +5,31 @@ pub mod gems {
         pub name: &'static str,
         pub color: Color,
         pub level: u8,
+        /// Gem tags of active skills, e.g. `spell` or `projectile`.
+        pub tags: &'static [&'static str],
+        /// Skill types of active skills from the gem data export, e.g. `Projectile`.
+        pub types: &'static [&'static str],
+        /// Skill types a support gem applies to, `None` if not known.
+        pub support: Option<SupportRules>,
         pub vendors: &'static [Vendor],
     }
 
     impl Gem {
+        /// Whether the support gem `support` supports this gem.
+        ///
+        /// `None` if the skill types of this gem or the rules of the support are not known.
+        pub fn is_supported_by(&self, support: &Gem) -> Option<bool> {
+            let rules = support.support.as_ref()?;
+            if self.types.is_empty() {
+                return None;
+            }
+
+            let has_type = |ty: &&str| self.types.contains(ty);
+            let allowed =
+                rules.allowed_types.is_empty() || rules.allowed_types.iter().any(has_type);
+            Some(allowed && !rules.excluded_types.iter().any(has_type))
+        }
+
         pub fn vendors(&self, class: Class) -> impl Iterator<Item = &'static Vendor> + '_ {
             self.vendors
                 .iter()
@@ -16,6 +37,13 @@ pub mod gems {
         }
     }
 
+    pub struct SupportRules {
+        /// The supported skill needs at least one of these types, any skill if empty.
+        pub allowed_types: &'static [&'static str],
+        /// The supported skill may not have any of these types.
+        pub excluded_types: &'static [&'static str],
+    }
+
     pub struct Vendor {
         pub act: u8,
         pub npc: &'static str,
@@ -27,7 +55,56 @@ pub mod gems {
         data::GEMS.get(id)
     }
 
+    /// Finds a gem and its id by the in game name, e.g. `Void Manipulation Support`.
+    pub fn by_name(name: &str) -> Option<(&'static str, &'static Gem)> {
+        data::GEMS
+            .entries()
+            .find(|(_, gem)| gem.name == name)
+            .map(|(&id, gem)| (id, gem))
+    }
+
     mod data {
         include!(concat!(env!("OUT_DIR"), "/gems.rs"));
     }
 }
+
+pub mod ascendancies {
+    pub use self::data::{alternate_notables, notables};
+
+    pub struct Notable {
+        /// Passive tree node id.
+        pub id: u32,
+        pub name: &'static str,
+        pub stats: &'static [&'static str],
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/ascendancies.rs"));
+    }
+}
+
+pub mod pantheon {
+    pub use self::data::{major, minor};
+
+    /// A soul of a pantheon god, the first soul of a god is the base soul
+    /// and all following souls are its upgrades.
+    ///
+    /// Souls without stats have no stat text in the data yet.
+    pub struct Soul {
+        pub name: &'static str,
+        pub stats: &'static [&'static str],
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/pantheon.rs"));
+    }
+}
+
+pub mod bandits {
+    /// Rewards for helping a bandit, `None` for killing all bandits.
+    pub use self::data::rewards;
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/bandits.rs"));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -15,23 +15,256 @@ fn get_trees() -> impl Iterator<Item = String> {
 }
 
 pub fn main() -> anyhow::Result<()> {
+    // Keystones of all trees and of timeless jewel conquerors, by name.
+    let mut keystones = BTreeMap::new();
+
     for version in get_trees() {
         let data_path = Path::new(".").join("data").join(format!("{version}.json"));
 
         let data = std::fs::read_to_string(data_path)?;
+        let layout: serde_json::Value = serde_json::from_str(&data)?;
         let data: SkillTreeData = serde_json::from_str(&data)?;
 
         let dest_path = Path::new(&env::var_os("OUT_DIR").unwrap())
             .join(format!("tree{}.rs", version.replace('.', "_")));
         let mut output = File::create(dest_path)?;
 
-        generate(&data, &mut output)?;
+        let layout = Layout {
+            positions: node_positions(&layout),
+            expansion_jewels: expansion_jewels(&layout),
+            jewel_slots: jewel_slots(&layout),
+            start_nodes: start_nodes(&layout),
+        };
+        generate(&data, &layout, &mut output)?;
+
+        for node in data.nodes.values().filter(|node| node.is_keystone) {
+            keystones
+                .entry(node.name.clone())
+                .or_insert(Some(node.skill));
+        }
     }
 
+    for name in CONQUEROR_KEYSTONES {
+        keystones.entry(name.to_string()).or_insert(None);
+    }
+
+    let dest_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("keystones.rs");
+    generate_keystones(&keystones, &mut File::create(dest_path)?)?;
+
     Ok(())
 }
 
-fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()> {
+/// Keystones of timeless jewel conquerors, they replace keystones conquered by the jewel
+/// and are not part of the tree.
+const CONQUEROR_KEYSTONES: &[&str] = &[
+    "Chainbreaker",
+    "Corrupted Soul",
+    "Dance with Death",
+    "Divine Flesh",
+    "Immortal Ambition",
+    "Inner Conviction",
+    "Power of Purpose",
+    "Second Sight",
+    "Strength of Blood",
+    "Supreme Decadence",
+    "Supreme Grandstanding",
+    "Supreme Ostentation",
+    "Tempered by War",
+    "The Traitor",
+    "Transcendence",
+];
+
+fn generate_keystones(
+    keystones: &BTreeMap<String, Option<u32>>,
+    output: &mut dyn Write,
+) -> anyhow::Result<()> {
+    let keystones = keystones
+        .iter()
+        .map(|(name, &node)| (keystone_variant(name), name, node))
+        .collect::<Vec<_>>();
+
+    writeln!(output, "#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]")?;
+    writeln!(output, "#[non_exhaustive]")?;
+    writeln!(output, "pub enum Keystone {{")?;
+    for (variant, _, _) in &keystones {
+        writeln!(output, "    {variant},")?;
+    }
+    writeln!(output, "}}")?;
+
+    writeln!(output, "impl Keystone {{")?;
+
+    writeln!(output, "    pub const ALL: &'static [Keystone] = &[")?;
+    for (variant, _, _) in &keystones {
+        writeln!(output, "        Self::{variant},")?;
+    }
+    writeln!(output, "    ];")?;
+
+    writeln!(
+        output,
+        "    /// Node of the keystone, `None` for keystones which are not part of the tree."
+    )?;
+    writeln!(output, "    pub fn node(&self) -> Option<u32> {{")?;
+    writeln!(output, "        match *self {{")?;
+    for (variant, _, node) in &keystones {
+        writeln!(output, "            Self::{variant} => {node:?},")?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "    pub fn name(&self) -> &'static str {{")?;
+    writeln!(output, "        match *self {{")?;
+    for (variant, name, _) in &keystones {
+        writeln!(output, "            Self::{variant} => {name:?},")?;
+    }
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "    pub fn from_node(node: u32) -> Option<Self> {{")?;
+    writeln!(output, "        match node {{")?;
+    for (variant, _, node) in &keystones {
+        if let Some(node) = node {
+            writeln!(output, "            {node} => Some(Self::{variant}),")?;
+        }
+    }
+    writeln!(output, "            _ => None,")?;
+    writeln!(output, "        }}")?;
+    writeln!(output, "    }}")?;
+
+    writeln!(output, "}}")?;
+
+    Ok(())
+}
+
+/// Turns a keystone name into an enum variant, e.g. `Zealot's Oath` -> `ZealotsOath`.
+fn keystone_variant(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars().filter(|c| c.is_ascii_alphanumeric());
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + &chars.collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Positions of all nodes which are placed on the tree, calculated from their group and orbit.
+///
+/// The layout is read from the raw tree data, it is not part of the typed tree data.
+fn node_positions(layout: &serde_json::Value) -> HashMap<u32, (f32, f32)> {
+    let as_f64 = |value: &serde_json::Value| value.as_f64().unwrap_or_default();
+    let constants = &layout["constants"];
+    let orbit_radii = constants["orbitRadii"]
+        .as_array()
+        .map(|radii| radii.iter().map(as_f64).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let skills_per_orbit = constants["skillsPerOrbit"]
+        .as_array()
+        .map(|skills| skills.iter().map(as_f64).collect::<Vec<_>>())
+        .unwrap_or_default();
+    // Since 3.17 orbits with 16 and 40 nodes are not evenly spaced anymore.
+    let uneven_orbits = skills_per_orbit.contains(&16.0);
+
+    let mut positions = HashMap::new();
+    let Some(nodes) = layout["nodes"].as_object() else {
+        return positions;
+    };
+    for node in nodes.values() {
+        let (Some(skill), Some(group)) = (node["skill"].as_u64(), node["group"].as_u64()) else {
+            continue;
+        };
+        let group = &layout["groups"][group.to_string()];
+        let orbit = node["orbit"].as_u64().unwrap_or_default() as usize;
+        let orbit_index = as_f64(&node["orbitIndex"]);
+
+        let skills = skills_per_orbit.get(orbit).copied().unwrap_or(1.0);
+        let angle = match skills as u32 {
+            16 if uneven_orbits => ORBIT_ANGLES_16[orbit_index as usize % 16],
+            40 if uneven_orbits => ORBIT_ANGLES_40[orbit_index as usize % 40],
+            _ => 360.0 * orbit_index / skills,
+        }
+        .to_radians();
+        let radius = orbit_radii.get(orbit).copied().unwrap_or_default();
+
+        let x = as_f64(&group["x"]) + radius * angle.sin();
+        let y = as_f64(&group["y"]) - radius * angle.cos();
+        positions.insert(skill as u32, (x as f32, y as f32));
+    }
+
+    positions
+}
+
+/// Sockets for cluster jewels, as `(size, index, parent)`.
+fn expansion_jewels(layout: &serde_json::Value) -> HashMap<u32, (u64, u64, Option<u32>)> {
+    let Some(nodes) = layout["nodes"].as_object() else {
+        return HashMap::new();
+    };
+
+    nodes
+        .values()
+        .filter_map(|node| {
+            let skill = node["skill"].as_u64()? as u32;
+            let jewel = node["expansionJewel"].as_object()?;
+            let size = jewel.get("size")?.as_u64()?;
+            let index = jewel.get("index")?.as_u64()?;
+            let parent = jewel
+                .get("parent")
+                .and_then(|parent| parent.as_str()?.parse().ok());
+            Some((skill, (size, index, parent)))
+        })
+        .collect()
+}
+
+/// Jewel sockets in the order the character API refers to them.
+fn jewel_slots(layout: &serde_json::Value) -> Vec<u32> {
+    layout["jewelSlots"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|slot| slot.as_u64())
+        .map(|slot| slot as u32)
+        .collect()
+}
+
+/// Start nodes of the classes and of all ascendancies, including alternate ascendancies.
+fn start_nodes(layout: &serde_json::Value) -> Vec<u32> {
+    let Some(nodes) = layout["nodes"].as_object() else {
+        return Vec::new();
+    };
+
+    let mut start_nodes = nodes
+        .values()
+        .filter(|node| {
+            node.get("classStartIndex").is_some()
+                || node["isAscendancyStart"].as_bool() == Some(true)
+        })
+        .filter_map(|node| node["skill"].as_u64())
+        .map(|skill| skill as u32)
+        .collect::<Vec<_>>();
+    start_nodes.sort_unstable();
+    start_nodes
+}
+
+/// Parts of the tree data which are not part of the typed tree data.
+struct Layout {
+    positions: HashMap<u32, (f32, f32)>,
+    expansion_jewels: HashMap<u32, (u64, u64, Option<u32>)>,
+    jewel_slots: Vec<u32>,
+    start_nodes: Vec<u32>,
+}
+
+const ORBIT_ANGLES_16: [f64; 16] = [
+    0.0, 30.0, 45.0, 60.0, 90.0, 120.0, 135.0, 150.0, 180.0, 210.0, 225.0, 240.0, 270.0, 300.0,
+    315.0, 330.0,
+];
+
+const ORBIT_ANGLES_40: [f64; 40] = [
+    0.0, 10.0, 20.0, 30.0, 40.0, 45.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0, 130.0,
+    135.0, 140.0, 150.0, 160.0, 170.0, 180.0, 190.0, 200.0, 210.0, 220.0, 225.0, 230.0, 240.0,
+    250.0, 260.0, 270.0, 280.0, 290.0, 300.0, 310.0, 315.0, 320.0, 330.0, 340.0, 350.0,
+];
+
+fn generate(data: &SkillTreeData, layout: &Layout, output: &mut dyn Write) -> anyhow::Result<()> {
     let mut map = phf_codegen::Map::new();
 
     let alternate_ascendancies = data
@@ -49,7 +282,10 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
     };
 
     writeln!(output, "#[allow(unused_imports)]")?;
-    writeln!(output, "use crate::{{Kind, MasteryEffect, Node}};")?;
+    writeln!(
+        output,
+        "use crate::{{ExpansionJewel, Kind, MasteryEffect, Node}};"
+    )?;
 
     for node in data.nodes.values() {
         let kind = if node.is_mastery {
@@ -90,6 +326,18 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
                 .map(|icon| icon.strip_suffix(".png").unwrap_or(icon))
             // files on the cdn are lowercase (directly extracted from the bundle)
                 .map(|icon| icon.to_lowercase());
+        let position = layout.positions.get(&node.skill);
+        let expansion_jewel =
+            layout
+                .expansion_jewels
+                .get(&node.skill)
+                .map(|(size, index, parent)| {
+                    format!("ExpansionJewel {{ size: {size}, index: {index}, parent: {parent:?} }}")
+                });
+        let expansion_jewel = match expansion_jewel {
+            Some(expansion_jewel) => format!("Some({expansion_jewel})"),
+            None => "None".to_owned(),
+        };
 
         let n = format!(
             r#"Node {{ 
@@ -97,9 +345,14 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
                 name: "{}", 
                 stats: &{:?}, 
                 mastery_effects: &[{mastery_effects}], 
-                icon: {icon:?} 
+                icon: {icon:?},
+                position: {position:?},
+                expansion_jewel: {expansion_jewel},
+                is_ascendancy: {}
             }}"#,
-            node.name, node.stats
+            node.name,
+            node.stats,
+            node.ascendancy_name.is_some()
         );
 
         map.entry(node.skill, &n);
@@ -110,6 +363,16 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
         "pub static TREE: phf::Map<u32, Node> = {};",
         map.build()
     )?;
+    writeln!(
+        output,
+        "pub static JEWEL_SLOTS: &[u32] = &{:?};",
+        layout.jewel_slots
+    )?;
+    writeln!(
+        output,
+        "pub static START_NODES: &[u32] = &{:?};",
+        layout.start_nodes
+    )?;
 
     Ok(())
 }
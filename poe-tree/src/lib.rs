@@ -1,3 +1,9 @@
+mod keystones {
+    include!(concat!(env!("OUT_DIR"), "/keystones.rs"));
+}
+
+pub use self::keystones::Keystone;
+
 #[derive(Debug, Copy, Clone)]
 #[non_exhaustive]
 pub enum Kind {
@@ -34,6 +40,33 @@ pub struct Node {
     pub stats: &'static [&'static str],
     pub mastery_effects: &'static [MasteryEffect],
     pub icon: Option<&'static str>,
+    /// Position on the tree, `None` for nodes which are not placed on the tree,
+    /// e.g. cluster jewel notables.
+    pub position: Option<(f32, f32)>,
+    /// Set for jewel sockets which can hold a cluster jewel.
+    pub expansion_jewel: Option<ExpansionJewel>,
+    /// Whether the node belongs to an ascendancy, ascendancy points are separate from passive points.
+    pub is_ascendancy: bool,
+}
+
+/// A jewel socket which can hold a cluster jewel.
+#[derive(Debug)]
+pub struct ExpansionJewel {
+    /// Largest cluster jewel size the socket fits, `0` small, `1` medium and `2` large.
+    pub size: u8,
+    /// Index of the socket, part of the ids of the nodes added by the cluster jewel in it.
+    pub index: u8,
+    /// The larger socket which contains this socket.
+    pub parent: Option<u32>,
+}
+
+impl Node {
+    /// Distance between two nodes, `None` if one of them is not placed on the tree.
+    pub fn distance(&self, other: &Node) -> Option<f32> {
+        let (x1, y1) = self.position?;
+        let (x2, y2) = other.position?;
+        Some((x1 - x2).hypot(y1 - y2))
+    }
 }
 
 #[derive(Debug)]
@@ -83,6 +116,39 @@ macro_rules! gen {
                     _ => None,
                 }
             }
+
+            fn nodes(&self) -> Box<dyn Iterator<Item = (u32, &'static Node)>> {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => Box::new(self::$module::TREE.entries().map(|(&id, node)| (id, node))),
+                    )*
+                    #[allow(unreachable_patterns)]
+                    _ => Box::new(std::iter::empty()),
+                }
+            }
+
+            fn jewel_slots(&self) -> &'static [u32] {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::JEWEL_SLOTS,
+                    )*
+                    #[allow(unreachable_patterns)]
+                    _ => &[],
+                }
+            }
+
+            fn start_nodes(&self) -> &'static [u32] {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::START_NODES,
+                    )*
+                    #[allow(unreachable_patterns)]
+                    _ => &[],
+                }
+            }
         }
 
         impl std::str::FromStr for Version {
@@ -110,6 +176,18 @@ macro_rules! gen {
     };
 }
 
+impl Version {
+    /// Radius of a large jewel, e.g. a timeless jewel, jewel radii were increased with 3.16.
+    pub fn large_jewel_radius(&self) -> f32 {
+        match self {
+            #[cfg(feature = "tree-3_15")]
+            Self::V3_15 => 1500.0,
+            #[allow(unreachable_patterns)]
+            _ => 1800.0,
+        }
+    }
+}
+
 pub fn get_node_opt(version: &str, id: u32) -> Option<&'static Node> {
     version.parse::<Version>().ok().and_then(|v| v.get_node(id))
 }
@@ -118,6 +196,41 @@ pub fn get_node(version: Version, id: u32) -> Option<&'static Node> {
     version.get_node(id)
 }
 
+/// All nodes of the tree, including nodes which are not placed on the tree.
+pub fn nodes(version: Version) -> impl Iterator<Item = (u32, &'static Node)> {
+    version.nodes()
+}
+
+/// Jewel sockets of the tree, the character API identifies sockets by their index in this list.
+pub fn jewel_slots(version: Version) -> &'static [u32] {
+    version.jewel_slots()
+}
+
+/// Start nodes of the classes and ascendancies, they are allocated without costing a point.
+pub fn start_nodes(version: Version) -> &'static [u32] {
+    version.start_nodes()
+}
+
+/// Finds a node by its name, e.g. a cluster jewel notable.
+pub fn find_node(version: Version, name: &str) -> Option<(u32, &'static Node)> {
+    version.nodes().find(|(_, node)| node.name == name)
+}
+
+/// All nodes placed within `radius` of the node `center`, excluding the center itself.
+pub fn nodes_in_radius(
+    version: Version,
+    center: u32,
+    radius: f32,
+) -> impl Iterator<Item = (u32, &'static Node)> {
+    let center_node = version.get_node(center);
+    version.nodes().filter(move |&(id, node)| {
+        id != center
+            && center_node
+                .and_then(|center| center.distance(node))
+                .is_some_and(|distance| distance <= radius)
+    })
+}
+
 gen! {
     (V3_24, "/tree3_24.rs", tree3_24, "tree-3_24", "3_24" | "3.24"),
     (V3_23, "/tree3_23.rs", tree3_23, "tree-3_23", "3_23" | "3.23"),
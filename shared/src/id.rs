@@ -108,6 +108,10 @@ impl UserPasteId {
         format!("/u/{}/{}/json", self.user, self.id)
     }
 
+    pub fn to_snapshot_url(&self) -> String {
+        format!("/u/{}/{}/snapshot", self.user, self.id)
+    }
+
     pub fn to_pob_load_url(&self) -> String {
         // TODO: maybe get rid of this format?
         format!("/pob/{}:{}", self.user, self.id)
@@ -172,6 +176,18 @@ impl PasteId {
         }
     }
 
+    pub fn to_snapshot_url(&self) -> String {
+        match self {
+            Self::Paste(id) => format!("/{id}/snapshot"),
+            Self::UserPaste(up) => up.to_snapshot_url(),
+        }
+    }
+
+    /// Url of the structured difference from this paste to `other`.
+    pub fn to_diff_json_url(&self, other: &PasteId) -> String {
+        format!("/{self}/diff/{other}/json")
+    }
+
     pub fn to_pob_load_url(&self) -> String {
         // TODO: maybe this is just `format!("/pob/{}", self)
         match self {
@@ -7,7 +7,7 @@ pub mod validation;
 
 pub use id::{Id, InvalidId, InvalidPasteId, PasteId, UserPasteId};
 pub use poe::{
-    Ascendancy, AscendancyOrClass, Bandit, Class, ClassSet, Color, PantheonMajorGod,
-    PantheonMinorGod,
+    AlternateAscendancy, Ascendancy, AscendancyOrClass, Bandit, Class, ClassSet, Color,
+    PantheonMajorGod, PantheonMinorGod, Realm,
 };
 pub use user::{InvalidUser, User};
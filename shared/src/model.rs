@@ -33,6 +33,20 @@ pub struct PasteMetadata {
     pub rank: Option<NonZeroU8>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub private: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archetype: Option<PasteArchetype>,
+}
+
+/// Classification of the build, by name of the archetype variants.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PasteArchetype {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub damage_types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defenses: Vec<String>,
+    pub budget: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
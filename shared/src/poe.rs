@@ -312,6 +312,47 @@ impl FromStr for AscendancyOrClass {
     }
 }
 
+/// Secondary ascendancies which can be chosen in addition to the regular ascendancy.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlternateAscendancy {
+    Warden,
+    Warlock,
+    Primalist,
+}
+
+impl AlternateAscendancy {
+    /// Resolves the id stored by PoB in `secondaryAscendClassId`, `0` means none.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Warden),
+            2 => Some(Self::Warlock),
+            3 => Some(Self::Primalist),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warden => "Warden",
+            Self::Warlock => "Warlock",
+            Self::Primalist => "Primalist",
+        }
+    }
+}
+
+impl FromStr for AlternateAscendancy {
+    type Err = Invalid;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Warden" => Self::Warden,
+            "Warlock" => Self::Warlock,
+            "Primalist" => Self::Primalist,
+            _ => return Err(Invalid("Alternate Ascendancy")),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PantheonMajorGod {
     BrineKing,
@@ -326,7 +367,7 @@ impl PantheonMajorGod {
             Self::BrineKing => "Soul of the Brine King",
             Self::Lunaris => "Soul of Lunaris",
             Self::Solaris => "Soul of Solaris",
-            Self::Arakaali => "Sould of Arakaali",
+            Self::Arakaali => "Soul of Arakaali",
         }
     }
 }
@@ -378,8 +419,8 @@ impl FromStr for PantheonMinorGod {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "Gruthkul" => Self::Gruthkul,
-            "Lunaris" => Self::Yugul,
-            "Solaris" => Self::Abberath,
+            "Yugul" => Self::Yugul,
+            "Abberath" => Self::Abberath,
             "Tukohama" => Self::Tukohama,
             "Garukhan" => Self::Garukhan,
             "Ralakesh" => Self::Ralakesh,
@@ -420,6 +461,48 @@ impl FromStr for Bandit {
     }
 }
 
+/// Realm of a character on pathofexile.com.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Realm {
+    Pc,
+    Xbox,
+    Sony,
+}
+
+impl Realm {
+    pub const ALL: [Realm; 3] = [Self::Pc, Self::Xbox, Self::Sony];
+
+    /// Identifier of the realm used by the official website, e.g. `pc`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pc => "pc",
+            Self::Xbox => "xbox",
+            Self::Sony => "sony",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pc => "PC",
+            Self::Xbox => "Xbox",
+            Self::Sony => "PlayStation",
+        }
+    }
+}
+
+impl FromStr for Realm {
+    type Err = Invalid;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "pc" => Self::Pc,
+            "xbox" => Self::Xbox,
+            "sony" => Self::Sony,
+            _ => return Err(Invalid("Realm")),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,4 +521,31 @@ mod tests {
         assert_eq!(ClassSet::from_u8(0b11000001), ClassSet::from_u8(0b01000001));
         assert_eq!(ClassSet::all(), ClassSet::from_u8(0b01111111));
     }
+
+    #[test]
+    fn test_pantheon_ids() {
+        assert_eq!(
+            "TheBrineKing".parse::<PantheonMajorGod>().unwrap(),
+            PantheonMajorGod::BrineKing
+        );
+        assert_eq!(
+            "Yugul".parse::<PantheonMinorGod>().unwrap(),
+            PantheonMinorGod::Yugul
+        );
+        assert_eq!(
+            "Abberath".parse::<PantheonMinorGod>().unwrap(),
+            PantheonMinorGod::Abberath
+        );
+        assert!("Lunaris".parse::<PantheonMinorGod>().is_err());
+    }
+
+    #[test]
+    fn test_alternate_ascendancy() {
+        assert_eq!(AlternateAscendancy::from_id(0), None);
+        assert_eq!(
+            AlternateAscendancy::from_id(2),
+            Some(AlternateAscendancy::Warlock)
+        );
+        assert_eq!(AlternateAscendancy::from_id(4), None);
+    }
 }
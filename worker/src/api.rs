@@ -1,9 +1,15 @@
 use std::{borrow::Cow, num::NonZeroU8, rc::Rc, time::Duration};
 
-use pob::{PathOfBuilding, PathOfBuildingExt, SerdePathOfBuilding};
+use pob::{
+    archetype::BuildArchetype, snapshot::BuildSnapshot, PathOfBuilding, PathOfBuildingExt,
+    SerdePathOfBuilding,
+};
 use sentry::MetricUnit;
 use serde::{Deserialize, Serialize};
-use shared::{model::PasteMetadata, validation, PasteId, User, UserPasteId};
+use shared::{
+    model::{PasteArchetype, PasteMetadata},
+    validation, PasteId, Realm, User, UserPasteId,
+};
 
 use crate::{
     consts, crypto, poe_api,
@@ -11,7 +17,7 @@ use crate::{
     response,
     route::{self, DeleteEndpoints, GetEndpoints, PostEndpoints},
     statsd::{Counters, Distributions},
-    utils::{self, CacheControl, Etag, LenientId, RequestExt},
+    utils::{self, Cachability, CacheControl, Etag, LenientId, RequestExt},
     Error, Response, Result,
 };
 
@@ -64,6 +70,9 @@ pub async fn handle(rctx: &mut RequestContext, route: route::Api) -> response::R
         Get(PobUserPaste(user, LenientId(id))) => {
             handle_download_text(rctx, UserPasteId { user, id }.into()).await
         }
+        Get(PobCharacter(realm, account, character)) => {
+            handle_download_character(rctx, realm, account, character).await
+        }
         Get(Paste(id)) => handle_download_text(rctx, PasteId::Paste(id)).await,
         Get(UserPaste(user, id)) => {
             handle_download_text(rctx, UserPasteId { user, id }.into()).await
@@ -76,6 +85,11 @@ pub async fn handle(rctx: &mut RequestContext, route: route::Api) -> response::R
         Get(UserPasteXml(user, id)) => {
             handle_download_xml(rctx, UserPasteId { user, id }.into()).await
         }
+        Get(PasteSnapshot(id)) => handle_download_snapshot(rctx, PasteId::Paste(id)).await,
+        Get(UserPasteSnapshot(user, id)) => {
+            handle_download_snapshot(rctx, UserPasteId { user, id }.into()).await
+        }
+        Get(PasteDiffJson(before, after)) => handle_diff_json(rctx, before, after).await,
         Get(Login) => handle_login(rctx).await,
         Get(Oauht2Poe) => handle_oauth2_poe(rctx).await,
         // Post
@@ -149,6 +163,32 @@ async fn handle_download_text(rctx: &RequestContext, id: PasteId) -> Result<Resp
         .result()
 }
 
+#[tracing::instrument(skip(rctx))]
+async fn handle_download_character(
+    rctx: &RequestContext,
+    realm: Realm,
+    account: String,
+    character: String,
+) -> Result<Response> {
+    // Every import fetches from pathofexile.com, only allow it for logged in users.
+    validate_access!(rctx.is_logged_in());
+
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let character = storage.get_character(realm, &account, &character).await?;
+
+    // Characters change and the import is specific to the session, do not cache it on the edge.
+    Response::ok()
+        .body(character.content)
+        .content_type("text/plain")
+        .etag(Etag::strong(&character.etag))
+        .cache(
+            CacheControl::default()
+                .cachability(Cachability::Private)
+                .max_age(Duration::from_secs(300)),
+        )
+        .result()
+}
+
 #[tracing::instrument(skip(rctx))]
 async fn handle_download_json(rctx: &RequestContext, id: PasteId) -> Result<Response> {
     let pastes = rctx.inject::<crate::pastes::Pastes>();
@@ -193,6 +233,51 @@ async fn handle_download_xml(rctx: &RequestContext, id: PasteId) -> Result<Respo
         .result()
 }
 
+#[tracing::instrument(skip(rctx))]
+async fn handle_download_snapshot(rctx: &RequestContext, id: PasteId) -> Result<Response> {
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let (meta, snapshot) = pastes
+        .get_snapshot(&id)
+        .await?
+        .ok_or_else(|| Error::NotFound("paste", id.to_string()))?;
+
+    // The snapshot format can change independently of the paste.
+    let etag = format!("{}-{}", meta.etag, snapshot.version);
+
+    Response::ok()
+        .json(&snapshot)
+        .etag(Etag::strong(&etag))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_diff_json(
+    rctx: &RequestContext,
+    before: PasteId,
+    after: PasteId,
+) -> Result<Response> {
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let (meta, diff) = pastes.get_diff(&before, &after).await?;
+
+    // Anonymous pastes never change, user pastes can be edited and diffs
+    // are not purged from the cache when that happens.
+    let cache_for = match before.user().is_some() || after.user().is_some() {
+        true => consts::CACHE_A_BIT,
+        false => consts::CACHE_FOREVER,
+    };
+
+    Response::ok()
+        .json(&diff)
+        .etag(Etag::strong(&meta.etag))
+        .cache(CacheControl::default().public().s_max_age(cache_for))
+        .result()
+}
+
 #[tracing::instrument(skip(rctx))]
 async fn handle_delete_paste(rctx: &RequestContext, id: PasteId) -> Result<Response> {
     let storage = rctx.inject::<crate::storage::Storage>();
@@ -236,10 +321,10 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
     tracing::info!(?data.id, data.as_user, ?data.title, ?data.custom_id, size = content.len(), "upload");
     sentry::add_attachment_plain(content.clone(), "pob.txt");
 
-    let pob = validate_pob(rctx.is_logged_in(), &content)?;
+    let (pob, content) = validate_pob(rctx.is_logged_in(), &content)?;
     let mut metadata = to_metadata(&pob);
 
-    let sha1 = crypto::sha1(&content).await?;
+    let sha1 = crypto::sha1(content.as_bytes()).await?;
 
     let id = if data.as_user {
         let session = rctx.session().ok_or_else(|| {
@@ -289,7 +374,10 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
 
     tracing::debug!("--> uploading paste '{}'", id);
     let storage = rctx.inject::<crate::storage::Storage>();
-    storage.put(&id, &sha1, &content, Some(&metadata)).await?;
+    storage
+        .put(&id, &sha1, content.as_bytes(), Some(&metadata))
+        .await?;
+    storage.put_snapshot(&id, &BuildSnapshot::new(&pob)).await?;
     tracing::debug!("<-- paste uploaded");
 
     let response = Response::ok().json(&id).meta_paste(&id, metadata);
@@ -306,15 +394,18 @@ async fn handle_pob_upload(rctx: &mut RequestContext) -> Result<Response> {
     tracing::info!(size = data.len(), "pob upload");
     sentry::add_attachment_plain(data.clone(), "pob.txt");
 
-    let pob = validate_pob(rctx.is_logged_in(), &data)?;
+    let (pob, content) = validate_pob(rctx.is_logged_in(), &data)?;
     let metadata = to_metadata(&pob);
 
-    let sha1 = crypto::sha1(&data).await?;
+    let sha1 = crypto::sha1(content.as_bytes()).await?;
     let id = PasteId::Paste(utils::hash_to_short_id(&sha1));
 
     tracing::debug!("--> uploading paste '{}'", id);
     let storage = rctx.inject::<crate::storage::Storage>();
-    storage.put(&id, &sha1, &data, Some(&metadata)).await?;
+    storage
+        .put(&id, &sha1, content.as_bytes(), Some(&metadata))
+        .await?;
+    storage.put_snapshot(&id, &BuildSnapshot::new(&pob)).await?;
     tracing::debug!("<-- paste uploaing ...");
 
     let response = Response::ok()
@@ -326,7 +417,9 @@ async fn handle_pob_upload(rctx: &mut RequestContext) -> Result<Response> {
     Ok(response)
 }
 
-fn validate_pob(is_logged_in: bool, data: &[u8]) -> Result<SerdePathOfBuilding> {
+/// Validates an uploaded build, returns the parsed build and the upload
+/// normalized into a PoB export code, which is what gets stored.
+fn validate_pob(is_logged_in: bool, data: &[u8]) -> Result<(SerdePathOfBuilding, String)> {
     let limit = if is_logged_in {
         consts::MAX_UPLOAD_SIZE_LOGGED_IN
     } else {
@@ -335,30 +428,35 @@ fn validate_pob(is_logged_in: bool, data: &[u8]) -> Result<SerdePathOfBuilding>
 
     sentry::distribution(Distributions::PobSize, data.len() as f64).unit(MetricUnit::Byte);
 
+    // Checked on the upload itself, before anything is decoded or decompressed.
     if data.len() > limit {
         return Err(Error::BadRequest(
             "Paste too large, please login and use the website".to_owned(),
         ));
     }
 
-    let s = std::str::from_utf8(data)
-        .map_err(|_| "invalid content".to_owned())
-        .map_err(Error::BadRequest)?;
-
     // Generic 401, probably just actually bad data
-    let s = pob::decompress(s).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let (content, s) = pob::normalize(data).map_err(|e| Error::BadRequest(e.to_string()))?;
     // More specific error for a separate Sentry categoy
-    let pob = SerdePathOfBuilding::from_xml(&s).map_err(move |e| Error::InvalidPoB(e, s))?;
+    let (pob, warnings) =
+        SerdePathOfBuilding::from_xml_lenient(&s).map_err(move |e| Error::InvalidPoB(e, s))?;
+    for warning in &warnings {
+        tracing::info!("partially broken build: {warning}");
+    }
 
     sentry::counter(Counters::PobUpload)
         .inc(1)
+        .tag(
+            "partial",
+            if warnings.is_empty() { "false" } else { "true" },
+        )
         .tag("class", pob.class().as_str())
         .tag(
             "ascendancy",
             pob.ascendancy().map_or("None", |a| a.as_str()),
         );
 
-    Ok(pob)
+    Ok((pob, content))
 }
 
 fn to_metadata(pob: &SerdePathOfBuilding) -> PasteMetadata {
@@ -369,6 +467,25 @@ fn to_metadata(pob: &SerdePathOfBuilding) -> PasteMetadata {
         main_skill_name: pob.main_skill_name().map(|x| x.to_owned()),
         rank: None,
         private: false,
+        archetype: Some(to_archetype(pob)),
+    }
+}
+
+fn to_archetype(pob: &SerdePathOfBuilding) -> PasteArchetype {
+    let archetype = BuildArchetype::classify(pob);
+    PasteArchetype {
+        damage_types: archetype
+            .damage_types
+            .iter()
+            .map(|dt| dt.name().to_owned())
+            .collect(),
+        delivery: archetype.delivery.map(|d| d.name().to_owned()),
+        defenses: archetype
+            .defenses
+            .iter()
+            .map(|d| d.name().to_owned())
+            .collect(),
+        budget: archetype.budget.name().to_owned(),
     }
 }
 
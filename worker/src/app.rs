@@ -102,6 +102,26 @@ async fn build_context(
             let location = UserPasteId { user, id }.to_paste_url();
             (ResponseInfo::redirect(location), Context::not_found())
         }
+        Diff(before, after) => {
+            let pastes = rctx.inject::<crate::pastes::Pastes>();
+            match pastes.get_diff(&before, &after).await {
+                Ok((meta, diff)) => {
+                    let mut info = ResponseInfo::default().with_etag(meta.etag);
+                    // User pastes can be edited, the diff is not purged when that happens.
+                    if before.user().is_some() || after.user().is_some() {
+                        info.cache_control = CacheControl::default()
+                            .public()
+                            .s_max_age(consts::CACHE_A_BIT);
+                    }
+                    (info, Context::diff(before, after, diff))
+                }
+                Err(Error::InvalidId(..)) | Err(Error::NotFound(..)) => (
+                    ResponseInfo::default().with_etag("not_found"),
+                    Context::not_found(),
+                ),
+                Err(err) => return Err(err),
+            }
+        }
     };
 
     Ok((info, ctx))
@@ -2,7 +2,7 @@ use shared::PasteId;
 
 use crate::{
     request_context::RequestContext,
-    route::{Api, Route},
+    route::{Api, GetEndpoints, Route},
     Response,
 };
 
@@ -63,7 +63,16 @@ impl From<&RequestContext> for CacheEntry {
         // This may also fail for URLs with credentials (-> MDN) on Firefox only,
         // we're not running on Firefox, also we should never have credentials in URLs at
         // this point.
-        let key = value.req().inner().clone().expect("clone request");
+        let key = match value.route() {
+            Route::Api(Api::Get(
+                GetEndpoints::PasteSnapshot(_) | GetEndpoints::UserPasteSnapshot(..),
+            )) => {
+                let mut url = value.url().expect("request url");
+                url.set_query(Some(&snapshot_version_query()));
+                web_sys::Request::new_with_str(url.as_str()).expect("snapshot cache key")
+            }
+            _ => value.req().inner().clone().expect("clone request"),
+        };
         let ctx = value.ctx().clone();
 
         Self {
@@ -86,8 +95,7 @@ impl Cache {
         let session = rctx.session();
 
         match rctx.route() {
-            Route::App(app::Route::User(user))
-            | Route::Api(Api::Get(crate::route::GetEndpoints::User(user))) => {
+            Route::App(app::Route::User(user)) | Route::Api(Api::Get(GetEndpoints::User(user))) => {
                 if Some(user) == session.map(|s| &s.name) {
                     Cache::Owned
                 } else {
@@ -112,6 +120,12 @@ impl std::fmt::Display for Cache {
     }
 }
 
+/// Snapshots are cached forever, their cache key includes the snapshot format version
+/// to not serve snapshots in an older format after a deployment.
+fn snapshot_version_query() -> String {
+    format!("v={}", pob::snapshot::VERSION)
+}
+
 pub(crate) fn on_paste_change(rctx: &RequestContext, id: PasteId) {
     let url = rctx.url().unwrap();
     rctx.ctx().wait_until(on_paste_change_async(url, id));
@@ -138,6 +152,11 @@ pub(crate) async fn on_paste_change_async(mut url: url::Url, id: PasteId) {
     clear!(id.to_url());
     clear!(id.to_raw_url());
     clear!(id.to_json_url());
+    clear!(format!(
+        "{}?{}",
+        id.to_snapshot_url(),
+        snapshot_version_query()
+    ));
     clear!(id.to_pob_load_url());
 
     if let PasteId::UserPaste(up) = id {
@@ -23,5 +23,8 @@ pub const ENV_STATS_TOKEN: &str = "STATS_TOKEN";
 
 pub const OAUTH_SCOPE: &str = "account:profile";
 
+/// User agent for the public, unauthenticated character window of pathofexile.com.
+pub const CHARACTER_WINDOW_USER_AGENT: &str = "pobbin/1.0 (contact: ggg@pobb.in)";
+
 pub const CACHE_A_BIT: Duration = Duration::from_secs(21600); // 6 Hours
 pub const CACHE_FOREVER: Duration = Duration::from_secs(31536000);
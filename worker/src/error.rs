@@ -29,6 +29,9 @@ pub enum Error {
     #[error("Access Denied")]
     AccessDenied,
 
+    #[error("The profile of '{0}' is private, make the characters tab public to import it")]
+    PrivateProfile(String),
+
     #[error("Missing Authorization Grant")]
     MissingAuthorizationGrant,
 
@@ -73,6 +76,7 @@ impl Error {
             Self::Worker(..) => "Worker",
             Self::BadRequest(..) => "BadRequest",
             Self::AccessDenied => "AccessDenied",
+            Self::PrivateProfile(..) => "PrivateProfile",
             Self::MissingAuthorizationGrant => "MissingAuthorizationGrant",
             Self::AuthorizationGrantError(..) => "AuthorizationGrantError",
             Self::InvalidSessionState => "InvalidSessionState",
@@ -92,6 +96,7 @@ impl Error {
             Self::NotFound(..) | Self::InvalidId(..) => 404,
             Self::BadRequest(..) | Self::InvalidPoB(..) => 400,
             Self::AccessDenied
+            | Self::PrivateProfile(..)
             | Self::MissingAuthorizationGrant
             | Self::AuthorizationGrantError(..)
             | Self::InvalidSessionState => 403,
@@ -114,6 +119,7 @@ impl Error {
             Self::Worker(..) => Level::Error,
             Self::BadRequest(..) => Level::Info,
             Self::AccessDenied => Level::Info,
+            Self::PrivateProfile(..) => Level::Info,
             Self::MissingAuthorizationGrant => Level::Warning,
             Self::AuthorizationGrantError(..) => Level::Warning,
             Self::InvalidSessionState => Level::Info,
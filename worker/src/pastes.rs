@@ -1,6 +1,9 @@
 use std::collections::{BTreeMap, HashMap};
 
-use pob::{PathOfBuilding, SerdePathOfBuilding};
+use pob::{
+    diff::BuildDiff, snapshot::BuildSnapshot, JewelKind, PathOfBuilding, PathOfBuildingExt,
+    SerdePathOfBuilding,
+};
 use shared::{
     model::{
         data::{self, NodeStat},
@@ -33,7 +36,8 @@ impl Pastes {
             return Ok(None);
         };
 
-        let pob = SerdePathOfBuilding::from_export(&stored.content)
+        // Partially broken builds still render, the app reports the warnings itself.
+        let (pob, _) = SerdePathOfBuilding::from_export_lenient(&stored.content)
             .map_err(|e| crate::Error::InvalidPoB(e, String::new()))?;
 
         let paste = Paste {
@@ -53,6 +57,61 @@ impl Pastes {
         Ok(Some((meta, paste)))
     }
 
+    /// Loads the snapshot stored with the paste at upload time.
+    ///
+    /// Pastes without a snapshot in the current format are parsed instead.
+    pub async fn get_snapshot(&self, id: &PasteId) -> crate::Result<Option<(Meta, BuildSnapshot)>> {
+        if let Some(stored) = self.storage.get_snapshot(id).await? {
+            let meta = Meta {
+                etag: stored.entity_id,
+            };
+            return Ok(Some((meta, stored.snapshot)));
+        }
+
+        let Some(stored) = self.storage.get(id).await? else {
+            return Ok(None);
+        };
+
+        let (pob, _) = SerdePathOfBuilding::from_export_lenient(&stored.content)
+            .map_err(|e| crate::Error::InvalidPoB(e, String::new()))?;
+
+        let meta = Meta {
+            etag: stored.entity_id,
+        };
+
+        Ok(Some((meta, BuildSnapshot::new(&pob))))
+    }
+
+    /// Loads both pastes and computes the difference from `before` to `after`.
+    pub async fn get_diff(
+        &self,
+        before: &PasteId,
+        after: &PasteId,
+    ) -> crate::Result<(Meta, BuildDiff)> {
+        let load = |id: &PasteId| {
+            let id = id.clone();
+            async move {
+                let stored = self
+                    .storage
+                    .get(&id)
+                    .await?
+                    .ok_or_else(|| crate::Error::NotFound("paste", id.to_string()))?;
+                let (pob, _) = SerdePathOfBuilding::from_export_lenient(&stored.content)
+                    .map_err(|e| crate::Error::InvalidPoB(e, String::new()))?;
+                Ok::<_, crate::Error>((stored.entity_id, pob))
+            }
+        };
+
+        let (before_etag, before) = load(before).await?;
+        let (after_etag, after) = load(after).await?;
+
+        let meta = Meta {
+            etag: format!("{before_etag}-{after_etag}"),
+        };
+
+        Ok((meta, pob::diff::diff(&before, &after)))
+    }
+
     pub async fn list_pastes(
         &self,
         session: Session<'_>,
@@ -126,6 +185,7 @@ fn extract_node_info(pob: &impl PathOfBuilding) -> Vec<data::Nodes> {
                 sort: node.kind.is_keystone().then_some(0),
             })
             .collect::<Vec<_>>();
+        keystones.extend(jewel_nodes(pob, &spec));
         keystones.sort_unstable_by(|a, b| (a.sort, &a.name).cmp(&(b.sort, &b.name)));
 
         struct MasteryNode {
@@ -166,6 +226,61 @@ fn extract_node_info(pob: &impl PathOfBuilding) -> Vec<data::Nodes> {
     data
 }
 
+/// Passives granted by jewels, notables of cluster jewels and the nodes conquered by timeless jewels.
+///
+/// Stats of the nodes are attributed to the jewel socket.
+fn jewel_nodes(pob: &impl PathOfBuilding, spec: &pob::TreeSpec) -> Vec<data::Node> {
+    let mut nodes = Vec::new();
+
+    for jewel in pob.jewels(spec) {
+        match jewel.kind {
+            JewelKind::Cluster(cluster) => {
+                let notables = cluster.notables.iter().filter_map(|notable| {
+                    let (_, node) = notable.node?;
+                    Some(data::Node {
+                        name: node.name.to_owned(),
+                        icon: node.icon.map(|icon| icon.to_owned()),
+                        stats: stats_to_owned(jewel.node_id, node.stats),
+                        sort: Some(1),
+                    })
+                });
+                nodes.extend(notables);
+            }
+            JewelKind::Timeless(timeless) if !timeless.conquered.is_empty() => {
+                let mut stats = vec![format!(
+                    "Conquered by {} ({}), seed {}",
+                    timeless.conqueror,
+                    timeless.kind.faction(),
+                    timeless.seed
+                )];
+                stats.extend(
+                    timeless
+                        .conquered
+                        .iter()
+                        .filter(|(_, node)| node.kind.is_keystone() || node.kind.is_notable())
+                        .map(|(_, node)| format!("Conquers {}", node.name)),
+                );
+
+                nodes.push(data::Node {
+                    name: timeless.kind.name().to_owned(),
+                    icon: None,
+                    stats: stats
+                        .into_iter()
+                        .map(|text| NodeStat {
+                            id: jewel.node_id,
+                            text,
+                        })
+                        .collect(),
+                    sort: Some(1),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    nodes
+}
+
 fn stats_to_owned(id: u32, stats: &[&str]) -> Vec<NodeStat> {
     stats
         .iter()
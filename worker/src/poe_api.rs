@@ -9,7 +9,7 @@ use crate::{
 };
 
 const OAUTH_AUTHORIZE_URL: &str = "https://www.pathofexile.com/oauth/authorize";
-const POE_API_USER_AGENT: &str = "OAuth pobbin/1.0 (contact: ggg@pobb.in)";
+pub(crate) const POE_API_USER_AGENT: &str = "OAuth pobbin/1.0 (contact: ggg@pobb.in)";
 
 pub struct AuthorizationGrant<'a> {
     pub code: Cow<'a, str>,
@@ -292,8 +292,8 @@ impl Response {
     /// A response without caching headers is not cacheable,
     /// becaue it wouldn't be cached for any duration.
     ///
-    /// Also returns false when the response has status code 206
-    /// or contains the `Vary: *` header.
+    /// Also returns false when the response has status code 206,
+    /// contains the `Vary: *` header or is marked as private.
     ///
     /// See also: https://developers.cloudflare.com/workers/runtime-apis/cache/#parameters
     pub fn is_cacheable(&self) -> bool {
@@ -303,6 +303,10 @@ impl Response {
         if self.headers.get("Vary").unwrap().as_deref() == Some("*") {
             return false;
         }
+        let cache_control = self.headers.get("Cache-Control").unwrap();
+        if cache_control.is_some_and(|cc| cc.contains("private")) {
+            return false;
+        }
         ["Cache-Control", "ETag", "Expires", "Last-Modified"]
             .into_iter()
             .any(|hn| self.headers.has(hn).unwrap())
@@ -1,5 +1,5 @@
 use app::PercentRoute;
-use shared::{Id, PasteId, User};
+use shared::{Id, PasteId, Realm, User};
 use worker::{Method, Request};
 
 use crate::{assets, utils::LenientId};
@@ -88,12 +88,24 @@ pub enum GetEndpoints {
     PasteXml(Id),
     #[to("/u/<name>/<id>/xml")]
     UserPasteXml(User, Id),
+    /// Versioned JSON snapshot of the parsed build, see `pob::snapshot`.
+    #[to("/<id>/snapshot")]
+    PasteSnapshot(Id),
+    #[to("/u/<name>/<id>/snapshot")]
+    UserPasteSnapshot(User, Id),
+    /// Structured difference between two pastes.
+    /// Both IDs are stringified `PasteId`s, e.g. `abc` or `user:abc`.
+    #[to("/<id>/diff/<other>/json")]
+    PasteDiffJson(PasteId, PasteId),
     /// Path of Building endpoint for importing builds.
     /// This supports the anonymous and user scoped paste IDs.
     /// User scoped paste IDs are used in `pob://` protocol links.
     /// Anonymous paste IDs are coming from importing an anonymous build URL in PoB.
     #[to("/pob/<id>")]
     PobPaste(LenientId<PasteId>),
+    /// Build of a character on pathofexile.com, as Path of Building export.
+    #[to("/pob/character/<realm>/<account>/<character>")]
+    PobCharacter(Realm, String, String),
     /// Path of Building endpoint for importing user paste URLs.
     #[to("/pob/u/<name>/<id>")]
     PobUserPaste(User, LenientId<Id>),
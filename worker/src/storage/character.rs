@@ -0,0 +1,73 @@
+use pob::{CharacterItems, CharacterPassives, SerdePathOfBuilding};
+use serde::de::DeserializeOwned;
+use shared::Realm;
+
+use crate::{consts, crypto, net, Error, Result};
+
+const CHARACTER_WINDOW_URL: &str = "https://www.pathofexile.com/character-window";
+
+/// A character of the official website, converted into a build.
+#[derive(Debug)]
+pub struct Character {
+    /// Path of Building export of the character.
+    pub content: String,
+    /// Hash of the export, changes whenever the character changes.
+    pub etag: String,
+}
+
+/// Imports a character from the official website as a build.
+///
+/// Only characters of public profiles can be imported.
+#[tracing::instrument]
+pub async fn get(realm: Realm, account: &str, character: &str) -> Result<Character> {
+    let items = fetch::<CharacterItems>("get-items", realm, account, character).await?;
+    let passives =
+        fetch::<CharacterPassives>("get-passive-skills", realm, account, character).await?;
+
+    let pob = SerdePathOfBuilding::from_character(&items, &passives)
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+    let content = pob
+        .to_export()
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+
+    let etag = crypto::sha1(content.as_bytes())
+        .await?
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+
+    Ok(Character { content, etag })
+}
+
+async fn fetch<T: DeserializeOwned>(
+    endpoint: &str,
+    realm: Realm,
+    account: &str,
+    character: &str,
+) -> Result<T> {
+    let url = url::Url::parse_with_params(
+        &format!("{CHARACTER_WINDOW_URL}/{endpoint}"),
+        &[
+            ("accountName", account),
+            ("character", character),
+            ("realm", realm.as_str()),
+        ],
+    )
+    .map_err(|e| Error::Error(e.to_string()))?;
+
+    let mut response = net::Request::get(url.as_str())
+        .header("User-Agent", consts::CHARACTER_WINDOW_USER_AGENT)
+        .tag("character")
+        .send()
+        .await?;
+
+    match response.status_code() {
+        200 => Ok(serde_json::from_str(&response.text().await?)?),
+        404 => Err(Error::NotFound(
+            "character",
+            format!("{account}/{character}"),
+        )),
+        403 => Err(Error::PrivateProfile(account.to_owned())),
+        code => Err(Error::RemoteFailed(code, format!("{endpoint} failed"))),
+    }
+}
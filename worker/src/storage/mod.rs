@@ -1,7 +1,8 @@
+use pob::snapshot::BuildSnapshot;
 use serde::{Deserialize, Serialize};
 use shared::{
     model::{ListPaste, PasteMetadata},
-    PasteId, User,
+    PasteId, Realm, User,
 };
 
 use crate::{
@@ -11,11 +12,13 @@ use crate::{
     Result,
 };
 
+mod character;
 mod pastebin;
 mod r2;
 mod utils;
 
-pub(crate) use utils::{strip_prefix, to_path_r2, to_prefix_r2};
+pub use character::Character;
+pub(crate) use utils::{strip_prefix, to_path_r2, to_prefix_r2, to_snapshot_path_r2};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StoredPaste {
@@ -27,6 +30,12 @@ pub struct StoredPaste {
     pub content: String,
 }
 
+#[derive(Debug)]
+pub struct StoredSnapshot {
+    pub entity_id: String,
+    pub snapshot: BuildSnapshot,
+}
+
 pub struct Storage {
     r2: r2::R2Storage,
 }
@@ -55,6 +64,35 @@ impl Storage {
         self.r2.get(id).await
     }
 
+    /// Imports a character from pathofexile.com, the content is generated and not stored.
+    pub async fn get_character(
+        &self,
+        realm: Realm,
+        account: &str,
+        character: &str,
+    ) -> Result<Character> {
+        tracing::info!("fetching character from pathofexile.com");
+        sentry::counter(Counters::StorageGet)
+            .inc(1)
+            .tag("type", "character");
+        character::get(realm, account, character).await
+    }
+
+    /// The snapshot stored next to a paste at upload time, see [`Self::put_snapshot`].
+    ///
+    /// Pastes stored before their snapshot format existed, and pastebin pastes, have none.
+    pub async fn get_snapshot(&self, id: &PasteId) -> Result<Option<StoredSnapshot>> {
+        if pastebin::could_be_pastebin_id(id) {
+            return Ok(None);
+        }
+
+        sentry::counter(Counters::StorageGet)
+            .inc(1)
+            .tag("type", "snapshot");
+        self.r2.get_snapshot(id).await
+    }
+
+    /// Deletes the paste and its snapshot.
     pub async fn delete(&self, id: &PasteId) -> Result<()> {
         sentry::counter(Counters::StorageDelete).inc(1);
         self.r2.delete(id).await
@@ -71,6 +109,11 @@ impl Storage {
         self.r2.put(id, sha1, data, metadata).await
     }
 
+    pub async fn put_snapshot(&self, id: &PasteId, snapshot: &BuildSnapshot) -> Result<()> {
+        sentry::counter(Counters::StoragePut).inc(1);
+        self.r2.put_snapshot(id, snapshot).await
+    }
+
     pub async fn list(&self, user: &User) -> Result<Vec<ListPaste>> {
         sentry::counter(Counters::StorageList).inc(1);
         self.r2.list(user).await
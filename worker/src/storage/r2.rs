@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use pob::snapshot::BuildSnapshot;
 use serde::{Deserialize, Serialize};
 use shared::{
     model::{ListPaste, PasteMetadata},
@@ -7,7 +8,7 @@ use shared::{
 };
 use worker::{Bucket, HttpMetadata, Include, Object};
 
-use super::StoredPaste;
+use super::{StoredPaste, StoredSnapshot};
 use crate::{
     crypto::Sha1,
     request_context::{Env, FromEnv},
@@ -64,11 +65,34 @@ impl R2Storage {
         }))
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn get_snapshot(&self, id: &PasteId) -> Result<Option<StoredSnapshot>> {
+        let path = super::to_snapshot_path_r2(id)?;
+
+        let obj = retry::retry_all(3, |_| self.bucket.get(&path).execute()).await?;
+
+        let Some(obj) = obj else {
+            return Ok(None);
+        };
+
+        let snapshot = match obj.body() {
+            Some(body) => serde_json::from_str(&body.text().await?)?,
+            None => return Ok(None),
+        };
+
+        Ok(Some(StoredSnapshot {
+            entity_id: obj.etag(),
+            snapshot,
+        }))
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn delete(&self, id: &PasteId) -> Result<()> {
         let path = super::to_path_r2(id)?;
+        let snapshot_path = super::to_snapshot_path_r2(id)?;
 
         retry::retry_all(3, |_| self.bucket.delete(&path)).await?;
+        retry::retry_all(3, |_| self.bucket.delete(&snapshot_path)).await?;
 
         Ok(())
     }
@@ -109,6 +133,25 @@ impl R2Storage {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, snapshot))]
+    pub async fn put_snapshot(&self, id: &PasteId, snapshot: &BuildSnapshot) -> Result<()> {
+        let path = super::to_snapshot_path_r2(id)?;
+        let data = serde_json::to_vec(snapshot)?;
+
+        retry::retry_all(3, |_| {
+            self.bucket
+                .put(&path, worker::Data::Bytes(data.as_slice()))
+                .http_metadata(HttpMetadata {
+                    content_type: Some("application/json".to_owned()),
+                    ..Default::default()
+                })
+                .execute()
+        })
+        .await?;
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn list(&self, user: &User) -> Result<Vec<ListPaste>> {
         let prefix = super::to_prefix_r2(user);
@@ -9,6 +9,16 @@ pub(crate) fn to_path_r2(id: &PasteId) -> Result<String> {
     }
 }
 
+/// Snapshots are kept outside of the paste prefixes, so they are not listed as pastes,
+/// and are keyed by the snapshot format version.
+pub(crate) fn to_snapshot_path_r2(id: &PasteId) -> Result<String> {
+    Ok(format!(
+        "snapshots/v{}/{}",
+        pob::snapshot::VERSION,
+        to_path_r2(id)?
+    ))
+}
+
 pub(crate) fn to_prefix_r2(user: &User) -> String {
     format!("users/{}/pastes/", user.normalized())
 }
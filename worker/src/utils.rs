@@ -63,7 +63,6 @@ pub fn to_link(p: &[app::Prefetch], rel: &str) -> String {
 #[derive(Copy, Clone, Debug)]
 pub enum Cachability {
     Public,
-    #[allow(dead_code)]
     Private,
     #[allow(dead_code)]
     NoCache,